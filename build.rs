@@ -23,6 +23,7 @@ fn main() {
 struct ToolInfo {
     name: String,
     description: String,
+    read_only: bool,
     args_type: String,
     function_path: String,
 }
@@ -60,6 +61,14 @@ fn parse_mcp_tool_from_file(content: &str, file_path: &Path) -> Option<ToolInfo>
     // Extract name and description
     let name = extract_attribute_value(attr_content, "name")?;
     let description = extract_attribute_value(attr_content, "description")?;
+    // Required so that a tool that modifies Azure DevOps can never be exposed
+    // by --read-only for lack of a marker
+    let read_only = extract_attribute_bool(attr_content, "read_only").unwrap_or_else(|| {
+        panic!(
+            "{}: #[mcp_tool] requires read_only = true or read_only = false",
+            file_path.display()
+        )
+    });
 
     // Find the function signature after the attribute
     let fn_start = content[mcp_tool_start + attr_end..].find("pub async fn ")?;
@@ -77,7 +86,10 @@ fn parse_mcp_tool_from_file(content: &str, file_path: &Path) -> Option<ToolInfo>
     // Extract args type (second parameter)
     let args_start = fn_sig.find("args:")? + "args:".len();
     let args_end = fn_sig[args_start..].find(')')?;
-    let args_type = fn_sig[args_start..args_start + args_end].trim().to_string();
+    let args_type = fn_sig[args_start..args_start + args_end]
+        .trim()
+        .trim_end_matches(',')
+        .to_string();
 
     // Build function path from file path
     let function_path = build_function_path(file_path, fn_name);
@@ -85,6 +97,7 @@ fn parse_mcp_tool_from_file(content: &str, file_path: &Path) -> Option<ToolInfo>
     Some(ToolInfo {
         name,
         description,
+        read_only,
         args_type,
         function_path,
     })
@@ -97,6 +110,19 @@ fn extract_attribute_value(attr: &str, key: &str) -> Option<String> {
     Some(attr[start..start + end].to_string())
 }
 
+fn extract_attribute_bool(attr: &str, key: &str) -> Option<bool> {
+    let pattern = format!("{} = ", key);
+    let start = attr.find(&pattern)? + pattern.len();
+    let value = &attr[start..];
+    if value.starts_with("true") {
+        Some(true)
+    } else if value.starts_with("false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
 fn build_function_path(file_path: &Path, fn_name: &str) -> String {
     // Convert file path to module path using components for cross-platform compatibility
     let components: Vec<_> = file_path
@@ -141,6 +167,9 @@ fn generate_tool_router_code(tools: &[ToolInfo]) -> String {
         "use crate::mcp::tools::organizations::{GetCurrentUserArgs, ListOrganizationsArgs};\n",
     );
     code.push_str("use crate::mcp::tools::projects::ListProjectsArgs;\n");
+    code.push_str(
+        "use crate::mcp::tools::queries::{CreateQueryArgs, ListQueriesArgs, RunSavedQueryArgs};\n",
+    );
     code.push_str("use crate::mcp::tools::tags::ListTagsArgs;\n");
     code.push_str("use crate::mcp::tools::teams::{\n");
     code.push_str(
@@ -149,12 +178,23 @@ fn generate_tool_router_code(tools: &[ToolInfo]) -> String {
     code.push_str(
         "    boards::{GetBoardArgs, ListBoardColumnsArgs, ListBoardRowsArgs, ListBoardsArgs},\n",
     );
+    code.push_str("    capacity::{\n");
+    code.push_str(
+        "        GetSprintCapacityArgs, GetSprintRemainingCapacityArgs, SetMemberCapacityArgs,\n",
+    );
+    code.push_str("        SetTeamDaysOffArgs,\n");
+    code.push_str("    },\n");
     code.push_str("};\n");
     code.push_str("use crate::mcp::tools::work_item_types::ListWorkItemTypesArgs;\n");
     code.push_str("use crate::mcp::tools::work_items::{\n");
-    code.push_str("    AddCommentArgs, CreateWorkItemArgs, GetWorkItemArgs, GetWorkItemsArgs, LinkWorkItemsArgs,\n");
+    code.push_str("    AddAttachmentArgs, AddCommentArgs, BatchWorkItemsArgs, CreateWorkItemArgs, GetAttachmentArgs,\n");
+    code.push_str("    GetWorkItemArgs,\n");
+    code.push_str(
+        "    GetWorkItemHistoryArgs, GetWorkItemTreeArgs, GetWorkItemsArgs, LinkWorkItemsArgs,\n",
+    );
+    code.push_str("    ListAttachmentsArgs, ListWorkItemRelationsArgs, QueryWorkItemsArgs,\n");
     code.push_str(
-        "    QueryWorkItemsArgs, QueryWorkItemsArgsWiql, UpdateCommentArgs, UpdateWorkItemArgs,\n",
+        "    QueryWorkItemsArgsWiql, UnlinkWorkItemsArgs, UpdateCommentArgs, UpdateWorkItemArgs,\n",
     );
     code.push_str("};\n");
     code.push_str("use rmcp::{\n");
@@ -166,33 +206,36 @@ fn generate_tool_router_code(tools: &[ToolInfo]) -> String {
 
     code.push_str("#[tool_router]\nimpl AzureMcpServer {\n");
     code.push_str("    pub fn new(client: AzureDevOpsClient) -> Self {\n");
-    code.push_str("        Self {\n");
-    code.push_str("            client: Arc::new(client),\n");
-    code.push_str("            tool_router: Self::tool_router(),\n");
-    code.push_str("        }\n");
+    code.push_str("        Self::from_client(Arc::new(client))\n");
     code.push_str("    }\n\n");
     code.push_str("    #[cfg(feature = \"test-support\")]\n");
     code.push_str(
         "    pub fn new_with_api(client: impl AzureDevOpsApi + Send + Sync + 'static) -> Self {\n",
     );
-    code.push_str("        Self {\n");
-    code.push_str("            client: Arc::new(client),\n");
-    code.push_str("            tool_router: Self::tool_router(),\n");
-    code.push_str("        }\n");
+    code.push_str("        Self::from_client(Arc::new(client))\n");
     code.push_str("    }\n\n");
 
     for tool in tools {
+        // The arguments are taken as a raw object so that the organization,
+        // project and team can be filled from the defaults before they are
+        // deserialized; the schema marks those as optional accordingly.
         code.push_str(&format!(
-            "    #[tool(description = \"{}\")]\n",
-            tool.description
+            "    #[tool(description = \"{}\", input_schema = crate::mcp::defaults::defaulted_input_schema::<{}>(), annotations(read_only_hint = {}))]\n",
+            tool.description, tool.args_type, tool.read_only
         ));
         code.push_str(&format!("    async fn {}(\n", tool.name));
         code.push_str("        &self,\n");
-        code.push_str(&format!("        args: Parameters<{}>,\n", tool.args_type));
+        code.push_str("        args: Parameters<rmcp::model::JsonObject>,\n");
+        code.push_str("        context: rmcp::service::RequestContext<rmcp::RoleServer>,\n");
         code.push_str("    ) -> Result<CallToolResult, McpError> {\n");
         code.push_str(&format!(
-            "        {}(&*self.client, args.0).await\n",
-            tool.function_path
+            "        let args: {} = self.resolve_args(args.0)?;\n",
+            tool.args_type
+        ));
+        code.push_str("        let client = self.client_for(&context)?;\n");
+        code.push_str(&format!(
+            "        crate::metrics::observe_tool_call(\"{}\", {}(&*client, args)).await\n",
+            tool.name, tool.function_path
         ));
         code.push_str("    }\n\n");
     }
@@ -7,11 +7,15 @@ use syn::{parse_macro_input, ItemFn};
 /// This macro validates the required metadata and passes through the function.
 /// The build script will scan for these attributes and generate the router code.
 ///
+/// `read_only` must be `false` for tools that modify Azure DevOps; it is
+/// published as the tool's `readOnlyHint` and used by `--read-only` to hide them.
+///
 /// Usage:
 /// ```rust
 /// #[mcp_tool(
 ///     name = "azdo_list_iteration_paths",
-///     description = "List iteration paths for a project or team"
+///     description = "List iteration paths for a project or team",
+///     read_only = true
 /// )]
 /// pub async fn list_iteration_paths(
 ///     client: &AzureDevOpsClient,
@@ -26,8 +30,11 @@ pub fn mcp_tool(attr: TokenStream, item: TokenStream) -> TokenStream {
 
     // Validate that we have the required attributes
     let attr_str = attr.to_string();
-    if !attr_str.contains("name") || !attr_str.contains("description") {
-        panic!("mcp_tool attribute requires both 'name' and 'description' parameters");
+    if !attr_str.contains("name")
+        || !attr_str.contains("description")
+        || !attr_str.contains("read_only")
+    {
+        panic!("mcp_tool attribute requires 'name', 'description' and 'read_only' parameters");
     }
 
     // Just pass through the function unchanged
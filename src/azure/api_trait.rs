@@ -1,22 +1,33 @@
 use async_trait::async_trait;
 use serde_json::Value;
 
+use crate::azure::attachments::AttachmentReference;
 use crate::azure::boards::{BoardColumn, BoardDetail, BoardRow, BoardSummary, Team, WorkItemType};
+use crate::azure::capacity::{CapacityPatch, DateRange, TeamCapacity, TeamMemberCapacity};
 use crate::azure::classification_nodes::ClassificationNode;
 use crate::azure::client::{AzureDevOpsClient, AzureError};
+use crate::azure::git::GitRepository;
 use crate::azure::iterations::TeamSettingsIteration;
-use crate::azure::models::WorkItem;
+use crate::azure::models::{
+    WorkItem, WorkItemBatchResponse, WorkItemQueryPage, WorkItemRelation, WorkItemRelationType,
+    WorkItemUpdate, WorkItemWithRelations,
+};
 use crate::azure::organizations::{Organization, Profile};
 use crate::azure::projects::Project;
+use crate::azure::queries::QueryHierarchyItem;
 use crate::azure::tags::TagDefinition;
 use crate::azure::teams::TeamMember;
+use crate::azure::work_items::WorkItemBatchRequest;
 use crate::azure::{
-    boards, classification_nodes, iterations, organizations, projects, tags, teams, work_items,
+    attachments, boards, capacity, classification_nodes, git, iterations, organizations, projects,
+    queries, tags, teams, work_items,
 };
 
 #[cfg_attr(feature = "test-support", mockall::automock)]
 #[async_trait]
 pub trait AzureDevOpsApi {
+    /// Succeeds when a credential source can provide an authorization header
+    async fn check_credentials(&self) -> Result<(), AzureError>;
     async fn get_profile(&self) -> Result<Profile, AzureError>;
     async fn list_organizations(&self, member_id: &str) -> Result<Vec<Organization>, AzureError>;
     async fn list_projects(&self, organization: &str) -> Result<Vec<Project>, AzureError>;
@@ -104,6 +115,14 @@ pub trait AzureDevOpsApi {
         ids: &[u32],
         include_latest_n_comments: Option<i32>,
     ) -> Result<Vec<WorkItem>, AzureError>;
+    /// Gets work items with their links, leaving out those that do not exist
+    /// or are not visible
+    async fn get_work_items_with_relations(
+        &self,
+        organization: &str,
+        project: &str,
+        ids: &[u32],
+    ) -> Result<Vec<WorkItemWithRelations>, AzureError>;
     async fn create_work_item(
         &self,
         organization: &str,
@@ -112,13 +131,16 @@ pub trait AzureDevOpsApi {
         fields: &[(String, Value)],
         multiline_fields_format: &[(String, String)],
     ) -> Result<WorkItem, AzureError>;
+    #[allow(clippy::too_many_arguments)]
     async fn update_work_item(
         &self,
         organization: &str,
         project: &str,
         id: u32,
         fields: &[(String, Value)],
+        clear_fields: &[String],
         multiline_fields_format: &[(String, String)],
+        expected_rev: Option<u32>,
     ) -> Result<WorkItem, AzureError>;
     async fn add_comment(
         &self,
@@ -142,16 +164,73 @@ pub trait AzureDevOpsApi {
         organization: &str,
         project: &str,
         source_id: u32,
-        target_id: u32,
-        link_type: &str,
+        relation: WorkItemRelation,
     ) -> Result<Value, AzureError>;
+    async fn unlink_work_items(
+        &self,
+        organization: &str,
+        project: &str,
+        source_id: u32,
+        target_url: &str,
+        link_type: Option<String>,
+    ) -> Result<Option<Value>, AzureError>;
+    async fn list_work_item_relation_types(
+        &self,
+        organization: &str,
+    ) -> Result<Vec<WorkItemRelationType>, AzureError>;
+    async fn get_repository(
+        &self,
+        organization: &str,
+        project: &str,
+        repository: &str,
+    ) -> Result<GitRepository, AzureError>;
+    async fn batch_work_items(
+        &self,
+        organization: &str,
+        requests: Vec<WorkItemBatchRequest>,
+    ) -> Result<Vec<WorkItemBatchResponse>, AzureError>;
     async fn query_work_items(
         &self,
         organization: &str,
         project: &str,
         query: &str,
+        skip: usize,
+        top: usize,
         include_latest_n_comments: Option<i32>,
-    ) -> Result<Vec<WorkItem>, AzureError>;
+    ) -> Result<WorkItemQueryPage, AzureError>;
+    /// Runs the saved query `query_id` and returns one page of its results
+    async fn run_saved_query(
+        &self,
+        organization: &str,
+        project: &str,
+        query_id: &str,
+        skip: usize,
+        top: usize,
+        include_latest_n_comments: Option<i32>,
+    ) -> Result<WorkItemQueryPage, AzureError>;
+    /// Lists the root query folders and their content down to `depth` levels
+    async fn list_queries(
+        &self,
+        organization: &str,
+        project: &str,
+        depth: u32,
+    ) -> Result<Vec<QueryHierarchyItem>, AzureError>;
+    /// Gets a saved query or folder by ID or path
+    async fn get_query(
+        &self,
+        organization: &str,
+        project: &str,
+        id_or_path: &str,
+        depth: u32,
+    ) -> Result<QueryHierarchyItem, AzureError>;
+    async fn create_query(
+        &self,
+        organization: &str,
+        project: &str,
+        parent: &str,
+        name: &str,
+        wiql: &str,
+    ) -> Result<QueryHierarchyItem, AzureError>;
     async fn get_team_iterations(
         &self,
         organization: &str,
@@ -159,10 +238,93 @@ pub trait AzureDevOpsApi {
         team_id: &str,
         timeframe: Option<String>,
     ) -> Result<Vec<TeamSettingsIteration>, AzureError>;
+    async fn get_team_iteration(
+        &self,
+        organization: &str,
+        project: &str,
+        team_id: &str,
+        iteration_id: &str,
+    ) -> Result<TeamSettingsIteration, AzureError>;
+    /// Lowercase names of the team's working days, e.g. "monday"
+    async fn get_team_working_days(
+        &self,
+        organization: &str,
+        project: &str,
+        team_id: &str,
+    ) -> Result<Vec<String>, AzureError>;
+    async fn get_iteration_capacities(
+        &self,
+        organization: &str,
+        project: &str,
+        team_id: &str,
+        iteration_id: &str,
+    ) -> Result<TeamCapacity, AzureError>;
+    async fn update_member_capacity(
+        &self,
+        organization: &str,
+        project: &str,
+        team_id: &str,
+        iteration_id: &str,
+        team_member_id: &str,
+        patch: CapacityPatch,
+    ) -> Result<TeamMemberCapacity, AzureError>;
+    async fn get_team_days_off(
+        &self,
+        organization: &str,
+        project: &str,
+        team_id: &str,
+        iteration_id: &str,
+    ) -> Result<Vec<DateRange>, AzureError>;
+    /// Replaces the team days off of an iteration
+    async fn set_team_days_off(
+        &self,
+        organization: &str,
+        project: &str,
+        team_id: &str,
+        iteration_id: &str,
+        days_off: Vec<DateRange>,
+    ) -> Result<Vec<DateRange>, AzureError>;
+    async fn get_work_item_relations(
+        &self,
+        organization: &str,
+        project: &str,
+        id: u32,
+    ) -> Result<Vec<WorkItemRelation>, AzureError>;
+    async fn get_work_item_updates(
+        &self,
+        organization: &str,
+        project: &str,
+        id: u32,
+    ) -> Result<Vec<WorkItemUpdate>, AzureError>;
+    async fn upload_attachment(
+        &self,
+        organization: &str,
+        project: &str,
+        file_name: &str,
+        content: Vec<u8>,
+    ) -> Result<AttachmentReference, AzureError>;
+    async fn add_attachment_to_work_item(
+        &self,
+        organization: &str,
+        project: &str,
+        work_item_id: u32,
+        attachment_url: &str,
+        comment: Option<String>,
+    ) -> Result<WorkItem, AzureError>;
+    async fn get_attachment(
+        &self,
+        organization: &str,
+        project: &str,
+        attachment_id: &str,
+        max_size: u64,
+    ) -> Result<Vec<u8>, AzureError>;
 }
 
 #[async_trait]
 impl AzureDevOpsApi for AzureDevOpsClient {
+    async fn check_credentials(&self) -> Result<(), AzureError> {
+        self.get_authorization().await.map(|_| ())
+    }
     async fn get_profile(&self) -> Result<Profile, AzureError> {
         organizations::get_profile(self).await
     }
@@ -299,6 +461,14 @@ impl AzureDevOpsApi for AzureDevOpsClient {
         work_items::get_work_items(self, organization, project, ids, include_latest_n_comments)
             .await
     }
+    async fn get_work_items_with_relations(
+        &self,
+        organization: &str,
+        project: &str,
+        ids: &[u32],
+    ) -> Result<Vec<WorkItemWithRelations>, AzureError> {
+        work_items::get_work_items_with_relations(self, organization, project, ids).await
+    }
     async fn create_work_item(
         &self,
         organization: &str,
@@ -317,13 +487,16 @@ impl AzureDevOpsApi for AzureDevOpsClient {
         )
         .await
     }
+    #[allow(clippy::too_many_arguments)]
     async fn update_work_item(
         &self,
         organization: &str,
         project: &str,
         id: u32,
         fields: &[(String, Value)],
+        clear_fields: &[String],
         multiline_fields_format: &[(String, String)],
+        expected_rev: Option<u32>,
     ) -> Result<WorkItem, AzureError> {
         work_items::update_work_item(
             self,
@@ -331,7 +504,9 @@ impl AzureDevOpsApi for AzureDevOpsClient {
             project,
             id,
             fields,
+            clear_fields,
             multiline_fields_format,
+            expected_rev,
         )
         .await
     }
@@ -370,28 +545,116 @@ impl AzureDevOpsApi for AzureDevOpsClient {
         organization: &str,
         project: &str,
         source_id: u32,
-        target_id: u32,
-        link_type: &str,
+        relation: WorkItemRelation,
     ) -> Result<Value, AzureError> {
-        work_items::link_work_items(self, organization, project, source_id, target_id, link_type)
-            .await
+        work_items::link_work_items(self, organization, project, source_id, &relation).await
+    }
+    async fn unlink_work_items(
+        &self,
+        organization: &str,
+        project: &str,
+        source_id: u32,
+        target_url: &str,
+        link_type: Option<String>,
+    ) -> Result<Option<Value>, AzureError> {
+        work_items::unlink_work_items(
+            self,
+            organization,
+            project,
+            source_id,
+            target_url,
+            link_type.as_deref(),
+        )
+        .await
+    }
+    async fn list_work_item_relation_types(
+        &self,
+        organization: &str,
+    ) -> Result<Vec<WorkItemRelationType>, AzureError> {
+        work_items::list_work_item_relation_types(self, organization).await
+    }
+    async fn get_repository(
+        &self,
+        organization: &str,
+        project: &str,
+        repository: &str,
+    ) -> Result<GitRepository, AzureError> {
+        git::get_repository(self, organization, project, repository).await
+    }
+    async fn batch_work_items(
+        &self,
+        organization: &str,
+        requests: Vec<WorkItemBatchRequest>,
+    ) -> Result<Vec<WorkItemBatchResponse>, AzureError> {
+        work_items::batch_work_items(self, organization, requests).await
     }
     async fn query_work_items(
         &self,
         organization: &str,
         project: &str,
         query: &str,
+        skip: usize,
+        top: usize,
         include_latest_n_comments: Option<i32>,
-    ) -> Result<Vec<WorkItem>, AzureError> {
+    ) -> Result<WorkItemQueryPage, AzureError> {
         work_items::query_work_items(
             self,
             organization,
             project,
             query,
+            skip,
+            top,
+            include_latest_n_comments,
+        )
+        .await
+    }
+    async fn run_saved_query(
+        &self,
+        organization: &str,
+        project: &str,
+        query_id: &str,
+        skip: usize,
+        top: usize,
+        include_latest_n_comments: Option<i32>,
+    ) -> Result<WorkItemQueryPage, AzureError> {
+        work_items::run_saved_query(
+            self,
+            organization,
+            project,
+            query_id,
+            skip,
+            top,
             include_latest_n_comments,
         )
         .await
     }
+    async fn list_queries(
+        &self,
+        organization: &str,
+        project: &str,
+        depth: u32,
+    ) -> Result<Vec<QueryHierarchyItem>, AzureError> {
+        queries::list_queries(self, organization, project, depth).await
+    }
+    async fn get_query(
+        &self,
+        organization: &str,
+        project: &str,
+        id_or_path: &str,
+        depth: u32,
+    ) -> Result<QueryHierarchyItem, AzureError> {
+        queries::get_query(self, organization, project, id_or_path, depth).await
+    }
+    async fn create_query(
+        &self,
+        organization: &str,
+        project: &str,
+        parent: &str,
+        name: &str,
+        wiql: &str,
+    ) -> Result<QueryHierarchyItem, AzureError> {
+        queries::create_query(self, organization, project, parent, name, wiql).await
+    }
     async fn get_team_iterations(
         &self,
         organization: &str,
@@ -402,4 +665,122 @@ impl AzureDevOpsApi for AzureDevOpsClient {
         iterations::get_team_iterations(self, organization, project, team_id, timeframe.as_deref())
             .await
     }
+    async fn get_team_iteration(
+        &self,
+        organization: &str,
+        project: &str,
+        team_id: &str,
+        iteration_id: &str,
+    ) -> Result<TeamSettingsIteration, AzureError> {
+        iterations::get_team_iteration(self, organization, project, team_id, iteration_id).await
+    }
+    async fn get_team_working_days(
+        &self,
+        organization: &str,
+        project: &str,
+        team_id: &str,
+    ) -> Result<Vec<String>, AzureError> {
+        iterations::get_team_working_days(self, organization, project, team_id).await
+    }
+    async fn get_iteration_capacities(
+        &self,
+        organization: &str,
+        project: &str,
+        team_id: &str,
+        iteration_id: &str,
+    ) -> Result<TeamCapacity, AzureError> {
+        capacity::get_iteration_capacities(self, organization, project, team_id, iteration_id).await
+    }
+    async fn update_member_capacity(
+        &self,
+        organization: &str,
+        project: &str,
+        team_id: &str,
+        iteration_id: &str,
+        team_member_id: &str,
+        patch: CapacityPatch,
+    ) -> Result<TeamMemberCapacity, AzureError> {
+        capacity::update_member_capacity(
+            self,
+            organization,
+            project,
+            team_id,
+            iteration_id,
+            team_member_id,
+            &patch,
+        )
+        .await
+    }
+    async fn get_team_days_off(
+        &self,
+        organization: &str,
+        project: &str,
+        team_id: &str,
+        iteration_id: &str,
+    ) -> Result<Vec<DateRange>, AzureError> {
+        capacity::get_team_days_off(self, organization, project, team_id, iteration_id).await
+    }
+    async fn set_team_days_off(
+        &self,
+        organization: &str,
+        project: &str,
+        team_id: &str,
+        iteration_id: &str,
+        days_off: Vec<DateRange>,
+    ) -> Result<Vec<DateRange>, AzureError> {
+        capacity::set_team_days_off(self, organization, project, team_id, iteration_id, days_off)
+            .await
+    }
+    async fn get_work_item_relations(
+        &self,
+        organization: &str,
+        project: &str,
+        id: u32,
+    ) -> Result<Vec<WorkItemRelation>, AzureError> {
+        work_items::get_work_item_relations(self, organization, project, id).await
+    }
+    async fn get_work_item_updates(
+        &self,
+        organization: &str,
+        project: &str,
+        id: u32,
+    ) -> Result<Vec<WorkItemUpdate>, AzureError> {
+        work_items::get_work_item_updates(self, organization, project, id).await
+    }
+    async fn upload_attachment(
+        &self,
+        organization: &str,
+        project: &str,
+        file_name: &str,
+        content: Vec<u8>,
+    ) -> Result<AttachmentReference, AzureError> {
+        attachments::upload_attachment(self, organization, project, file_name, content).await
+    }
+    async fn add_attachment_to_work_item(
+        &self,
+        organization: &str,
+        project: &str,
+        work_item_id: u32,
+        attachment_url: &str,
+        comment: Option<String>,
+    ) -> Result<WorkItem, AzureError> {
+        attachments::add_attachment_to_work_item(
+            self,
+            organization,
+            project,
+            work_item_id,
+            attachment_url,
+            comment.as_deref(),
+        )
+        .await
+    }
+    async fn get_attachment(
+        &self,
+        organization: &str,
+        project: &str,
+        attachment_id: &str,
+        max_size: u64,
+    ) -> Result<Vec<u8>, AzureError> {
+        attachments::get_attachment(self, organization, project, attachment_id, max_size).await
+    }
 }
@@ -0,0 +1,86 @@
+//! Relation types and URIs for links from work items to resources other than
+//! work items: hyperlinks and artifact links to commits, pull requests and builds.
+
+/// Relation type of a link to an arbitrary URL
+pub const HYPERLINK_REL: &str = "Hyperlink";
+
+/// Relation type of a link to a versioned artifact (commit, pull request, build)
+pub const ARTIFACT_LINK_REL: &str = "ArtifactLink";
+
+/// Link names Azure DevOps shows for artifact links, stored in the relation's
+/// `name` attribute
+pub const COMMIT_LINK_NAME: &str = "Fixed in Commit";
+pub const PULL_REQUEST_LINK_NAME: &str = "Pull Request";
+pub const BUILD_LINK_NAME: &str = "Build";
+
+/// Artifact URI of a Git commit
+pub fn commit_artifact_uri(project_id: &str, repository_id: &str, commit_id: &str) -> String {
+    format!(
+        "vstfs:///Git/Commit/{}%2F{}%2F{}",
+        project_id, repository_id, commit_id
+    )
+}
+
+/// Artifact URI of a Git pull request
+pub fn pull_request_artifact_uri(
+    project_id: &str,
+    repository_id: &str,
+    pull_request_id: u32,
+) -> String {
+    format!(
+        "vstfs:///Git/PullRequestId/{}%2F{}%2F{}",
+        project_id, repository_id, pull_request_id
+    )
+}
+
+/// Artifact URI of a pipeline build
+pub fn build_artifact_uri(build_id: u32) -> String {
+    format!("vstfs:///Build/Build/{}", build_id)
+}
+
+/// Short description of an artifact URI, e.g. `commit 1a2b3c` or `build 12`.
+/// Unknown artifact kinds are returned unchanged.
+pub fn describe_artifact_uri(uri: &str) -> String {
+    let Some(rest) = uri.strip_prefix("vstfs:///") else {
+        return uri.to_string();
+    };
+    let mut parts = rest.splitn(3, '/');
+    let (tool, kind, id) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(tool), Some(kind), Some(id)) => (tool, kind, id),
+        _ => return uri.to_string(),
+    };
+    // Git artifact IDs are `{projectId}%2F{repositoryId}%2F{id}`
+    let decoded = id.replace("%2F", "/").replace("%2f", "/");
+    let last_segment = decoded.rsplit('/').next().unwrap_or(&decoded);
+    match (tool, kind) {
+        ("Git", "Commit") => format!("commit {}", last_segment),
+        ("Git", "PullRequestId") => format!("pull_request {}", last_segment),
+        ("Build", "Build") => format!("build {}", id),
+        _ => uri.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_artifact_uris_round_trip_through_description() {
+        let cases = vec![
+            (commit_artifact_uri("p1", "r1", "1a2b3c"), "commit 1a2b3c"),
+            (pull_request_artifact_uri("p1", "r1", 17), "pull_request 17"),
+            (build_artifact_uri(12), "build 12"),
+            (
+                "vstfs:///Git/Ref/p1%2Fr1%2FGBmain".to_string(),
+                "vstfs:///Git/Ref/p1%2Fr1%2FGBmain",
+            ),
+            (
+                "https://example.com/doc".to_string(),
+                "https://example.com/doc",
+            ),
+        ];
+        for (uri, expected) in cases {
+            assert_eq!(describe_artifact_uri(&uri), expected, "uri '{uri}'");
+        }
+    }
+}
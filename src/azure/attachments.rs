@@ -0,0 +1,117 @@
+use crate::azure::client::{AzureDevOpsClient, AzureError};
+use crate::azure::models::WorkItem;
+use crate::azure::work_items::JsonPatchOperation;
+use serde::{Deserialize, Serialize};
+
+/// Relation type Azure DevOps uses for files attached to a work item
+pub const ATTACHED_FILE_REL: &str = "AttachedFile";
+
+/// Reference returned by the attachments API after an upload
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AttachmentReference {
+    pub id: String,
+    pub url: String,
+}
+
+/// Extract the attachment ID (GUID) from an attachment URL
+/// (e.g. `https://dev.azure.com/{org}/{projectId}/_apis/wit/attachments/{id}`)
+pub fn attachment_id_from_url(url: &str) -> &str {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    path.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(path)
+}
+
+/// Upload a file to the attachment store of a project
+pub async fn upload_attachment(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    file_name: &str,
+    content: Vec<u8>,
+) -> Result<AttachmentReference, AzureError> {
+    // API: POST https://dev.azure.com/{org}/{project}/_apis/wit/attachments?fileName={name}&api-version=7.1
+    let path = format!(
+        "wit/attachments?fileName={}&api-version=7.1",
+        urlencoding::encode(file_name)
+    );
+    client
+        .post_binary(organization, project, &path, content)
+        .await
+}
+
+/// Add an `AttachedFile` relation pointing to an uploaded attachment
+pub async fn add_attachment_to_work_item(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    work_item_id: u32,
+    attachment_url: &str,
+    comment: Option<&str>,
+) -> Result<WorkItem, AzureError> {
+    let mut relation = serde_json::json!({
+        "rel": ATTACHED_FILE_REL,
+        "url": attachment_url,
+    });
+    if let Some(comment) = comment {
+        relation["attributes"] = serde_json::json!({ "comment": comment });
+    }
+
+    let operations = vec![JsonPatchOperation {
+        op: "add".to_string(),
+        path: "/relations/-".to_string(),
+        value: Some(relation),
+        from: None,
+    }];
+
+    let path = format!("wit/workitems/{}?api-version=7.1", work_item_id);
+    client
+        .patch_patch(organization, project, &path, &operations)
+        .await
+}
+
+/// Download the raw content of an attachment
+pub async fn get_attachment(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    attachment_id: &str,
+    max_size: u64,
+) -> Result<Vec<u8>, AzureError> {
+    // API: GET https://dev.azure.com/{org}/{project}/_apis/wit/attachments/{id}?download=true&api-version=7.1
+    let path = format!(
+        "wit/attachments/{}?download=true&api-version=7.1",
+        urlencoding::encode(attachment_id)
+    );
+    client
+        .get_binary(organization, project, &path, max_size)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attachment_id_from_url() {
+        let cases = vec![
+            (
+                "https://dev.azure.com/org/1234/_apis/wit/attachments/0f1e2d3c-aaaa-bbbb-cccc-000000000001",
+                "0f1e2d3c-aaaa-bbbb-cccc-000000000001",
+            ),
+            (
+                "https://dev.azure.com/org/_apis/wit/attachments/abc?fileName=log.txt",
+                "abc",
+            ),
+            (
+                "https://dev.azure.com/org/_apis/wit/attachments/abc/",
+                "abc",
+            ),
+            ("abc", "abc"),
+        ];
+        for (url, expected) in cases {
+            assert_eq!(attachment_id_from_url(url), expected, "url '{url}'");
+        }
+    }
+}
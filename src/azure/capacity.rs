@@ -0,0 +1,202 @@
+use crate::azure::client::{AzureDevOpsClient, AzureError};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+/// An inclusive range of days off, as RFC 3339 timestamps at midnight UTC
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DateRange {
+    pub start: String,
+    pub end: String,
+}
+
+/// Hours per day a team member can spend on an activity (e.g. "Development");
+/// an empty name is the unassigned activity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Activity {
+    pub capacity_per_day: f64,
+    #[serde(default)]
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapacityIdentity {
+    pub id: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub unique_name: Option<String>,
+}
+
+/// Capacity of one team member for an iteration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamMemberCapacity {
+    pub team_member: CapacityIdentity,
+    #[serde(default)]
+    pub activities: Vec<Activity>,
+    #[serde(default)]
+    pub days_off: Vec<DateRange>,
+}
+
+/// Capacity of a team for an iteration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamCapacity {
+    #[serde(default)]
+    pub team_members: Vec<TeamMemberCapacity>,
+    #[serde(default)]
+    pub total_capacity_per_day: f64,
+    #[serde(default)]
+    pub total_days_off: u32,
+}
+
+/// Fields of a member's capacity to replace; omitted ones are kept
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapacityPatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activities: Option<Vec<Activity>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days_off: Option<Vec<DateRange>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TeamDaysOff {
+    #[serde(default)]
+    days_off: Vec<DateRange>,
+}
+
+fn capacities_path(iteration_id: &str) -> String {
+    format!(
+        "work/teamsettings/iterations/{}/capacities",
+        urlencoding::encode(iteration_id)
+    )
+}
+
+/// Get the capacity of every team member for an iteration
+pub async fn get_iteration_capacities(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+    iteration_id: &str,
+) -> Result<TeamCapacity, AzureError> {
+    // API: GET https://dev.azure.com/{org}/{project}/{team}/_apis/work/teamsettings/iterations/{iterationId}/capacities?api-version=7.1
+    let path = format!("{}?api-version=7.1", capacities_path(iteration_id));
+    client
+        .team_request(
+            organization,
+            project,
+            Method::GET,
+            team_id,
+            &path,
+            None::<&String>,
+        )
+        .await
+}
+
+/// Update the activities and/or days off of one team member for an iteration
+pub async fn update_member_capacity(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+    iteration_id: &str,
+    team_member_id: &str,
+    patch: &CapacityPatch,
+) -> Result<TeamMemberCapacity, AzureError> {
+    // API: PATCH https://dev.azure.com/{org}/{project}/{team}/_apis/work/teamsettings/iterations/{iterationId}/capacities/{teamMemberId}?api-version=7.1
+    let path = format!(
+        "{}/{}?api-version=7.1",
+        capacities_path(iteration_id),
+        urlencoding::encode(team_member_id)
+    );
+    client
+        .team_request(
+            organization,
+            project,
+            Method::PATCH,
+            team_id,
+            &path,
+            Some(patch),
+        )
+        .await
+}
+
+/// Get the days off of the whole team for an iteration
+pub async fn get_team_days_off(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+    iteration_id: &str,
+) -> Result<Vec<DateRange>, AzureError> {
+    // API: GET https://dev.azure.com/{org}/{project}/{team}/_apis/work/teamsettings/iterations/{iterationId}/teamdaysoff?api-version=7.1
+    let path = format!(
+        "work/teamsettings/iterations/{}/teamdaysoff?api-version=7.1",
+        urlencoding::encode(iteration_id)
+    );
+    let response: TeamDaysOff = client
+        .team_request(
+            organization,
+            project,
+            Method::GET,
+            team_id,
+            &path,
+            None::<&String>,
+        )
+        .await?;
+    Ok(response.days_off)
+}
+
+/// Replace the days off of the whole team for an iteration
+pub async fn set_team_days_off(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+    iteration_id: &str,
+    days_off: Vec<DateRange>,
+) -> Result<Vec<DateRange>, AzureError> {
+    // API: PATCH https://dev.azure.com/{org}/{project}/{team}/_apis/work/teamsettings/iterations/{iterationId}/teamdaysoff?api-version=7.1
+    let path = format!(
+        "work/teamsettings/iterations/{}/teamdaysoff?api-version=7.1",
+        urlencoding::encode(iteration_id)
+    );
+    let body = TeamDaysOff { days_off };
+    let response: TeamDaysOff = client
+        .team_request(
+            organization,
+            project,
+            Method::PATCH,
+            team_id,
+            &path,
+            Some(&body),
+        )
+        .await?;
+    Ok(response.days_off)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capacity_patch_omits_unchanged_fields() {
+        let patch = CapacityPatch {
+            activities: None,
+            days_off: Some(vec![DateRange {
+                start: "2026-10-07T00:00:00Z".to_string(),
+                end: "2026-10-08T00:00:00Z".to_string(),
+            }]),
+        };
+        assert_eq!(
+            serde_json::to_value(&patch).unwrap(),
+            serde_json::json!({
+                "daysOff": [{"start": "2026-10-07T00:00:00Z", "end": "2026-10-08T00:00:00Z"}]
+            })
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use crate::azure::client::{AzureDevOpsClient, AzureError};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GitProjectReference {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GitRepository {
+    pub id: String,
+    pub name: String,
+    pub project: GitProjectReference,
+}
+
+/// Get a Git repository by name or ID
+pub async fn get_repository(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    repository: &str,
+) -> Result<GitRepository, AzureError> {
+    // API: GET https://dev.azure.com/{org}/{project}/_apis/git/repositories/{repository}?api-version=7.1
+    let path = format!(
+        "git/repositories/{}?api-version=7.1",
+        urlencoding::encode(repository)
+    );
+    client.get(organization, project, &path).await
+}
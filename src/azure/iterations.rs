@@ -51,9 +51,7 @@ pub async fn get_team_current_iteration(
             // The API returns a list, but with $timeframe=current there should only be one
             Ok(response.value.into_iter().next())
         }
-        Err(AzureError::ApiError(msg)) if msg.contains("CurrentIterationDoesNotExistException") => {
-            Ok(None)
-        }
+        Err(e) if e.type_key() == Some("CurrentIterationDoesNotExistException") => Ok(None),
         Err(e) => Err(e),
     }
 }
@@ -89,3 +87,56 @@ pub async fn get_team_iterations(
 
     Ok(response.value)
 }
+
+/// Get one iteration of a team by ID
+pub async fn get_team_iteration(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+    iteration_id: &str,
+) -> Result<TeamSettingsIteration, AzureError> {
+    // API: https://dev.azure.com/{org}/{project}/{team}/_apis/work/teamsettings/iterations/{id}?api-version=7.1
+    let path = format!(
+        "work/teamsettings/iterations/{}?api-version=7.1",
+        urlencoding::encode(iteration_id)
+    );
+    client
+        .team_request(
+            organization,
+            project,
+            Method::GET,
+            team_id,
+            &path,
+            None::<&String>,
+        )
+        .await
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TeamSettings {
+    /// Lowercase day names, e.g. "monday"
+    #[serde(rename = "workingDays", default)]
+    pub working_days: Vec<String>,
+}
+
+/// Get the working days of a team
+pub async fn get_team_working_days(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    team_id: &str,
+) -> Result<Vec<String>, AzureError> {
+    // API: https://dev.azure.com/{org}/{project}/{team}/_apis/work/teamsettings?api-version=7.1
+    let settings: TeamSettings = client
+        .team_request(
+            organization,
+            project,
+            Method::GET,
+            team_id,
+            "work/teamsettings?api-version=7.1",
+            None::<&String>,
+        )
+        .await?;
+    Ok(settings.working_days)
+}
@@ -1,11 +1,18 @@
 pub mod api_trait;
+pub mod artifact_links;
+pub mod attachments;
 pub mod boards;
+pub mod capacity;
 pub mod classification_nodes;
 pub mod client;
+pub mod git;
 pub mod iterations;
 pub mod models;
 pub mod organizations;
 pub mod projects;
+pub mod queries;
+pub mod retry;
 pub mod tags;
 pub mod teams;
+pub mod token_cache;
 pub mod work_items;
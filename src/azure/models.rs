@@ -43,8 +43,22 @@ pub struct WiqlQuery {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WiqlResponse {
-    #[serde(rename = "workItems")]
+    /// Matches of a flat (`FROM WorkItems`) query
+    #[serde(rename = "workItems", default)]
     pub work_items: Vec<WorkItemReference>,
+    /// Matches of a tree or one-hop (`FROM WorkItemLinks`) query
+    #[serde(rename = "workItemRelations", default)]
+    pub work_item_relations: Vec<WorkItemLinkReference>,
+}
+
+/// One row of a link query result: a top-level item has no `source` nor `rel`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkItemLinkReference {
+    #[serde(default)]
+    pub rel: Option<String>,
+    #[serde(default)]
+    pub source: Option<WorkItemReference>,
+    pub target: WorkItemReference,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -52,3 +66,155 @@ pub struct WorkItemReference {
     pub id: u32,
     pub url: String,
 }
+
+/// One page of the work items matched by a WIQL query
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkItemQueryPage {
+    pub work_items: Vec<WorkItem>,
+    /// Total number of work items matched by the query
+    pub total_count: usize,
+    /// Number of matches skipped before this page
+    pub skip: usize,
+    /// Whether more matches exist after this page
+    pub truncated: bool,
+    /// Links between the work items of this page, for link queries
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<WorkItemLink>,
+}
+
+/// A `source` → `target` link matched by a link query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkItemLink {
+    /// `None` for the top-level items of the result
+    pub source: Option<u32>,
+    pub target: u32,
+    /// Link type reference name (e.g., `System.LinkTypes.Hierarchy-Forward`),
+    /// `None` for the top-level items of the result
+    pub link_type: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkItemRelation {
+    pub rel: String,
+    pub url: String,
+    #[serde(default)]
+    pub attributes: HashMap<String, serde_json::Value>,
+}
+
+/// A work item with its fields and links, as returned with `$expand=relations`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkItemWithRelations {
+    pub id: u32,
+    #[serde(default)]
+    pub fields: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub relations: Vec<WorkItemRelation>,
+}
+
+/// Batch of work items fetched with `errorPolicy=omit`: items that do not
+/// exist or are not visible are `null`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkItemWithRelationsListResponse {
+    pub count: u32,
+    pub value: Vec<Option<WorkItemWithRelations>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkItemRelationsResponse {
+    pub id: u32,
+    #[serde(default)]
+    pub rev: Option<u32>,
+    #[serde(default)]
+    pub relations: Vec<WorkItemRelation>,
+}
+
+/// A link type from `wit/workitemrelationtypes`, e.g. "Parent" for
+/// `System.LinkTypes.Hierarchy-Reverse`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkItemRelationType {
+    pub name: String,
+    #[serde(rename = "referenceName")]
+    pub reference_name: String,
+    #[serde(default)]
+    pub attributes: HashMap<String, serde_json::Value>,
+}
+
+impl WorkItemRelationType {
+    /// Whether this type links two work items (as opposed to hyperlinks,
+    /// artifact links and attachments, which point to resources)
+    pub fn is_work_item_link(&self) -> bool {
+        self.attributes
+            .get("usage")
+            .and_then(|usage| usage.as_str())
+            == Some("workItemLink")
+    }
+
+    /// Whether the link type is enabled in the organization
+    pub fn is_enabled(&self) -> bool {
+        self.attributes
+            .get("enabled")
+            .and_then(|enabled| enabled.as_bool())
+            .unwrap_or(true)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkItemRelationTypeListResponse {
+    pub count: u32,
+    pub value: Vec<WorkItemRelationType>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkItemFieldUpdate {
+    #[serde(rename = "oldValue", default, skip_serializing_if = "Option::is_none")]
+    pub old_value: Option<serde_json::Value>,
+    #[serde(rename = "newValue", default, skip_serializing_if = "Option::is_none")]
+    pub new_value: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct WorkItemRelationUpdates {
+    #[serde(default)]
+    pub added: Vec<WorkItemRelation>,
+    #[serde(default)]
+    pub removed: Vec<WorkItemRelation>,
+    #[serde(default)]
+    pub updated: Vec<WorkItemRelation>,
+}
+
+/// A single revision of a work item, as returned by the `updates` endpoint
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkItemUpdate {
+    pub id: u32,
+    #[serde(default)]
+    pub rev: Option<u32>,
+    #[serde(rename = "revisedBy", default)]
+    pub revised_by: Option<serde_json::Value>,
+    #[serde(rename = "revisedDate", default)]
+    pub revised_date: Option<String>,
+    #[serde(default)]
+    pub fields: Option<HashMap<String, WorkItemFieldUpdate>>,
+    #[serde(default)]
+    pub relations: Option<WorkItemRelationUpdates>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkItemUpdateListResponse {
+    pub count: u32,
+    pub value: Vec<WorkItemUpdate>,
+}
+
+/// Response to one request of a work item `$batch` call. `body` is the JSON
+/// response of the individual request, serialized as a string.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkItemBatchResponse {
+    pub code: u16,
+    #[serde(default)]
+    pub body: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkItemBatchResponseList {
+    pub count: u32,
+    pub value: Vec<WorkItemBatchResponse>,
+}
@@ -0,0 +1,137 @@
+use crate::azure::client::{AzureDevOpsClient, AzureError};
+use serde::{Deserialize, Serialize};
+
+/// Deepest folder level the queries API expands in one request
+pub const MAX_QUERY_TREE_DEPTH: u32 = 2;
+
+/// A saved query or query folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryHierarchyItem {
+    pub id: String,
+    pub name: String,
+    /// Full path, e.g. `Shared Queries/Team/Active Bugs`
+    pub path: String,
+    #[serde(default)]
+    pub is_folder: bool,
+    #[serde(default)]
+    pub has_children: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<QueryHierarchyItem>,
+    /// `flat`, `tree` or `oneHop`; `None` for folders
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wiql: Option<String>,
+    #[serde(default)]
+    pub is_public: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueryHierarchyItemList {
+    pub count: u32,
+    pub value: Vec<QueryHierarchyItem>,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateQueryRequest<'a> {
+    name: &'a str,
+    wiql: &'a str,
+}
+
+/// Encodes a query ID or a `/`-separated query path for use in a URL path
+fn encode_query_path(id_or_path: &str) -> String {
+    id_or_path
+        .trim_matches('/')
+        .split('/')
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// List the root query folders (`My Queries`, `Shared Queries`) and their
+/// content down to `depth` levels
+pub async fn list_queries(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    depth: u32,
+) -> Result<Vec<QueryHierarchyItem>, AzureError> {
+    // API: GET https://dev.azure.com/{org}/{project}/_apis/wit/queries?$depth={depth}&$expand=minimal&api-version=7.1
+    let path = format!(
+        "wit/queries?$depth={}&$expand=minimal&api-version=7.1",
+        depth.min(MAX_QUERY_TREE_DEPTH)
+    );
+    let response: QueryHierarchyItemList = client.get(organization, project, &path).await?;
+    Ok(response.value)
+}
+
+/// Get a saved query or folder by ID or path, with its WIQL and the content
+/// of a folder down to `depth` levels
+pub async fn get_query(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    id_or_path: &str,
+    depth: u32,
+) -> Result<QueryHierarchyItem, AzureError> {
+    // API: GET https://dev.azure.com/{org}/{project}/_apis/wit/queries/{query}?$depth={depth}&$expand=wiql&api-version=7.1
+    let path = format!(
+        "wit/queries/{}?$depth={}&$expand=wiql&api-version=7.1",
+        encode_query_path(id_or_path),
+        depth.min(MAX_QUERY_TREE_DEPTH)
+    );
+    client.get(organization, project, &path).await
+}
+
+/// Save a new query named `name` in the folder `parent` (ID or path)
+pub async fn create_query(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    parent: &str,
+    name: &str,
+    wiql: &str,
+) -> Result<QueryHierarchyItem, AzureError> {
+    // API: POST https://dev.azure.com/{org}/{project}/_apis/wit/queries/{parent}?api-version=7.1
+    let path = format!("wit/queries/{}?api-version=7.1", encode_query_path(parent));
+    let body = CreateQueryRequest { name, wiql };
+    client.post(organization, project, &path, &body).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_query_path() {
+        assert_eq!(
+            encode_query_path("/Shared Queries/Team A/Active Bugs"),
+            "Shared%20Queries/Team%20A/Active%20Bugs"
+        );
+        assert_eq!(
+            encode_query_path("0f1e2d3c-4b5a-6978-8796-a5b4c3d2e1f0"),
+            "0f1e2d3c-4b5a-6978-8796-a5b4c3d2e1f0"
+        );
+    }
+
+    #[test]
+    fn test_deserialize_query_hierarchy() {
+        let json = r#"{
+            "id": "f1",
+            "name": "Shared Queries",
+            "path": "Shared Queries",
+            "isFolder": true,
+            "hasChildren": true,
+            "isPublic": true,
+            "children": [
+                {"id": "q1", "name": "Epics", "path": "Shared Queries/Epics", "queryType": "tree"}
+            ]
+        }"#;
+        let folder: QueryHierarchyItem = serde_json::from_str(json).unwrap();
+        assert!(folder.is_folder);
+        assert_eq!(folder.children.len(), 1);
+        assert_eq!(folder.children[0].query_type.as_deref(), Some("tree"));
+        assert!(!folder.children[0].is_folder);
+    }
+}
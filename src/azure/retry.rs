@@ -0,0 +1,217 @@
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Method, StatusCode};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Azure DevOps reports the remaining request budget of the throttled
+/// resource in `X-RateLimit-Remaining` (out of `X-RateLimit-Limit`) and the
+/// delay it applied to the request in `X-RateLimit-Delay`. See
+/// https://learn.microsoft.com/azure/devops/integrate/concepts/rate-limits
+const RATE_LIMIT_RESOURCE: &str = "x-ratelimit-resource";
+const RATE_LIMIT_LIMIT: &str = "x-ratelimit-limit";
+const RATE_LIMIT_REMAINING: &str = "x-ratelimit-remaining";
+const RATE_LIMIT_DELAY: &str = "x-ratelimit-delay";
+
+/// Fraction of the rate limit below which the remaining headroom is logged
+/// as a warning, so throttling can be anticipated before requests fail.
+const LOW_HEADROOM_RATIO: f64 = 0.1;
+
+/// How failed requests are retried: up to `max_retries` extra attempts,
+/// waiting `base_delay * 2^attempt` (with jitter, capped at `max_delay`)
+/// between them, or the server's `Retry-After` when it sends one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Exponential backoff for the given (zero-based) retry attempt with
+    /// "equal jitter": half of the delay is fixed and half is random, so
+    /// concurrent clients spread out without ever retrying immediately.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        let half = exponential / 2;
+        half + half.mul_f64(jitter())
+    }
+
+    /// Delay before retrying a response. A `Retry-After` from the server is
+    /// honored as is; when it exceeds `max_delay` the request is not retried
+    /// (`None`) and the caller gets the throttling error with the hint instead
+    /// of blocking for minutes.
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        match retry_after {
+            Some(retry_after) if retry_after > self.max_delay => None,
+            Some(retry_after) => Some(retry_after),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+}
+
+/// Random factor in `[0, 1)`, drawn from the per-process random hasher keys
+/// so no extra dependency is needed for jitter.
+fn jitter() -> f64 {
+    let value = RandomState::new().build_hasher().finish();
+    (value >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Only methods that can be repeated without side effects are retried; a
+/// POST or PATCH that timed out may already have been applied.
+pub fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE
+    )
+}
+
+/// Statuses that signal a transient condition worth retrying
+pub fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Parses the `Retry-After` header, which Azure DevOps sends in seconds
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+fn header_number(headers: &HeaderMap, name: &str) -> Option<f64> {
+    headers.get(name)?.to_str().ok()?.trim().parse::<f64>().ok()
+}
+
+/// Remaining and total rate-limit budget reported by the response, when the
+/// remaining part has dropped below [`LOW_HEADROOM_RATIO`] of the limit.
+fn low_rate_limit_headroom(headers: &HeaderMap) -> Option<(f64, f64)> {
+    let limit = header_number(headers, RATE_LIMIT_LIMIT)?;
+    let remaining = header_number(headers, RATE_LIMIT_REMAINING)?;
+    (limit > 0.0 && remaining < limit * LOW_HEADROOM_RATIO).then_some((remaining, limit))
+}
+
+/// Logs a warning when Azure DevOps reports that the rate-limit headroom is
+/// running low or that it has started delaying requests.
+pub fn log_rate_limit_headroom(headers: &HeaderMap) {
+    let resource = headers
+        .get(RATE_LIMIT_RESOURCE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("unknown");
+    if let Some((remaining, limit)) = low_rate_limit_headroom(headers) {
+        log::warn!(
+            "Azure DevOps rate limit headroom is low for {resource}: {remaining} of {limit} remaining"
+        );
+    }
+    if let Some(delay) = header_number(headers, RATE_LIMIT_DELAY).filter(|delay| *delay > 0.0) {
+        log::warn!("Azure DevOps delayed the request by {delay}s to throttle {resource}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+        }
+    }
+
+    #[test]
+    fn test_backoff_grows_exponentially_within_jitter_bounds() {
+        let policy = policy();
+        for (attempt, full) in [
+            (0, 100),
+            (1, 200),
+            (2, 400),
+            (3, 800),
+            (4, 1000),
+            (20, 1000),
+        ] {
+            let full = Duration::from_millis(full);
+            for _ in 0..20 {
+                let delay = policy.backoff(attempt);
+                assert!(
+                    delay >= full / 2 && delay <= full,
+                    "attempt {attempt}: {delay:?} outside [{:?}, {full:?}]",
+                    full / 2
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_delay_honors_retry_after_up_to_max_delay() {
+        let policy = policy();
+        assert_eq!(
+            policy.delay(0, Some(Duration::from_millis(700))),
+            Some(Duration::from_millis(700))
+        );
+        assert_eq!(policy.delay(0, Some(Duration::from_secs(5))), None);
+        assert!(policy.delay(0, None).is_some());
+    }
+
+    #[test]
+    fn test_only_idempotent_methods_and_transient_statuses_are_retried() {
+        assert!(is_idempotent(&Method::GET));
+        assert!(is_idempotent(&Method::PUT));
+        assert!(is_idempotent(&Method::DELETE));
+        assert!(!is_idempotent(&Method::POST));
+        assert!(!is_idempotent(&Method::PATCH));
+
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_retryable_status(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn test_retry_after_parses_seconds() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(RETRY_AFTER, "12".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(12)));
+        headers.insert(
+            RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(retry_after(&headers), None);
+    }
+
+    #[test]
+    fn test_low_rate_limit_headroom() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(low_rate_limit_headroom(&headers), None);
+        headers.insert(RATE_LIMIT_LIMIT, "200".parse().unwrap());
+        headers.insert(RATE_LIMIT_REMAINING, "150".parse().unwrap());
+        assert_eq!(low_rate_limit_headroom(&headers), None);
+        headers.insert(RATE_LIMIT_REMAINING, "12.5".parse().unwrap());
+        assert_eq!(low_rate_limit_headroom(&headers), Some((12.5, 200.0)));
+    }
+}
@@ -0,0 +1,100 @@
+use reqwest::header::HeaderValue;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Tokens are refreshed this long before they expire, so a request never
+/// starts with a token that lapses while it is in flight.
+pub const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(5 * 60);
+
+/// An `Authorization` header value together with the credential source that
+/// produced it and, for tokens, when it expires.
+#[derive(Debug, Clone)]
+pub struct CachedToken {
+    pub authorization: HeaderValue,
+    /// Index of the credential source in the chain
+    pub source: usize,
+    /// `None` for credentials that do not expire (basic auth, PAT)
+    pub expires_at: Option<Instant>,
+}
+
+impl CachedToken {
+    /// Whether the token can be used without refreshing it first
+    pub fn is_fresh(&self, now: Instant) -> bool {
+        self.expires_at
+            .is_none_or(|expires_at| now + TOKEN_REFRESH_MARGIN < expires_at)
+    }
+
+    /// Whether the token can no longer be used at all
+    pub fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+}
+
+/// The slot of one scope: its last token, behind an async lock held for the
+/// whole refresh so concurrent callers wait for a single in-flight refresh
+/// instead of each walking the credential chain.
+pub type TokenSlot = Arc<tokio::sync::Mutex<Option<CachedToken>>>;
+
+/// In-memory cache of authorization headers, keyed by scope
+#[derive(Debug, Default)]
+pub struct TokenCache {
+    slots: Mutex<HashMap<String, TokenSlot>>,
+}
+
+impl TokenCache {
+    /// The slot of `scope`, created empty on first use
+    pub fn slot(&self, scope: &str) -> TokenSlot {
+        let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+        slots.entry(scope.to_string()).or_default().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(expires_in: Option<Duration>, now: Instant) -> CachedToken {
+        CachedToken {
+            authorization: HeaderValue::from_static("Bearer token"),
+            source: 0,
+            expires_at: expires_in.map(|expires_in| now + expires_in),
+        }
+    }
+
+    #[test]
+    fn test_cached_token_freshness() {
+        let now = Instant::now();
+        let cases = vec![
+            ("never expires", None, true, false),
+            (
+                "expires in an hour",
+                Some(Duration::from_secs(3600)),
+                true,
+                false,
+            ),
+            (
+                "within refresh margin",
+                Some(Duration::from_secs(60)),
+                false,
+                false,
+            ),
+            ("expired", Some(Duration::ZERO), false, true),
+        ];
+        for (name, expires_in, fresh, expired) in cases {
+            let token = token(expires_in, now);
+            assert_eq!(token.is_fresh(now), fresh, "case '{name}'");
+            assert_eq!(token.is_expired(now), expired, "case '{name}'");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_token_cache_slots_are_per_scope() {
+        let cache = TokenCache::default();
+        let now = Instant::now();
+        *cache.slot("scope-a").lock().await = Some(token(None, now));
+
+        assert!(cache.slot("scope-a").lock().await.is_some());
+        assert!(cache.slot("scope-b").lock().await.is_none());
+    }
+}
@@ -1,12 +1,25 @@
 use crate::azure::client::{AzureDevOpsClient, AzureError};
 use crate::azure::models::{
-    Comment, CommentListResponse, WiqlQuery, WiqlResponse, WorkItem, WorkItemListResponse,
+    Comment, CommentListResponse, WiqlQuery, WiqlResponse, WorkItem, WorkItemBatchResponse,
+    WorkItemBatchResponseList, WorkItemLink, WorkItemListResponse, WorkItemQueryPage,
+    WorkItemRelation, WorkItemRelationType, WorkItemRelationTypeListResponse,
+    WorkItemRelationsResponse, WorkItemUpdate, WorkItemUpdateListResponse, WorkItemWithRelations,
+    WorkItemWithRelationsListResponse,
 };
 use futures::future::join_all;
+use reqwest::Method;
 use serde::Serialize;
 use serde_json::Value;
+use std::collections::{HashMap, HashSet};
 
 const COMMENT_FETCH_CONCURRENCY: usize = 10;
+const UPDATES_PAGE_SIZE: usize = 200;
+const WORK_ITEM_URL_PREFIX: &str = "https://dev.azure.com/_apis/wit/workitems/";
+
+/// Exception type and error code Azure DevOps reports for a work item that
+/// does not exist (or that the caller cannot read)
+const WORK_ITEM_MISSING_TYPE_KEY: &str = "WorkItemUnauthorizedAccessException";
+const WORK_ITEM_MISSING_ERROR_CODE: &str = "TF401232";
 
 fn escape_json_pointer_token(token: &str) -> String {
     token.replace('~', "~0").replace('/', "~1")
@@ -22,6 +35,121 @@ pub struct JsonPatchOperation {
     pub from: Option<String>,
 }
 
+/// JSON Patch `add` operations setting `fields`, preceded by the text format
+/// of the multiline fields among them.
+pub fn field_operations(
+    fields: &[(String, Value)],
+    multiline_fields_format: &[(String, String)],
+) -> Vec<JsonPatchOperation> {
+    let formats = multiline_fields_format
+        .iter()
+        .map(|(field, format)| JsonPatchOperation {
+            op: "add".to_string(),
+            path: format!(
+                "/multilineFieldsFormat/{}",
+                escape_json_pointer_token(field)
+            ),
+            value: Some(Value::String(format.to_string())),
+            from: None,
+        });
+    let values = fields.iter().map(|(field, value)| JsonPatchOperation {
+        op: "add".to_string(),
+        path: format!("/fields/{}", escape_json_pointer_token(field)),
+        value: Some(value.clone()),
+        from: None,
+    });
+    formats.chain(values).collect()
+}
+
+/// One request of a work item `$batch` call
+#[derive(Serialize)]
+pub struct WorkItemBatchRequest {
+    pub method: String,
+    pub uri: String,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<JsonPatchOperation>,
+}
+
+impl WorkItemBatchRequest {
+    fn new(uri: String, body: Vec<JsonPatchOperation>) -> Self {
+        Self {
+            method: "PATCH".to_string(),
+            uri,
+            headers: HashMap::from([(
+                "Content-Type".to_string(),
+                "application/json-patch+json".to_string(),
+            )]),
+            body,
+        }
+    }
+
+    /// Create a work item. `temp_id` is a negative ID other requests of the
+    /// same batch can use in relation URLs (see `work_item_url`).
+    pub fn create(
+        project: &str,
+        work_item_type: &str,
+        temp_id: i32,
+        mut operations: Vec<JsonPatchOperation>,
+    ) -> Self {
+        operations.insert(
+            0,
+            JsonPatchOperation {
+                op: "add".to_string(),
+                path: "/id".to_string(),
+                value: Some(Value::String(temp_id.to_string())),
+                from: None,
+            },
+        );
+        Self::new(
+            format!(
+                "/{}/_apis/wit/workitems/${}?api-version=7.1",
+                urlencoding::encode(project),
+                urlencoding::encode(work_item_type)
+            ),
+            operations,
+        )
+    }
+
+    /// Update an existing work item
+    pub fn update(project: &str, id: u32, operations: Vec<JsonPatchOperation>) -> Self {
+        Self::new(
+            format!(
+                "/{}/_apis/wit/workitems/{}?api-version=7.1",
+                urlencoding::encode(project),
+                id
+            ),
+            operations,
+        )
+    }
+}
+
+/// Submit up to 200 work item create/update requests in one call. Requests
+/// run in order and are not transactional: each has its own response.
+pub async fn batch_work_items(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    mut requests: Vec<WorkItemBatchRequest>,
+) -> Result<Vec<WorkItemBatchResponse>, AzureError> {
+    for operation in requests.iter_mut().flat_map(|request| &mut request.body) {
+        if operation.path == "/relations/-"
+            && let Some(relation) = &mut operation.value
+        {
+            resolve_relation_url(client, organization, relation);
+        }
+    }
+
+    // API: POST https://dev.azure.com/{org}/_apis/wit/$batch?api-version=7.1
+    let response: WorkItemBatchResponseList = client
+        .org_request(
+            organization,
+            Method::POST,
+            "wit/$batch?api-version=7.1",
+            Some(&requests),
+        )
+        .await?;
+    Ok(response.value)
+}
+
 pub async fn get_work_item(
     client: &AzureDevOpsClient,
     organization: &str,
@@ -40,9 +168,10 @@ pub async fn get_work_item(
 
     match result {
         Ok(items) => Ok(items.into_iter().next()),
-        Err(AzureError::ApiError(msg))
-            if msg.contains("WorkItemUnauthorizedAccessException")
-                || msg.contains("Work item does not exist") =>
+        // Other 404s (e.g. a misspelled organization or project) are errors
+        Err(AzureError::NotFound { type_key, message })
+            if type_key.as_deref() == Some(WORK_ITEM_MISSING_TYPE_KEY)
+                || message.starts_with(WORK_ITEM_MISSING_ERROR_CODE) =>
         {
             Ok(None)
         }
@@ -50,6 +179,46 @@ pub async fn get_work_item(
     }
 }
 
+/// Get the relations (links, attachments, hyperlinks, artifact links) of a work item
+pub async fn get_work_item_relations(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    id: u32,
+) -> Result<Vec<WorkItemRelation>, AzureError> {
+    let path = format!("wit/workitems/{}?$expand=relations&api-version=7.1", id);
+    let response: WorkItemRelationsResponse = client.get(organization, project, &path).await?;
+    Ok(response.relations)
+}
+
+/// Get the full revision history of a work item (every update, oldest first)
+pub async fn get_work_item_updates(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    id: u32,
+) -> Result<Vec<WorkItemUpdate>, AzureError> {
+    let mut all_updates = Vec::new();
+
+    loop {
+        let path = format!(
+            "wit/workitems/{}/updates?$top={}&$skip={}&api-version=7.1",
+            id,
+            UPDATES_PAGE_SIZE,
+            all_updates.len()
+        );
+        let response: WorkItemUpdateListResponse = client.get(organization, project, &path).await?;
+        let page_len = response.value.len();
+        all_updates.extend(response.value);
+
+        if page_len < UPDATES_PAGE_SIZE {
+            break;
+        }
+    }
+
+    Ok(all_updates)
+}
+
 pub async fn get_comments(
     client: &AzureDevOpsClient,
     organization: &str,
@@ -119,22 +288,10 @@ pub async fn get_work_items(
         return Ok(vec![]);
     }
 
-    let max_items = 1000;
-    let ids_to_fetch = if ids.len() > max_items {
-        log::warn!(
-            "Requested {} work items, limiting to {} items",
-            ids.len(),
-            max_items
-        );
-        &ids[..max_items]
-    } else {
-        ids
-    };
-
     let batch_size = 200;
     let mut all_work_items = Vec::new();
 
-    for chunk in ids_to_fetch.chunks(batch_size) {
+    for chunk in ids.chunks(batch_size) {
         let ids_str = chunk
             .iter()
             .map(|id| id.to_string())
@@ -169,6 +326,33 @@ pub async fn get_work_items(
     Ok(all_work_items)
 }
 
+/// Get work items with their links, in batches of 200. Work items that do not
+/// exist or are not visible to the caller are left out rather than failing
+/// the batch.
+pub async fn get_work_items_with_relations(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    ids: &[u32],
+) -> Result<Vec<WorkItemWithRelations>, AzureError> {
+    let mut all_work_items = Vec::new();
+    for chunk in ids.chunks(200) {
+        let ids_str = chunk
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let path = format!(
+            "wit/workitems?ids={}&$expand=relations&errorPolicy=omit&api-version=7.1",
+            ids_str
+        );
+        let response: WorkItemWithRelationsListResponse =
+            client.get(organization, project, &path).await?;
+        all_work_items.extend(response.value.into_iter().flatten());
+    }
+    Ok(all_work_items)
+}
+
 pub async fn create_work_item(
     client: &AzureDevOpsClient,
     organization: &str,
@@ -177,28 +361,7 @@ pub async fn create_work_item(
     fields: &[(String, Value)],
     multiline_fields_format: &[(String, String)],
 ) -> Result<WorkItem, AzureError> {
-    let mut operations: Vec<JsonPatchOperation> = Vec::new();
-
-    for (field, format) in multiline_fields_format {
-        operations.push(JsonPatchOperation {
-            op: "add".to_string(),
-            path: format!(
-                "/multilineFieldsFormat/{}",
-                escape_json_pointer_token(field)
-            ),
-            value: Some(Value::String(format.to_string())),
-            from: None,
-        });
-    }
-
-    for (k, v) in fields {
-        operations.push(JsonPatchOperation {
-            op: "add".to_string(),
-            path: format!("/fields/{}", escape_json_pointer_token(k)),
-            value: Some(v.clone()),
-            from: None,
-        });
-    }
+    let operations = field_operations(fields, multiline_fields_format);
 
     let path = format!(
         "wit/workitems/${}?api-version=7.1",
@@ -209,33 +372,39 @@ pub async fn create_work_item(
         .await
 }
 
+/// Update a work item: `fields` are set, `clear_fields` are removed (unset),
+/// and `expected_rev` guards the update against concurrent changes.
+#[allow(clippy::too_many_arguments)]
 pub async fn update_work_item(
     client: &AzureDevOpsClient,
     organization: &str,
     project: &str,
     id: u32,
     fields: &[(String, Value)],
+    clear_fields: &[String],
     multiline_fields_format: &[(String, String)],
+    expected_rev: Option<u32>,
 ) -> Result<WorkItem, AzureError> {
     let mut operations: Vec<JsonPatchOperation> = Vec::new();
 
-    for (field, format) in multiline_fields_format {
+    // A failed `test` makes the whole patch fail, so nothing is written when
+    // the work item changed since `expected_rev` was read.
+    if let Some(rev) = expected_rev {
         operations.push(JsonPatchOperation {
-            op: "add".to_string(),
-            path: format!(
-                "/multilineFieldsFormat/{}",
-                escape_json_pointer_token(field)
-            ),
-            value: Some(Value::String(format.to_string())),
+            op: "test".to_string(),
+            path: "/rev".to_string(),
+            value: Some(serde_json::json!(rev)),
             from: None,
         });
     }
 
-    for (field, value) in fields {
+    operations.extend(field_operations(fields, multiline_fields_format));
+
+    for field in clear_fields {
         operations.push(JsonPatchOperation {
-            op: "add".to_string(),
+            op: "remove".to_string(),
             path: format!("/fields/{}", escape_json_pointer_token(field)),
-            value: Some(value.clone()),
+            value: None,
             from: None,
         });
     }
@@ -285,21 +454,78 @@ pub async fn update_comment(
     client.patch(organization, project, &path, &body).await
 }
 
+/// Link type from a parent to its children
+pub const HIERARCHY_FORWARD_REL: &str = "System.LinkTypes.Hierarchy-Forward";
+
+/// URL identifying a work item as the target of a work item link. Negative
+/// IDs refer to work items created earlier in the same `$batch` call.
+pub fn work_item_url(id: impl std::fmt::Display) -> String {
+    format!("{}{}", WORK_ITEM_URL_PREFIX, id)
+}
+
+/// URLs from `work_item_url` name no organization; before a relation is sent
+/// they are resolved against the configured organization (or Azure DevOps
+/// Server collection) URL, so links also work outside Azure DevOps Services.
+fn resolve_relation_url(client: &AzureDevOpsClient, organization: &str, relation: &mut Value) {
+    if let Some(Value::String(url)) = relation.get_mut("url")
+        && let Some(id) = url.strip_prefix(WORK_ITEM_URL_PREFIX)
+    {
+        *url = format!(
+            "{}/_apis/wit/workitems/{}",
+            client.organization_url(organization),
+            id
+        );
+    }
+}
+
+/// Extract the work item ID from a work item URL
+/// (e.g. `https://dev.azure.com/{org}/{projectId}/_apis/wit/workItems/{id}`)
+pub fn work_item_id_from_url(url: &str) -> Option<u32> {
+    let lower = url.to_ascii_lowercase();
+    let (_, id) = lower.split_once("/_apis/wit/workitems/")?;
+    id.trim_end_matches('/').parse().ok()
+}
+
+/// Whether a stored relation URL points to the given target. Work item URLs
+/// are compared by ID, since Azure DevOps stores them with the organization
+/// and project ID filled in; other URLs are compared case-insensitively.
+pub fn relation_url_matches(relation_url: &str, target_url: &str) -> bool {
+    match (
+        work_item_id_from_url(relation_url),
+        work_item_id_from_url(target_url),
+    ) {
+        (Some(a), Some(b)) => a == b,
+        _ => relation_url.eq_ignore_ascii_case(target_url),
+    }
+}
+
+/// List the link types (relation types) available in an organization
+pub async fn list_work_item_relation_types(
+    client: &AzureDevOpsClient,
+    organization: &str,
+) -> Result<Vec<WorkItemRelationType>, AzureError> {
+    // API: GET https://dev.azure.com/{org}/_apis/wit/workitemrelationtypes?api-version=7.1
+    let path = "wit/workitemrelationtypes?api-version=7.1";
+    let response: WorkItemRelationTypeListResponse = client
+        .org_request(organization, Method::GET, path, None::<&String>)
+        .await?;
+    Ok(response.value)
+}
+
+/// Add a relation (work item link, hyperlink or artifact link) to a work item
 pub async fn link_work_items(
     client: &AzureDevOpsClient,
     organization: &str,
     project: &str,
     source_id: u32,
-    target_id: u32,
-    link_type: &str,
+    relation: &WorkItemRelation,
 ) -> Result<Value, AzureError> {
+    let mut relation = serde_json::to_value(relation)?;
+    resolve_relation_url(client, organization, &mut relation);
     let operations = vec![JsonPatchOperation {
         op: "add".to_string(),
         path: "/relations/-".to_string(),
-        value: Some(serde_json::json!({
-            "rel": link_type,
-            "url": format!("https://dev.azure.com/_apis/wit/workitems/{}", target_id),
-        })),
+        value: Some(relation),
         from: None,
     }];
 
@@ -309,31 +535,272 @@ pub async fn link_work_items(
         .await
 }
 
+/// Remove every relation of a work item pointing to `target_url`, optionally
+/// restricted to one relation type. Returns `None` when nothing matched.
+pub async fn unlink_work_items(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    source_id: u32,
+    target_url: &str,
+    link_type: Option<&str>,
+) -> Result<Option<Value>, AzureError> {
+    let path = format!(
+        "wit/workitems/{}?$expand=relations&api-version=7.1",
+        source_id
+    );
+    let response: WorkItemRelationsResponse = client.get(organization, project, &path).await?;
+
+    let indices: Vec<usize> = response
+        .relations
+        .iter()
+        .enumerate()
+        .filter(|(_, relation)| {
+            relation_url_matches(&relation.url, target_url)
+                && link_type.is_none_or(|link_type| relation.rel.eq_ignore_ascii_case(link_type))
+        })
+        .map(|(index, _)| index)
+        .collect();
+
+    if indices.is_empty() {
+        return Ok(None);
+    }
+
+    // Relations are removed by index, so guard against concurrent edits with
+    // the revision the indices were computed from, and remove back to front.
+    let mut operations = Vec::new();
+    if let Some(rev) = response.rev {
+        operations.push(JsonPatchOperation {
+            op: "test".to_string(),
+            path: "/rev".to_string(),
+            value: Some(serde_json::json!(rev)),
+            from: None,
+        });
+    }
+    for index in indices.into_iter().rev() {
+        operations.push(JsonPatchOperation {
+            op: "remove".to_string(),
+            path: format!("/relations/{}", index),
+            value: None,
+            from: None,
+        });
+    }
+
+    let path = format!("wit/workitems/{}?api-version=7.1", source_id);
+    client
+        .patch_patch(organization, project, &path, &operations)
+        .await
+        .map(Some)
+}
+
+/// Run a WIQL query and fetch one page of the matched work items. The query
+/// itself only returns IDs, so the full match count is always known; only the
+/// `top` items after `skip` are fetched in full.
 pub async fn query_work_items(
     client: &AzureDevOpsClient,
     organization: &str,
     project: &str,
     query: &str,
+    skip: usize,
+    top: usize,
     include_latest_n_comments: Option<i32>,
-) -> Result<Vec<WorkItem>, AzureError> {
+) -> Result<WorkItemQueryPage, AzureError> {
     let wiql = WiqlQuery {
         query: query.to_string(),
     };
     let response: WiqlResponse = client
         .post(organization, project, "wit/wiql?api-version=7.1", &wiql)
         .await?;
+    query_page(
+        client,
+        organization,
+        project,
+        response,
+        skip,
+        top,
+        include_latest_n_comments,
+    )
+    .await
+}
 
-    if response.work_items.is_empty() {
-        return Ok(vec![]);
-    }
-
-    let ids: Vec<u32> = response.work_items.iter().map(|wi| wi.id).collect();
-    get_work_items(
+/// Run the saved query `query_id` and return one page of the work items it matches
+pub async fn run_saved_query(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    query_id: &str,
+    skip: usize,
+    top: usize,
+    include_latest_n_comments: Option<i32>,
+) -> Result<WorkItemQueryPage, AzureError> {
+    // API: GET https://dev.azure.com/{org}/{project}/_apis/wit/wiql/{id}?api-version=7.1
+    let path = format!("wit/wiql/{}?api-version=7.1", urlencoding::encode(query_id));
+    let response: WiqlResponse = client.get(organization, project, &path).await?;
+    query_page(
         client,
         organization,
         project,
-        &ids,
+        response,
+        skip,
+        top,
         include_latest_n_comments,
     )
     .await
 }
+
+/// Fetch the `skip`/`top` page of the work items a WIQL query matched. Link
+/// queries are paged by the distinct work items they reference, in result
+/// order, and the page keeps the links whose target is on it.
+async fn query_page(
+    client: &AzureDevOpsClient,
+    organization: &str,
+    project: &str,
+    response: WiqlResponse,
+    skip: usize,
+    top: usize,
+    include_latest_n_comments: Option<i32>,
+) -> Result<WorkItemQueryPage, AzureError> {
+    let all_links = query_links(&response);
+    let ids: Vec<u32> = if all_links.is_empty() {
+        response.work_items.iter().map(|wi| wi.id).collect()
+    } else {
+        let mut seen = HashSet::new();
+        all_links
+            .iter()
+            .flat_map(|link| link.source.into_iter().chain([link.target]))
+            .filter(|id| seen.insert(*id))
+            .collect()
+    };
+    let total_count = ids.len();
+    let page_ids: Vec<u32> = ids.into_iter().skip(skip).take(top).collect();
+    let truncated = skip.saturating_add(page_ids.len()) < total_count;
+    let on_page: HashSet<u32> = page_ids.iter().copied().collect();
+    let links = all_links
+        .into_iter()
+        .filter(|link| on_page.contains(&link.target))
+        .collect();
+
+    let work_items = get_work_items(
+        client,
+        organization,
+        project,
+        &page_ids,
+        include_latest_n_comments,
+    )
+    .await?;
+
+    Ok(WorkItemQueryPage {
+        work_items,
+        total_count,
+        skip,
+        truncated,
+        links,
+    })
+}
+
+/// The `source` → `target` pairs of a link query response
+fn query_links(response: &WiqlResponse) -> Vec<WorkItemLink> {
+    response
+        .work_item_relations
+        .iter()
+        .map(|relation| WorkItemLink {
+            source: relation.source.as_ref().map(|source| source.id),
+            target: relation.target.id,
+            link_type: relation.rel.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure::client::{DEFAULT_VSSPS_URL, ServiceEndpoints};
+
+    #[test]
+    fn test_work_item_id_from_url() {
+        let cases = vec![
+            (
+                "https://dev.azure.com/org/0f1e2d3c/_apis/wit/workItems/42",
+                Some(42),
+            ),
+            ("https://dev.azure.com/_apis/wit/workitems/7/", Some(7)),
+            ("https://example.com/wiki/page", None),
+            ("vstfs:///Build/Build/12", None),
+        ];
+        for (url, expected) in cases {
+            assert_eq!(work_item_id_from_url(url), expected, "url '{url}'");
+        }
+    }
+
+    #[test]
+    fn test_relation_url_matches() {
+        assert!(relation_url_matches(
+            "https://dev.azure.com/org/0f1e2d3c/_apis/wit/workItems/42",
+            &work_item_url(42)
+        ));
+        assert!(!relation_url_matches(
+            "https://dev.azure.com/org/0f1e2d3c/_apis/wit/workItems/42",
+            &work_item_url(43)
+        ));
+        assert!(relation_url_matches(
+            "vstfs:///Git/Commit/p%2Fr%2Fabc",
+            "vstfs:///Git/Commit/p%2fr%2fabc"
+        ));
+        assert!(!relation_url_matches(
+            "https://example.com/a",
+            "https://example.com/b"
+        ));
+    }
+
+    #[test]
+    fn test_resolve_relation_url_uses_configured_organization_url() {
+        let client = AzureDevOpsClient::new().with_endpoints(
+            ServiceEndpoints::new(
+                "https://tfs.example.com/tfs/{organization}/",
+                DEFAULT_VSSPS_URL,
+            )
+            .unwrap(),
+        );
+        let mut relation = serde_json::json!({
+            "rel": "System.LinkTypes.Hierarchy-Reverse",
+            "url": work_item_url(-1),
+        });
+        resolve_relation_url(&client, "DefaultCollection", &mut relation);
+        assert_eq!(
+            relation["url"],
+            "https://tfs.example.com/tfs/DefaultCollection/_apis/wit/workitems/-1"
+        );
+
+        let mut hyperlink = serde_json::json!({"rel": "Hyperlink", "url": "https://example.com"});
+        resolve_relation_url(&client, "DefaultCollection", &mut hyperlink);
+        assert_eq!(hyperlink["url"], "https://example.com");
+    }
+
+    #[test]
+    fn test_query_links_of_link_query() {
+        let response: WiqlResponse = serde_json::from_value(serde_json::json!({
+            "queryType": "tree",
+            "workItemRelations": [
+                {"rel": null, "source": null, "target": {"id": 1, "url": "u1"}},
+                {"rel": "System.LinkTypes.Hierarchy-Forward", "source": {"id": 1, "url": "u1"}, "target": {"id": 2, "url": "u2"}}
+            ]
+        }))
+        .unwrap();
+        assert!(response.work_items.is_empty());
+        assert_eq!(
+            query_links(&response),
+            vec![
+                WorkItemLink {
+                    source: None,
+                    target: 1,
+                    link_type: None,
+                },
+                WorkItemLink {
+                    source: Some(1),
+                    target: 2,
+                    link_type: Some("System.LinkTypes.Hierarchy-Forward".to_string()),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,332 @@
+use crate::azure::client::CredentialKind;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+/// Directory of the configuration file under the XDG config directory
+const CONFIG_DIR_NAME: &str = "mcp-for-azure-devops-boards";
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Names of the credential sources in the configuration file and on the
+/// command line
+pub const CREDENTIAL_SOURCES: [(&str, CredentialKind); 6] = [
+    ("pat", CredentialKind::Pat),
+    ("basic", CredentialKind::Basic),
+    ("environment", CredentialKind::Environment),
+    ("azure-cli", CredentialKind::AzureCli),
+    ("azure-developer-cli", CredentialKind::AzureDeveloperCli),
+    ("managed-identity", CredentialKind::ManagedIdentity),
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("Failed to read config file {}: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Failed to parse config file {}: {source}", path.display())]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+
+    #[error("Invalid config file {}: {detail}", path.display())]
+    Invalid { path: PathBuf, detail: String },
+}
+
+/// Server settings read from the TOML configuration file. Every setting is
+/// optional; command-line flags and environment variables take precedence.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Use only this credential source instead of the fallback chain
+    #[serde(deserialize_with = "deserialize_credential")]
+    pub credential: Option<CredentialKind>,
+    /// File holding a personal access token
+    pub pat_file: Option<PathBuf>,
+    /// Organization or collection URL, with an `{organization}` placeholder
+    pub base_url: Option<String>,
+    /// URL of the profile/accounts service
+    pub vssps_url: Option<String>,
+    /// Longest time a request to Azure DevOps may take, in seconds
+    pub request_timeout_secs: Option<u64>,
+    /// How often subscribed resources are read again, in seconds
+    pub subscription_poll_secs: Option<u64>,
+    /// Directory of user prompt templates
+    pub prompts_dir: Option<PathBuf>,
+    pub defaults: DefaultsConfig,
+    pub tools: ToolsConfig,
+    pub retry: RetryConfig,
+    pub server: ServerConfig,
+}
+
+/// `[defaults]`: arguments used by tool calls that omit them
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DefaultsConfig {
+    pub organization: Option<String>,
+    pub project: Option<String>,
+    pub team: Option<String>,
+    /// Format of large text fields and comments: `markdown` or `html`
+    pub text_format: Option<String>,
+}
+
+/// `[tools]`: which tools are exposed
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ToolsConfig {
+    pub read_only: Option<bool>,
+    pub enable: Vec<String>,
+    pub disable: Vec<String>,
+}
+
+/// `[retry]`: retries of throttled and transiently failing requests
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetryConfig {
+    pub max_retries: Option<u32>,
+    pub base_delay_ms: Option<u64>,
+    pub max_delay_secs: Option<u64>,
+}
+
+/// `[server]`: HTTP server settings, used with `--server`
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub port: Option<u16>,
+    pub bind: Option<IpAddr>,
+    pub max_connections: Option<usize>,
+    pub allowed_hosts: Vec<String>,
+    pub tls_cert: Option<PathBuf>,
+    pub tls_key: Option<PathBuf>,
+    pub shutdown_timeout_secs: Option<u64>,
+    pub auth_token_file: Option<PathBuf>,
+    pub auth_passthrough: Option<bool>,
+    pub auth_passthrough_fallback: Option<bool>,
+    pub oauth: OAuthConfig,
+}
+
+/// `[server.oauth]`: OAuth protected resource settings
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OAuthConfig {
+    pub resource: Option<String>,
+    pub issuer: Option<String>,
+    pub jwks_file: Option<PathBuf>,
+    pub audience: Option<String>,
+    pub scopes: Vec<String>,
+}
+
+impl Config {
+    /// Reads and parses the configuration file at `path`. Relative paths in
+    /// the file are resolved against the directory of the file.
+    pub fn load(path: &Path) -> Result<Config, ConfigError> {
+        let content = std::fs::read_to_string(path).map_err(|e| ConfigError::Read {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        let mut config: Config = toml::from_str(&content).map_err(|e| ConfigError::Parse {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        config.validate().map_err(|detail| ConfigError::Invalid {
+            path: path.to_path_buf(),
+            detail,
+        })?;
+        if let Some(dir) = path.parent() {
+            config.resolve_paths(dir);
+        }
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.server.max_connections == Some(0) {
+            return Err("server.max_connections must be at least 1".to_string());
+        }
+        if self.subscription_poll_secs == Some(0) {
+            return Err("subscription_poll_secs must be at least 1".to_string());
+        }
+        if let Some(format) = &self.defaults.text_format
+            && !matches!(format.as_str(), "markdown" | "html")
+        {
+            return Err(format!(
+                "defaults.text_format must be \"markdown\" or \"html\", not \"{format}\""
+            ));
+        }
+        let empty = |values: &[String]| values.iter().any(|value| value.trim().is_empty());
+        if empty(&self.server.allowed_hosts) {
+            return Err("server.allowed_hosts must not contain empty hosts".to_string());
+        }
+        if empty(&self.tools.enable) || empty(&self.tools.disable) {
+            return Err(
+                "tools.enable and tools.disable must not contain empty patterns".to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    fn resolve_paths(&mut self, dir: &Path) {
+        for path in [
+            &mut self.pat_file,
+            &mut self.prompts_dir,
+            &mut self.server.tls_cert,
+            &mut self.server.tls_key,
+            &mut self.server.auth_token_file,
+            &mut self.server.oauth.jwks_file,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if path.is_relative() {
+                *path = dir.join(&*path);
+            }
+        }
+    }
+}
+
+/// Default location of the configuration file:
+/// `$XDG_CONFIG_HOME/mcp-for-azure-devops-boards/config.toml`, falling back
+/// to `~/.config` when `XDG_CONFIG_HOME` is not set
+pub fn default_config_path() -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))?;
+    Some(config_home.join(CONFIG_DIR_NAME).join(CONFIG_FILE_NAME))
+}
+
+/// Loads the configuration file given with `--config`, or else the one at
+/// the default location if it exists
+/// The credential source called `name` in [`CREDENTIAL_SOURCES`]
+pub fn credential_kind(name: &str) -> Option<CredentialKind> {
+    CREDENTIAL_SOURCES
+        .iter()
+        .find(|(source, _)| *source == name)
+        .map(|(_, kind)| *kind)
+}
+
+fn deserialize_credential<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<CredentialKind>, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    credential_kind(&name).map(Some).ok_or_else(|| {
+        let names: Vec<&str> = CREDENTIAL_SOURCES.iter().map(|(name, _)| *name).collect();
+        D::Error::custom(format!(
+            "unknown credential source '{name}', expected one of: {}",
+            names.join(", ")
+        ))
+    })
+}
+
+pub fn load_config(explicit: Option<&Path>) -> Result<Option<(PathBuf, Config)>, ConfigError> {
+    let path = match explicit {
+        Some(path) => path.to_path_buf(),
+        None => match default_config_path() {
+            Some(path) if path.is_file() => path,
+            _ => return Ok(None),
+        },
+    };
+    let config = Config::load(&path)?;
+    Ok(Some((path, config)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_config(content: &str) -> (TempDir, PathBuf) {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        std::fs::write(&path, content).unwrap();
+        (tmp, path)
+    }
+
+    #[test]
+    fn test_load_full_config() {
+        let (tmp, path) = write_config(
+            r#"
+credential = "azure-cli"
+base_url = "https://tfs.example.com/tfs/{organization}"
+request_timeout_secs = 60
+
+[defaults]
+organization = "contoso"
+project = "Fabrikam"
+text_format = "html"
+
+[tools]
+read_only = true
+enable = ["azdo_list_*"]
+
+[retry]
+max_retries = 5
+
+[server]
+port = 8443
+bind = "127.0.0.1"
+tls_cert = "certs/server.crt"
+tls_key = "/etc/mcp/server.key"
+
+[server.oauth]
+resource = "https://mcp.example.com/mcp"
+scopes = ["boards.read"]
+"#,
+        );
+        let config = Config::load(&path).unwrap();
+        assert_eq!(config.credential, Some(CredentialKind::AzureCli));
+        assert_eq!(config.request_timeout_secs, Some(60));
+        assert_eq!(config.defaults.project.as_deref(), Some("Fabrikam"));
+        assert_eq!(config.defaults.text_format.as_deref(), Some("html"));
+        assert_eq!(config.tools.read_only, Some(true));
+        assert_eq!(config.tools.enable, vec!["azdo_list_*".to_string()]);
+        assert_eq!(config.retry.max_retries, Some(5));
+        assert_eq!(config.server.port, Some(8443));
+        assert_eq!(config.server.bind, Some(IpAddr::from([127, 0, 0, 1])));
+        assert_eq!(
+            config.server.tls_cert,
+            Some(tmp.path().join("certs/server.crt")),
+            "relative paths are resolved against the config file's directory"
+        );
+        assert_eq!(
+            config.server.tls_key,
+            Some(PathBuf::from("/etc/mcp/server.key"))
+        );
+        assert_eq!(config.server.oauth.scopes, vec!["boards.read".to_string()]);
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_config() {
+        for content in [
+            "unknown_setting = 1",
+            "credential = \"kerberos\"",
+            "[server]\nmax_connections = 0",
+            "[server]\nbind = \"localhost\"",
+            "[defaults]\ntext_format = \"rtf\"",
+            "[tools]\ndisable = [\" \"]",
+        ] {
+            let (_tmp, path) = write_config(content);
+            let error = Config::load(&path).expect_err(content);
+            assert!(
+                matches!(
+                    error,
+                    ConfigError::Parse { .. } | ConfigError::Invalid { .. }
+                ),
+                "unexpected error for {content:?}: {error}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_load_config_requires_explicit_file() {
+        let tmp = TempDir::new().unwrap();
+        let missing = tmp.path().join("missing.toml");
+        assert!(matches!(
+            load_config(Some(&missing)),
+            Err(ConfigError::Read { .. })
+        ));
+    }
+}
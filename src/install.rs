@@ -112,6 +112,28 @@ pub fn install(
     config_path: &Path,
     binary_path: &Path,
 ) -> Result<String, Box<InstallError>> {
+    install_with_server_config(target, config_path, binary_path, None)
+}
+
+/// Like [`install`], but the generated entry starts the server with
+/// `--config <server_config>`, so the client uses that configuration file
+pub fn install_with_server_config(
+    target: &InstallTarget,
+    config_path: &Path,
+    binary_path: &Path,
+    server_config: Option<&Path>,
+) -> Result<String, Box<InstallError>> {
+    let server_args = match server_config {
+        Some(path) => {
+            let path = std::fs::canonicalize(path).map_err(|e| InstallError::ReadConfig {
+                path: path.to_path_buf(),
+                source: e,
+            })?;
+            vec!["--config".to_string(), path.to_string_lossy().into_owned()]
+        }
+        None => Vec::new(),
+    };
+
     if let Some(parent) = config_path.parent() {
         std::fs::create_dir_all(parent).map_err(|e| InstallError::CreateDirectory {
             path: parent.to_path_buf(),
@@ -120,9 +142,11 @@ pub fn install(
     }
 
     match target {
-        InstallTarget::Codex => install_toml(config_path, binary_path)?,
-        InstallTarget::Vscode => install_json(config_path, binary_path, "servers", true)?,
-        _ => install_json(config_path, binary_path, "mcpServers", false)?,
+        InstallTarget::Codex => install_toml(config_path, binary_path, &server_args)?,
+        InstallTarget::Vscode => {
+            install_json(config_path, binary_path, &server_args, "servers", true)?
+        }
+        _ => install_json(config_path, binary_path, &server_args, "mcpServers", false)?,
     }
 
     Ok(format!(
@@ -135,6 +159,7 @@ pub fn install(
 fn install_json(
     config_path: &Path,
     binary_path: &Path,
+    server_args: &[String],
     servers_key: &str,
     include_type_stdio: bool,
 ) -> Result<(), Box<InstallError>> {
@@ -190,6 +215,9 @@ fn install_json(
         "command".to_string(),
         serde_json::Value::String(binary_str.into_owned()),
     );
+    if !server_args.is_empty() {
+        entry.insert("args".to_string(), serde_json::json!(server_args));
+    }
 
     servers.insert(SERVER_NAME.to_string(), serde_json::Value::Object(entry));
 
@@ -206,7 +234,11 @@ fn install_json(
     Ok(())
 }
 
-fn install_toml(config_path: &Path, binary_path: &Path) -> Result<(), Box<InstallError>> {
+fn install_toml(
+    config_path: &Path,
+    binary_path: &Path,
+    server_args: &[String],
+) -> Result<(), Box<InstallError>> {
     let content = match std::fs::read_to_string(config_path) {
         Ok(s) if s.is_empty() => String::new(),
         Ok(s) => s,
@@ -249,6 +281,10 @@ fn install_toml(config_path: &Path, binary_path: &Path) -> Result<(), Box<Instal
     let binary_str = binary_path.to_string_lossy().into_owned();
     let mut entry = toml::map::Map::new();
     entry.insert("command".to_string(), toml::Value::String(binary_str));
+    if !server_args.is_empty() {
+        let args = server_args.iter().cloned().map(toml::Value::String);
+        entry.insert("args".to_string(), toml::Value::Array(args.collect()));
+    }
 
     mcp_servers.insert(SERVER_NAME.to_string(), toml::Value::Table(entry));
 
@@ -621,6 +657,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_install_with_server_config_adds_config_args() {
+        let tmp = TempDir::new().unwrap();
+        let server_config = tmp.path().join("config.toml");
+        std::fs::write(&server_config, "").unwrap();
+        let expected = std::fs::canonicalize(&server_config)
+            .unwrap()
+            .to_string_lossy()
+            .into_owned();
+
+        let json_path = tmp.path().join(".claude.json");
+        install_with_server_config(
+            &InstallTarget::ClaudeCode,
+            &json_path,
+            &binary_path(),
+            Some(&server_config),
+        )
+        .unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(
+            json["mcpServers"][SERVER_NAME]["args"],
+            serde_json::json!(["--config", expected])
+        );
+
+        let toml_path = tmp.path().join("config-codex.toml");
+        install_with_server_config(
+            &InstallTarget::Codex,
+            &toml_path,
+            &binary_path(),
+            Some(&server_config),
+        )
+        .unwrap();
+        let toml: toml::Value =
+            toml::from_str(&std::fs::read_to_string(&toml_path).unwrap()).unwrap();
+        assert_eq!(
+            toml["mcp_servers"][SERVER_NAME]["args"][1].as_str(),
+            Some(expected.as_str())
+        );
+
+        let result = install_with_server_config(
+            &InstallTarget::Cursor,
+            &tmp.path().join("mcp.json"),
+            &binary_path(),
+            Some(&tmp.path().join("missing.toml")),
+        );
+        assert!(matches!(
+            result.map_err(|e| *e),
+            Err(InstallError::ReadConfig { .. })
+        ));
+    }
+
     #[test]
     fn test_resolve_config_path_claude_code() {
         let path = resolve_config_path(&InstallTarget::ClaudeCode).unwrap();
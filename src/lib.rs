@@ -1,5 +1,7 @@
 pub mod azure;
 pub mod compact_llm;
+pub mod config;
 pub mod install;
 pub mod mcp;
+pub mod metrics;
 pub mod server;
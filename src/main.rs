@@ -1,12 +1,33 @@
-use clap::Parser;
-use mcp_for_azure_devops_boards::azure::client::AzureDevOpsClient;
+use clap::builder::{PossibleValue, PossibleValuesParser, TypedValueParser};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser};
+use mcp_for_azure_devops_boards::azure::client::{
+    AzureDevOpsClient, CredentialKind, CredentialOptions, DEFAULT_BASE_URL, DEFAULT_VSSPS_URL,
+    ServiceEndpoints,
+};
+use mcp_for_azure_devops_boards::azure::retry::RetryPolicy;
+use mcp_for_azure_devops_boards::config::{
+    CREDENTIAL_SOURCES, Config, ConfigError, credential_kind, load_config,
+};
 use mcp_for_azure_devops_boards::install::{
-    InstallError, InstallTarget, install, resolve_config_path,
+    InstallError, InstallTarget, install_with_server_config, resolve_config_path,
 };
+use mcp_for_azure_devops_boards::mcp::defaults::ToolDefaults;
+use mcp_for_azure_devops_boards::mcp::prompts::load_prompts_dir;
 use mcp_for_azure_devops_boards::mcp::server::AzureMcpServer;
-use mcp_for_azure_devops_boards::server::http;
+use mcp_for_azure_devops_boards::mcp::tool_filter::ToolFilter;
+use mcp_for_azure_devops_boards::server::auth::{
+    AuthConfigError, InboundAuth, OAuthResource, load_static_tokens,
+};
+use mcp_for_azure_devops_boards::server::http::{
+    self, DEFAULT_MAX_CONNECTIONS, DEFAULT_SHUTDOWN_TIMEOUT, HttpServerOptions,
+};
+use mcp_for_azure_devops_boards::server::tls::load_server_config;
 use rmcp::ServiceExt;
 use rmcp::transport::stdio;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -15,10 +36,55 @@ pub(crate) struct Args {
     #[arg(long)]
     server: bool,
 
+    /// TOML configuration file (default:
+    /// $XDG_CONFIG_HOME/mcp-for-azure-devops-boards/config.toml, if it exists).
+    /// Flags and environment variables override its settings. With
+    /// --install, the generated client entry points to this file.
+    #[arg(long, value_name = "PATH", env = "MCP_AZURE_DEVOPS_CONFIG")]
+    config: Option<PathBuf>,
+
     /// Port to run the server on
     #[arg(long, default_value_t = 3000)]
     port: u16,
 
+    /// Address the HTTP server listens on, e.g. 127.0.0.1 to accept only
+    /// local clients or :: for all IPv6 (and, on most systems, IPv4) interfaces
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        default_value = "0.0.0.0",
+        requires = "server"
+    )]
+    bind: IpAddr,
+
+    /// Maximum number of HTTP connections served at once
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = DEFAULT_MAX_CONNECTIONS,
+        value_parser = parse_max_connections,
+        requires = "server"
+    )]
+    max_connections: usize,
+
+    /// PEM certificate chain to serve HTTPS with (requires --tls-key)
+    #[arg(long, value_name = "PATH", requires_all = ["server", "tls_key"])]
+    tls_cert: Option<PathBuf>,
+
+    /// PEM private key of --tls-cert
+    #[arg(long, value_name = "PATH", requires = "tls_cert")]
+    tls_key: Option<PathBuf>,
+
+    /// On SIGTERM or Ctrl-C, how long to wait for in-flight requests and open
+    /// connections to finish before exiting
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        default_value_t = DEFAULT_SHUTDOWN_TIMEOUT.as_secs(),
+        requires = "server"
+    )]
+    shutdown_timeout_secs: u64,
+
     /// Install MCP server configuration for the specified client
     #[arg(long, value_enum, conflicts_with = "server")]
     install: Option<InstallTarget>,
@@ -37,6 +103,408 @@ pub(crate) struct Args {
         value_parser = parse_allowed_host
     )]
     allowed_hosts: Vec<String>,
+
+    /// Act on behalf of each HTTP client: tool calls are sent to Azure DevOps
+    /// with the request's `Authorization` header (a bearer access token, or a
+    /// personal access token as basic credentials) instead of the server's
+    /// identity. Requests without the header are rejected.
+    #[arg(
+        long,
+        requires = "server",
+        conflicts_with_all = ["install", "auth_tokens", "auth_token_file", "oauth_resource"]
+    )]
+    auth_passthrough: bool,
+
+    /// With --auth-passthrough, run requests without an `Authorization`
+    /// header with the server's identity instead of rejecting them
+    #[arg(long, requires = "auth_passthrough")]
+    auth_passthrough_fallback: bool,
+
+    /// Bearer token HTTP clients must send to use the server (repeatable).
+    /// Prefer the environment variable (comma-separated) or --auth-token-file
+    /// so tokens do not show up in the process list.
+    #[arg(
+        long = "auth-token",
+        value_name = "TOKEN",
+        env = "MCP_AUTH_TOKENS",
+        value_delimiter = ',',
+        hide_env_values = true
+    )]
+    auth_tokens: Vec<String>,
+
+    /// File with the bearer tokens HTTP clients may use, one per line (lines
+    /// starting with `#` are ignored)
+    #[arg(long, value_name = "PATH", env = "MCP_AUTH_TOKEN_FILE")]
+    auth_token_file: Option<PathBuf>,
+
+    /// Canonical URL of this server's MCP endpoint (e.g.
+    /// https://mcp.example.com/mcp). Enables OAuth: JWT access tokens issued by
+    /// --oauth-issuer are accepted and the protected resource metadata is
+    /// published under /.well-known/oauth-protected-resource.
+    #[arg(
+        long,
+        value_name = "URL",
+        requires_all = ["server", "oauth_issuer", "oauth_jwks_file"]
+    )]
+    oauth_resource: Option<String>,
+
+    /// Issuer of the accepted access tokens, advertised as the authorization
+    /// server in the protected resource metadata
+    #[arg(long, value_name = "URL", requires = "oauth_resource")]
+    oauth_issuer: Option<String>,
+
+    /// JWKS file with the public keys the access tokens are signed with
+    #[arg(long, value_name = "PATH", requires = "oauth_resource")]
+    oauth_jwks_file: Option<PathBuf>,
+
+    /// Expected `aud` claim of access tokens (default: --oauth-resource)
+    #[arg(long, value_name = "AUDIENCE", requires = "oauth_resource")]
+    oauth_audience: Option<String>,
+
+    /// Scope access tokens must carry (repeatable)
+    #[arg(
+        long = "oauth-scope",
+        value_name = "SCOPE",
+        requires = "oauth_resource"
+    )]
+    oauth_scopes: Vec<String>,
+
+    /// Maximum number of retries for throttled (429) or transiently failing
+    /// Azure DevOps requests. Only idempotent requests (GET, PUT, DELETE) are
+    /// retried; 0 disables retries.
+    #[arg(long, default_value_t = 3)]
+    max_retries: u32,
+
+    /// Base delay of the exponential retry backoff, in milliseconds
+    #[arg(long, value_name = "MS", default_value_t = 500)]
+    retry_base_delay_ms: u64,
+
+    /// Longest delay between retries, in seconds. A `Retry-After` longer than
+    /// this is not waited for; the throttling error is returned instead.
+    #[arg(long, value_name = "SECONDS", default_value_t = 30)]
+    retry_max_delay_secs: u64,
+
+    /// Organization or collection URL of the Azure DevOps deployment.
+    /// `{organization}` is replaced by the organization passed to each tool,
+    /// e.g. `https://{organization}.visualstudio.com` or, for Azure DevOps
+    /// Server, `https://tfs.example.com/tfs/{organization}` (the collection).
+    #[arg(long, value_name = "URL", default_value = DEFAULT_BASE_URL)]
+    base_url: String,
+
+    /// URL of the profile/accounts service used to list organizations and
+    /// the current user
+    #[arg(long, value_name = "URL", default_value = DEFAULT_VSSPS_URL)]
+    vssps_url: String,
+
+    /// Use only this credential source instead of trying each source of the
+    /// fallback chain in order
+    #[arg(long = "credential", value_name = "SOURCE", value_parser = credential_source_parser())]
+    credential_source: Option<CredentialKind>,
+
+    /// File holding a personal access token (takes precedence over the
+    /// AZURE_DEVOPS_PAT and AZURE_DEVOPS_PAT_FILE environment variables)
+    #[arg(long, value_name = "PATH")]
+    pat_file: Option<PathBuf>,
+
+    /// Default organization of tool calls that omit one
+    #[arg(
+        long,
+        value_name = "NAME",
+        env = "AZURE_DEVOPS_ORG",
+        conflicts_with = "install"
+    )]
+    organization: Option<String>,
+
+    /// Default project of tool calls that omit one
+    #[arg(
+        long,
+        value_name = "NAME",
+        env = "AZURE_DEVOPS_PROJECT",
+        conflicts_with = "install"
+    )]
+    project: Option<String>,
+
+    /// Default team (ID or name) of tool calls that omit one
+    #[arg(
+        long,
+        value_name = "NAME",
+        env = "AZURE_DEVOPS_TEAM",
+        conflicts_with = "install"
+    )]
+    team: Option<String>,
+
+    /// Default format of large text fields and comments written by tool calls
+    /// that omit `format`
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        value_parser = ["markdown", "html"],
+        conflicts_with = "install"
+    )]
+    text_format: Option<String>,
+
+    /// Longest time a request to Azure DevOps may take, in seconds (default:
+    /// no limit)
+    #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    request_timeout_secs: Option<u64>,
+
+    /// How often resources a client subscribed to are read again to detect
+    /// changes, in seconds
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        default_value_t = 60,
+        value_parser = clap::value_parser!(u64).range(1..),
+        conflicts_with = "install"
+    )]
+    subscription_poll_secs: u64,
+
+    /// Directory of user prompt templates (`*.toml` files), added to the
+    /// built-in prompts; a prompt named like a built-in one replaces it
+    #[arg(long, value_name = "PATH", conflicts_with = "install")]
+    prompts_dir: Option<PathBuf>,
+
+    /// Expose only tools that do not modify Azure DevOps (no creating,
+    /// updating, commenting, linking or uploading)
+    #[arg(long, conflicts_with = "install")]
+    read_only: bool,
+
+    /// Expose only the tools matching this pattern (repeatable; `*` matches
+    /// any characters, e.g. azdo_list_*)
+    #[arg(
+        long = "enable-tool",
+        value_name = "PATTERN",
+        conflicts_with = "install",
+        value_parser = parse_tool_pattern
+    )]
+    enabled_tools: Vec<String>,
+
+    /// Hide the tools matching this pattern (repeatable; applied after
+    /// --enable-tool)
+    #[arg(
+        long = "disable-tool",
+        value_name = "PATTERN",
+        conflicts_with = "install",
+        value_parser = parse_tool_pattern
+    )]
+    disabled_tools: Vec<String>,
+}
+
+impl Args {
+    /// Takes each setting given neither as a flag nor as an environment
+    /// variable from the configuration file at `path`
+    fn merge_config(
+        &mut self,
+        path: &Path,
+        config: Config,
+        matches: &ArgMatches,
+    ) -> Result<(), ConfigError> {
+        let from_file = |id: &str| {
+            !matches!(
+                matches.value_source(id),
+                Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+            )
+        };
+        let non_empty = |values: Vec<String>| {
+            (!values.is_empty()).then(|| values.iter().map(|v| v.trim().to_string()).collect())
+        };
+        macro_rules! merge {
+            ($field:ident, $value:expr) => {
+                if let Some(value) = $value
+                    && from_file(stringify!($field))
+                {
+                    self.$field = value;
+                }
+            };
+        }
+
+        merge!(credential_source, config.credential.map(Some));
+        merge!(pat_file, config.pat_file.map(Some));
+        merge!(base_url, config.base_url);
+        merge!(vssps_url, config.vssps_url);
+        merge!(request_timeout_secs, config.request_timeout_secs.map(Some));
+        merge!(subscription_poll_secs, config.subscription_poll_secs);
+        merge!(prompts_dir, config.prompts_dir.map(Some));
+        merge!(organization, config.defaults.organization.map(Some));
+        merge!(project, config.defaults.project.map(Some));
+        merge!(team, config.defaults.team.map(Some));
+        merge!(text_format, config.defaults.text_format.map(Some));
+        merge!(read_only, config.tools.read_only);
+        merge!(enabled_tools, non_empty(config.tools.enable));
+        merge!(disabled_tools, non_empty(config.tools.disable));
+        merge!(max_retries, config.retry.max_retries);
+        merge!(retry_base_delay_ms, config.retry.base_delay_ms);
+        merge!(retry_max_delay_secs, config.retry.max_delay_secs);
+        merge!(port, config.server.port);
+        merge!(bind, config.server.bind);
+        merge!(max_connections, config.server.max_connections);
+        merge!(allowed_hosts, non_empty(config.server.allowed_hosts));
+        merge!(tls_cert, config.server.tls_cert.map(Some));
+        merge!(tls_key, config.server.tls_key.map(Some));
+        merge!(shutdown_timeout_secs, config.server.shutdown_timeout_secs);
+        merge!(auth_token_file, config.server.auth_token_file.map(Some));
+        merge!(auth_passthrough, config.server.auth_passthrough);
+        merge!(
+            auth_passthrough_fallback,
+            config.server.auth_passthrough_fallback
+        );
+        merge!(oauth_resource, config.server.oauth.resource.map(Some));
+        merge!(oauth_issuer, config.server.oauth.issuer.map(Some));
+        merge!(oauth_jwks_file, config.server.oauth.jwks_file.map(Some));
+        merge!(oauth_audience, config.server.oauth.audience.map(Some));
+        merge!(oauth_scopes, non_empty(config.server.oauth.scopes));
+
+        self.check_merged().map_err(|detail| ConfigError::Invalid {
+            path: path.to_path_buf(),
+            detail,
+        })
+    }
+
+    /// The combinations clap enforces between flags, checked again once the
+    /// configuration file has been merged
+    fn check_merged(&self) -> Result<(), String> {
+        if self.tls_cert.is_some() != self.tls_key.is_some() {
+            return Err("a TLS certificate and its key must be set together".to_string());
+        }
+        let oauth_settings = self.oauth_issuer.is_some()
+            || self.oauth_jwks_file.is_some()
+            || self.oauth_audience.is_some()
+            || !self.oauth_scopes.is_empty();
+        if self.oauth_resource.is_some()
+            && (self.oauth_issuer.is_none() || self.oauth_jwks_file.is_none())
+        {
+            return Err("the OAuth resource requires an issuer and a JWKS file".to_string());
+        }
+        if self.oauth_resource.is_none() && oauth_settings {
+            return Err("OAuth settings require the OAuth resource".to_string());
+        }
+        if self.auth_passthrough
+            && (!self.auth_tokens.is_empty()
+                || self.auth_token_file.is_some()
+                || self.oauth_resource.is_some())
+        {
+            return Err(
+                "auth passthrough cannot be combined with inbound authentication".to_string(),
+            );
+        }
+        if self.auth_passthrough_fallback && !self.auth_passthrough {
+            return Err("the auth passthrough fallback requires auth passthrough".to_string());
+        }
+        Ok(())
+    }
+
+    fn credential_options(&self) -> CredentialOptions {
+        CredentialOptions {
+            source: self.credential_source,
+            pat_file: self.pat_file.clone(),
+        }
+    }
+
+    fn tool_defaults(&self) -> ToolDefaults {
+        let non_empty = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        ToolDefaults {
+            organization: non_empty(&self.organization),
+            project: non_empty(&self.project),
+            team: non_empty(&self.team),
+            text_format: self.text_format.clone(),
+        }
+    }
+
+    fn tool_filter(&self) -> ToolFilter {
+        ToolFilter {
+            read_only: self.read_only,
+            enabled: self.enabled_tools.clone(),
+            disabled: self.disabled_tools.clone(),
+        }
+    }
+
+    fn inbound_auth(&self) -> Result<InboundAuth, AuthConfigError> {
+        let mut auth = InboundAuth::default().with_static_tokens(self.auth_tokens.clone());
+        if let Some(path) = &self.auth_token_file {
+            auth = auth.with_static_tokens(load_static_tokens(path)?);
+        }
+        if let (Some(resource), Some(issuer), Some(jwks_file)) = (
+            &self.oauth_resource,
+            &self.oauth_issuer,
+            &self.oauth_jwks_file,
+        ) {
+            let mut oauth = OAuthResource::from_jwks_file(resource, issuer, jwks_file)?
+                .with_required_scopes(self.oauth_scopes.clone());
+            if let Some(audience) = &self.oauth_audience {
+                oauth = oauth.with_audience(audience);
+            }
+            auth = auth.with_oauth(oauth);
+        }
+        Ok(auth)
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_retries: self.max_retries,
+            base_delay: Duration::from_millis(self.retry_base_delay_ms),
+            max_delay: Duration::from_secs(self.retry_max_delay_secs),
+        }
+    }
+}
+
+/// Accepts the names of [`CREDENTIAL_SOURCES`], listed in `--help`
+fn credential_source_parser() -> impl TypedValueParser<Value = CredentialKind> {
+    PossibleValuesParser::new(
+        CREDENTIAL_SOURCES.map(|(name, kind)| PossibleValue::new(name).help(kind.label())),
+    )
+    .map(|name| credential_kind(&name).expect("only credential source names are accepted"))
+}
+
+fn parse_max_connections(value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(0) => Err("must be at least 1".to_string()),
+        Ok(max_connections) => Ok(max_connections),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn parse_tool_pattern(value: &str) -> Result<String, String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err("tool pattern must not be empty".to_string());
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Resolves on SIGTERM (sent by Kubernetes and most process managers) or
+/// Ctrl-C, starting a graceful shutdown of the HTTP server.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            log::error!("Failed to listen for Ctrl-C: {e}");
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                log::error!("Failed to listen for SIGTERM: {e}");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => log::info!("Received Ctrl-C"),
+        () = terminate => log::info!("Received SIGTERM"),
+    }
 }
 
 /// Validates a single `--allowed-host` value. Rejects empty / whitespace-only
@@ -54,29 +522,80 @@ fn parse_allowed_host(value: &str) -> Result<String, String> {
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::init();
-    let args = Args::parse();
+    let matches = Args::command().get_matches();
+    let mut args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
 
     if let Some(target) = &args.install {
         let binary_path =
             std::env::current_exe().map_err(|e| InstallError::BinaryPathDetection { source: e })?;
         let config_path = resolve_config_path(target)?;
-        let message = install(target, &config_path, &binary_path)?;
+        let message =
+            install_with_server_config(target, &config_path, &binary_path, args.config.as_deref())?;
         println!("{message}");
         return Ok(());
     }
 
-    let client = AzureDevOpsClient::new();
-    let mcp_server = AzureMcpServer::new(client);
+    if let Some((path, config)) = load_config(args.config.as_deref())? {
+        log::info!("Using config file {}", path.display());
+        args.merge_config(&path, config, &matches)?;
+    }
+
+    let endpoints = ServiceEndpoints::new(&args.base_url, &args.vssps_url)?;
+    let mut client = AzureDevOpsClient::new()
+        .with_credential_options(&args.credential_options())?
+        .with_retry_policy(args.retry_policy())
+        .with_endpoints(endpoints);
+    if let Some(secs) = args.request_timeout_secs {
+        client = client.with_request_timeout(Duration::from_secs(secs))?;
+    }
+    let mcp_server = if args.auth_passthrough {
+        AzureMcpServer::new_with_caller_passthrough(client)
+            .with_server_identity_fallback(args.auth_passthrough_fallback)
+    } else {
+        AzureMcpServer::new(client)
+    }
+    .with_defaults(args.tool_defaults())
+    .with_tool_filter(&args.tool_filter())
+    .with_subscription_poll_interval(Duration::from_secs(args.subscription_poll_secs));
+    let mcp_server = match &args.prompts_dir {
+        Some(dir) => {
+            let prompts = load_prompts_dir(dir)?;
+            log::info!("Loaded {} prompts from {}", prompts.len(), dir.display());
+            mcp_server.with_prompts(prompts)
+        }
+        None => mcp_server,
+    };
 
     if args.server {
-        let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", args.port)).await?;
-        log::warn!(
-            "HTTP server binds 0.0.0.0 and is unauthenticated. Host-header validation guards \
-             against DNS rebinding but is NOT network access control; deploy behind a firewall \
-             or reverse proxy."
+        let auth = args.inbound_auth()?;
+        let tls = match (&args.tls_cert, &args.tls_key) {
+            (Some(cert), Some(key)) => Some(load_server_config(cert, key)?),
+            _ => None,
+        };
+        let listener = tokio::net::TcpListener::bind((args.bind, args.port)).await?;
+        if !auth.is_enabled() && !args.bind.is_loopback() {
+            log::warn!(
+                "HTTP server binds {} and is unauthenticated. Host-header validation guards \
+                 against DNS rebinding but is NOT network access control; configure \
+                 --auth-token-file or --oauth-resource, or deploy behind a firewall or reverse \
+                 proxy.",
+                args.bind
+            );
+        }
+        log::info!(
+            "Starting web server on {}://{}",
+            if tls.is_some() { "https" } else { "http" },
+            listener.local_addr()?
         );
-        log::info!("Starting web server on {}", listener.local_addr()?);
-        http::run_server(mcp_server, listener, args.allowed_hosts).await?;
+        let options = HttpServerOptions {
+            allowed_hosts: args.allowed_hosts,
+            auth,
+            max_connections: args.max_connections,
+            tls,
+            shutdown_timeout: Duration::from_secs(args.shutdown_timeout_secs),
+        };
+        http::run_server_with_shutdown(mcp_server, listener, options, shutdown_signal()).await?;
+        log::info!("Web server stopped");
     } else {
         log::info!("Starting stdio server");
         let service = mcp_server.serve(stdio()).await?;
@@ -223,6 +742,306 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_retry_policy_defaults_and_overrides() {
+        let args = Args::try_parse_from(["test"]).unwrap();
+        assert_eq!(args.retry_policy(), RetryPolicy::default());
+
+        let args = Args::try_parse_from([
+            "test",
+            "--max-retries",
+            "0",
+            "--retry-base-delay-ms",
+            "100",
+            "--retry-max-delay-secs",
+            "5",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.retry_policy(),
+            RetryPolicy {
+                max_retries: 0,
+                base_delay: Duration::from_millis(100),
+                max_delay: Duration::from_secs(5),
+            }
+        );
+    }
+
+    #[test]
+    fn test_base_url_defaults_and_overrides() {
+        let args = Args::try_parse_from(["test"]).unwrap();
+        assert_eq!(args.base_url, DEFAULT_BASE_URL);
+        assert_eq!(args.vssps_url, DEFAULT_VSSPS_URL);
+
+        let args = Args::try_parse_from([
+            "test",
+            "--base-url",
+            "https://tfs.example.com/tfs/{organization}",
+        ])
+        .unwrap();
+        assert_eq!(args.base_url, "https://tfs.example.com/tfs/{organization}");
+    }
+
+    #[test]
+    fn test_credential_source_flags() {
+        let args = Args::try_parse_from(["test"]).unwrap();
+        assert_eq!(args.credential_options().source, None);
+        assert_eq!(args.credential_options().pat_file, None);
+
+        let args = Args::try_parse_from(["test", "--credential", "pat", "--pat-file", "/run/pat"])
+            .unwrap();
+        let options = args.credential_options();
+        assert_eq!(options.source, Some(CredentialKind::Pat));
+        assert_eq!(options.pat_file, Some(PathBuf::from("/run/pat")));
+
+        for source in [
+            "basic",
+            "environment",
+            "azure-cli",
+            "azure-developer-cli",
+            "managed-identity",
+        ] {
+            Args::try_parse_from(["test", "--credential", source])
+                .unwrap_or_else(|e| panic!("failed to parse --credential {source}: {e}"));
+        }
+        assert!(Args::try_parse_from(["test", "--credential", "kerberos"]).is_err());
+    }
+
+    #[test]
+    fn test_auth_passthrough_requires_server() {
+        assert!(!Args::try_parse_from(["test"]).unwrap().auth_passthrough);
+        let args = Args::try_parse_from(["test", "--server", "--auth-passthrough"]).unwrap();
+        assert!(args.auth_passthrough);
+        let err = Args::try_parse_from(["test", "--auth-passthrough"])
+            .expect_err("--auth-passthrough without --server must be rejected");
+        assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+
+        let args = Args::try_parse_from([
+            "test",
+            "--server",
+            "--auth-passthrough",
+            "--auth-passthrough-fallback",
+        ])
+        .unwrap();
+        assert!(args.auth_passthrough_fallback);
+        let err = Args::try_parse_from(["test", "--server", "--auth-passthrough-fallback"])
+            .expect_err("--auth-passthrough-fallback without --auth-passthrough must be rejected");
+        assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn test_inbound_auth_flags() {
+        let args = Args::try_parse_from(["test", "--server"]).unwrap();
+        assert!(!args.inbound_auth().unwrap().is_enabled());
+
+        let args = Args::try_parse_from(["test", "--server", "--auth-token", "a,b"]).unwrap();
+        assert_eq!(args.auth_tokens, vec!["a".to_string(), "b".to_string()]);
+        assert!(args.inbound_auth().unwrap().is_enabled());
+
+        let err = Args::try_parse_from(["test", "--server", "--oauth-resource", "https://x/mcp"])
+            .expect_err("--oauth-resource needs an issuer and a JWKS file");
+        assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+
+        let err = Args::try_parse_from([
+            "test",
+            "--server",
+            "--auth-passthrough",
+            "--auth-token",
+            "a",
+        ])
+        .expect_err("caller passthrough cannot be combined with inbound auth");
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+
+        let args = Args::try_parse_from([
+            "test",
+            "--server",
+            "--oauth-resource",
+            "https://mcp.example.com/mcp",
+            "--oauth-issuer",
+            "https://login.example.com",
+            "--oauth-jwks-file",
+            "/nonexistent/jwks.json",
+        ])
+        .unwrap();
+        assert!(matches!(
+            args.inbound_auth(),
+            Err(AuthConfigError::Read { .. })
+        ));
+    }
+
+    #[test]
+    fn test_listener_and_tls_flags() {
+        let args = Args::try_parse_from(["test", "--server"]).unwrap();
+        assert_eq!(args.bind, IpAddr::from([0, 0, 0, 0]));
+        assert_eq!(args.max_connections, DEFAULT_MAX_CONNECTIONS);
+        assert_eq!(
+            args.shutdown_timeout_secs,
+            DEFAULT_SHUTDOWN_TIMEOUT.as_secs()
+        );
+        assert_eq!(args.tls_cert, None);
+
+        let args = Args::try_parse_from([
+            "test",
+            "--server",
+            "--bind",
+            "::1",
+            "--max-connections",
+            "16",
+            "--tls-cert",
+            "cert.pem",
+            "--tls-key",
+            "key.pem",
+            "--shutdown-timeout-secs",
+            "5",
+        ])
+        .unwrap();
+        assert!(args.bind.is_loopback());
+        assert_eq!(args.max_connections, 16);
+        assert_eq!(args.tls_cert, Some(PathBuf::from("cert.pem")));
+        assert_eq!(args.tls_key, Some(PathBuf::from("key.pem")));
+        assert_eq!(args.shutdown_timeout_secs, 5);
+
+        for invalid in [
+            vec!["test", "--server", "--max-connections", "0"],
+            vec!["test", "--server", "--bind", "localhost"],
+        ] {
+            let err = Args::try_parse_from(&invalid).expect_err("must be rejected");
+            assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation);
+        }
+        let err = Args::try_parse_from(["test", "--server", "--tls-cert", "cert.pem"])
+            .expect_err("--tls-cert without --tls-key must be rejected");
+        assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+        let err = Args::try_parse_from(["test", "--bind", "127.0.0.1"])
+            .expect_err("--bind without --server must be rejected");
+        assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn test_tool_defaults_flags() {
+        let args =
+            Args::try_parse_from(["test", "--organization", "contoso", "--project", " "]).unwrap();
+        assert_eq!(
+            args.tool_defaults(),
+            ToolDefaults {
+                organization: Some("contoso".to_string()),
+                project: None,
+                team: None,
+                text_format: None,
+            }
+        );
+    }
+
+    /// Parses `argv` and merges the configuration file holding `config`
+    fn args_with_config(argv: &[&str], config: &str) -> Result<Args, ConfigError> {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        std::fs::write(&path, config).unwrap();
+        let matches = Args::command().try_get_matches_from(argv).unwrap();
+        let mut args = Args::from_arg_matches(&matches).unwrap();
+        args.merge_config(&path, Config::load(&path)?, &matches)?;
+        Ok(args)
+    }
+
+    #[test]
+    fn test_config_file_fills_unset_settings() {
+        let config = r#"
+credential = "pat"
+request_timeout_secs = 20
+
+[defaults]
+organization = "file-org"
+project = "file-project"
+text_format = "html"
+
+[tools]
+read_only = true
+disable = ["azdo_get_attachment"]
+
+[retry]
+max_retries = 7
+
+[server]
+port = 8080
+max_connections = 8
+allowed_hosts = ["mcp.example.com"]
+"#;
+        let args = args_with_config(
+            &[
+                "test",
+                "--server",
+                "--port",
+                "9090",
+                "--project",
+                "flag-project",
+            ],
+            config,
+        )
+        .unwrap();
+        // Flags win over the file...
+        assert_eq!(args.port, 9090);
+        assert_eq!(args.project.as_deref(), Some("flag-project"));
+        // ...which wins over the built-in defaults.
+        assert_eq!(args.credential_source, Some(CredentialKind::Pat));
+        assert_eq!(args.request_timeout_secs, Some(20));
+        assert_eq!(args.organization.as_deref(), Some("file-org"));
+        assert_eq!(args.text_format.as_deref(), Some("html"));
+        assert!(args.read_only);
+        assert_eq!(args.disabled_tools, vec!["azdo_get_attachment".to_string()]);
+        assert_eq!(args.retry_policy().max_retries, 7);
+        assert_eq!(args.max_connections, 8);
+        assert_eq!(args.allowed_hosts, vec!["mcp.example.com".to_string()]);
+    }
+
+    #[test]
+    fn test_config_file_combinations_are_checked() {
+        for config in [
+            "[server]\ntls_cert = \"server.crt\"",
+            "[server.oauth]\nresource = \"https://mcp.example.com/mcp\"",
+            "[server.oauth]\nscopes = [\"read\"]",
+            "[server]\nauth_passthrough = true\nauth_token_file = \"tokens\"",
+            "[server]\nauth_passthrough_fallback = true",
+        ] {
+            let error = args_with_config(&["test", "--server"], config).expect_err(config);
+            assert!(matches!(error, ConfigError::Invalid { .. }), "{error}");
+        }
+        let args = args_with_config(
+            &["test", "--server"],
+            "[server]\ntls_cert = \"server.crt\"\ntls_key = \"server.key\"",
+        )
+        .unwrap();
+        assert!(args.tls_cert.unwrap().ends_with("server.crt"));
+    }
+
+    #[test]
+    fn test_tool_filter_flags() {
+        let args = Args::try_parse_from(["test"]).unwrap();
+        assert!(args.tool_filter().is_empty());
+
+        let args = Args::try_parse_from([
+            "test",
+            "--read-only",
+            "--enable-tool",
+            "azdo_list_*",
+            "--enable-tool",
+            "azdo_get_work_item",
+            "--disable-tool",
+            "azdo_list_tags",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.tool_filter(),
+            ToolFilter {
+                read_only: true,
+                enabled: vec!["azdo_list_*".to_string(), "azdo_get_work_item".to_string()],
+                disabled: vec!["azdo_list_tags".to_string()],
+            }
+        );
+
+        let err = Args::try_parse_from(["test", "--disable-tool", " "])
+            .expect_err("empty tool pattern must be rejected");
+        assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation);
+    }
+
     #[test]
     fn test_install_flag_parsing() {
         let args = Args::try_parse_from(["test", "--install", "claude-code"]).unwrap();
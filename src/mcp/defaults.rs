@@ -0,0 +1,308 @@
+use rmcp::{
+    ErrorData as McpError,
+    handler::server::common::schema_for_input,
+    model::JsonObject,
+    schemars::JsonSchema,
+    serde::{Deserialize, Serialize, de::DeserializeOwned},
+};
+use serde_json::Value;
+use std::any::Any;
+use std::sync::Arc;
+
+/// Default organization, project and team of tool calls. Tools fall back to
+/// them when a call omits the corresponding argument.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolDefaults {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team: Option<String>,
+    /// Format of large text fields and comments (`markdown` or `html`) when
+    /// a call omits `format`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_format: Option<String>,
+}
+
+/// Arguments of `azdo_set_context`
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SetContextArgs {
+    /// Default AzDO org name ("" clears it)
+    #[serde(default)]
+    pub organization: Option<String>,
+    /// Default AzDO project name ("" clears it)
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Default team ID or name ("" clears it)
+    #[serde(default)]
+    pub team: Option<String>,
+}
+
+impl SetContextArgs {
+    /// Updates the session defaults with the values passed: an empty value
+    /// clears the session default, an omitted one keeps it
+    pub fn apply_to(self, session: &mut ToolDefaults) {
+        for (value, default) in [
+            (self.organization, &mut session.organization),
+            (self.project, &mut session.project),
+            (self.team, &mut session.team),
+        ] {
+            if let Some(value) = value {
+                let value = value.trim();
+                *default = (!value.is_empty()).then(|| value.to_string());
+            }
+        }
+    }
+}
+
+/// Tool arguments that can be omitted when a default is set
+const DEFAULTED_ARGUMENTS: [&str; 3] = ["organization", "project", "team_id"];
+/// Optional argument of the tools that write text fields or comments, filled
+/// from [`ToolDefaults::text_format`] when omitted
+const TEXT_FORMAT_ARGUMENT: &str = "format";
+
+impl ToolDefaults {
+    /// These defaults, with the values set in `overrides` taking precedence
+    pub fn overridden_by(&self, overrides: &ToolDefaults) -> ToolDefaults {
+        ToolDefaults {
+            organization: overrides
+                .organization
+                .clone()
+                .or_else(|| self.organization.clone()),
+            project: overrides.project.clone().or_else(|| self.project.clone()),
+            team: overrides.team.clone().or_else(|| self.team.clone()),
+            text_format: overrides
+                .text_format
+                .clone()
+                .or_else(|| self.text_format.clone()),
+        }
+    }
+
+    fn value_for(&self, argument: &str) -> Option<&str> {
+        match argument {
+            "organization" => self.organization.as_deref(),
+            "project" => self.project.as_deref(),
+            "team_id" => self.team.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Input schema of `T` in which the organization, project and team arguments
+/// are optional, since they can come from the defaults
+pub fn defaulted_input_schema<T: JsonSchema + Any>() -> Arc<JsonObject> {
+    let mut schema = schema_for_input::<T>()
+        .unwrap_or_else(|e| {
+            panic!(
+                "Invalid input schema for `{}`: {}",
+                std::any::type_name::<T>(),
+                e
+            )
+        })
+        .as_ref()
+        .clone();
+    let required = required_arguments(&schema);
+    let defaulted: Vec<&str> = DEFAULTED_ARGUMENTS
+        .into_iter()
+        .filter(|argument| required.contains(argument))
+        .collect();
+    if defaulted.is_empty() {
+        return Arc::new(schema);
+    }
+
+    if let Some(Value::Object(properties)) = schema.get_mut("properties") {
+        for argument in &defaulted {
+            if let Some(Value::Object(property)) = properties.get_mut(*argument) {
+                let description = property
+                    .get("description")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                let description = format!(
+                    "{} (optional when a default is set, see azdo_set_context)",
+                    description
+                );
+                property.insert("description".to_string(), Value::String(description));
+            }
+        }
+    }
+    if let Some(Value::Array(required)) = schema.get_mut("required") {
+        required.retain(|argument| {
+            !argument
+                .as_str()
+                .is_some_and(|argument| defaulted.contains(&argument))
+        });
+    }
+    Arc::new(schema)
+}
+
+/// Deserializes the arguments of a tool call into `T`, filling the required
+/// organization, project and team arguments the call omits from `defaults`.
+/// Optional arguments are never filled, so a default team does not turn a
+/// project-wide call into a team one.
+pub fn resolve_arguments<T: DeserializeOwned + JsonSchema + Any>(
+    mut arguments: JsonObject,
+    defaults: &ToolDefaults,
+) -> Result<T, McpError> {
+    let schema = schema_for_input::<T>().map_err(|e| McpError::internal_error(e, None))?;
+    for argument in required_arguments(&schema) {
+        if !DEFAULTED_ARGUMENTS.contains(&argument) {
+            continue;
+        }
+        let omitted = match arguments.get(argument) {
+            None | Some(Value::Null) => true,
+            Some(Value::String(value)) => value.trim().is_empty(),
+            Some(_) => false,
+        };
+        if !omitted {
+            continue;
+        }
+        match defaults.value_for(argument) {
+            Some(value) => {
+                arguments.insert(argument.to_string(), Value::String(value.to_string()));
+            }
+            None => {
+                return Err(McpError::invalid_params(
+                    format!(
+                        "Missing '{argument}': pass it, or set a default with azdo_set_context"
+                    ),
+                    None,
+                ));
+            }
+        }
+    }
+    if let Some(format) = &defaults.text_format
+        && has_property(&schema, TEXT_FORMAT_ARGUMENT)
+        && !arguments.contains_key(TEXT_FORMAT_ARGUMENT)
+    {
+        arguments.insert(
+            TEXT_FORMAT_ARGUMENT.to_string(),
+            Value::String(format.clone()),
+        );
+    }
+    serde_json::from_value(Value::Object(arguments)).map_err(|e| {
+        McpError::invalid_params(format!("failed to deserialize parameters: {e}"), None)
+    })
+}
+
+fn has_property(schema: &JsonObject, name: &str) -> bool {
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .is_some_and(|properties| properties.contains_key(name))
+}
+
+fn required_arguments(schema: &JsonObject) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| required.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::tools::classification_nodes::ListIterationPathsArgs;
+    use crate::mcp::tools::teams::GetTeamArgs;
+    use serde_json::json;
+
+    fn defaults() -> ToolDefaults {
+        ToolDefaults {
+            organization: Some("org".to_string()),
+            project: Some("proj".to_string()),
+            team: Some("team".to_string()),
+            text_format: None,
+        }
+    }
+
+    fn object(value: Value) -> JsonObject {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_overridden_by() {
+        let overrides = ToolDefaults {
+            project: Some("other".to_string()),
+            ..Default::default()
+        };
+        let merged = defaults().overridden_by(&overrides);
+        assert_eq!(merged.organization.as_deref(), Some("org"));
+        assert_eq!(merged.project.as_deref(), Some("other"));
+        assert_eq!(merged.team.as_deref(), Some("team"));
+    }
+
+    #[test]
+    fn test_set_context_args_apply_to() {
+        let mut session = defaults();
+        SetContextArgs {
+            organization: None,
+            project: Some(" other ".to_string()),
+            team: Some(String::new()),
+        }
+        .apply_to(&mut session);
+        assert_eq!(session.organization.as_deref(), Some("org"));
+        assert_eq!(session.project.as_deref(), Some("other"));
+        assert_eq!(session.team, None);
+    }
+
+    #[test]
+    fn test_defaulted_input_schema_makes_context_arguments_optional() {
+        let schema = defaulted_input_schema::<GetTeamArgs>();
+        assert_eq!(schema["required"], json!([]));
+        assert!(
+            schema["properties"]["organization"]["description"]
+                .as_str()
+                .unwrap()
+                .contains("azdo_set_context")
+        );
+    }
+
+    #[test]
+    fn test_resolve_arguments_fills_omitted_arguments() {
+        let args: GetTeamArgs =
+            resolve_arguments(object(json!({"project": "explicit"})), &defaults()).unwrap();
+        assert_eq!(args.organization, "org");
+        assert_eq!(args.project, "explicit");
+        assert_eq!(args.team_id, "team");
+    }
+
+    #[test]
+    fn test_resolve_arguments_does_not_fill_optional_team() {
+        let args: ListIterationPathsArgs =
+            resolve_arguments(JsonObject::new(), &defaults()).unwrap();
+        assert_eq!(args.project, "proj");
+        assert_eq!(args.team_id, None);
+    }
+
+    #[test]
+    fn test_resolve_arguments_fills_omitted_text_format() {
+        use crate::mcp::tools::work_items::AddCommentArgs;
+
+        let defaults = ToolDefaults {
+            text_format: Some("html".to_string()),
+            ..defaults()
+        };
+        let args: AddCommentArgs =
+            resolve_arguments(object(json!({"work_item_id": 1, "text": "hi"})), &defaults).unwrap();
+        assert_eq!(args.format, "html");
+        let args: AddCommentArgs = resolve_arguments(
+            object(json!({"work_item_id": 1, "text": "hi", "format": "markdown"})),
+            &defaults,
+        )
+        .unwrap();
+        assert_eq!(args.format, "markdown");
+    }
+
+    #[test]
+    fn test_resolve_arguments_reports_missing_default() {
+        let error = resolve_arguments::<GetTeamArgs>(
+            object(json!({"organization": "org", "project": "proj"})),
+            &ToolDefaults::default(),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+        assert!(error.message.contains("team_id"), "{}", error.message);
+    }
+}
@@ -1,2 +1,6 @@
+pub mod defaults;
+pub mod prompts;
+pub mod resources;
 pub mod server;
+pub mod tool_filter;
 pub mod tools;
@@ -0,0 +1,485 @@
+use crate::mcp::defaults::ToolDefaults;
+use rmcp::{
+    ErrorData as McpError,
+    model::{GetPromptResult, JsonObject, Prompt, PromptArgument, PromptMessage, Role},
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Prompt templates shipped with the server, in the format of user prompt files
+const BUILTIN_PROMPTS: [(&str, &str); 4] = [
+    ("triage_bugs.toml", include_str!("prompts/triage_bugs.toml")),
+    (
+        "sprint_review.toml",
+        include_str!("prompts/sprint_review.toml"),
+    ),
+    (
+        "feature_breakdown.toml",
+        include_str!("prompts/feature_breakdown.toml"),
+    ),
+    (
+        "standup_summary.toml",
+        include_str!("prompts/standup_summary.toml"),
+    ),
+];
+
+/// Prompt arguments filled from the default organization, project and team
+/// when omitted
+const CONTEXT_ARGUMENTS: [&str; 3] = ["organization", "project", "team"];
+
+#[derive(Debug, thiserror::Error)]
+pub enum PromptError {
+    #[error("Failed to read prompts from {}: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Failed to parse prompt file {}: {source}", path.display())]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+
+    #[error("Invalid prompt file {}: {detail}", path.display())]
+    Invalid { path: PathBuf, detail: String },
+}
+
+/// A prompt template, read from a TOML file. `{argument}` placeholders in the
+/// messages are replaced by the argument values.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PromptTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    pub description: String,
+    /// Tools the prompt relies on: it is hidden when one of them is not exposed
+    #[serde(default)]
+    pub tools: Vec<String>,
+    #[serde(default)]
+    pub arguments: Vec<PromptTemplateArgument>,
+    pub messages: Vec<PromptTemplateMessage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PromptTemplateArgument {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+    /// Value used when the argument is omitted
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PromptTemplateMessage {
+    #[serde(default)]
+    pub role: MessageRole,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageRole {
+    #[default]
+    User,
+    Assistant,
+}
+
+impl PromptTemplate {
+    /// Parses and validates the prompt file `content`, read from `path`
+    pub fn parse(content: &str, path: &Path) -> Result<PromptTemplate, PromptError> {
+        let template: PromptTemplate = toml::from_str(content).map_err(|e| PromptError::Parse {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        template.validate().map_err(|detail| PromptError::Invalid {
+            path: path.to_path_buf(),
+            detail,
+        })?;
+        Ok(template)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let valid_name = |name: &str| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        };
+        if !valid_name(&self.name) {
+            return Err(format!(
+                "name '{}' must be made of letters, digits, '_' and '-'",
+                self.name
+            ));
+        }
+        if self.messages.is_empty() {
+            return Err("a prompt needs at least one message".to_string());
+        }
+        let mut names = HashSet::new();
+        for argument in &self.arguments {
+            if !valid_name(&argument.name) {
+                return Err(format!(
+                    "argument name '{}' must be made of letters, digits, '_' and '-'",
+                    argument.name
+                ));
+            }
+            if !names.insert(argument.name.as_str()) {
+                return Err(format!("argument '{}' is declared twice", argument.name));
+            }
+            if argument.required && argument.default.is_some() {
+                return Err(format!(
+                    "argument '{}' cannot be both required and have a default",
+                    argument.name
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// The prompt as listed to clients
+    pub fn to_prompt(&self) -> Prompt {
+        let arguments = self
+            .arguments
+            .iter()
+            .map(|argument| {
+                let prompt_argument =
+                    PromptArgument::new(&argument.name).with_required(argument.required);
+                match &argument.description {
+                    Some(description) => prompt_argument.with_description(description),
+                    None => prompt_argument,
+                }
+            })
+            .collect();
+        let prompt = Prompt::new(&self.name, Some(&self.description), Some(arguments));
+        match &self.title {
+            Some(title) => prompt.with_title(title),
+            None => prompt,
+        }
+    }
+
+    /// Renders the messages with the arguments of a `prompts/get` request.
+    /// Omitted organization, project and team arguments come from `defaults`.
+    pub fn render(
+        &self,
+        arguments: Option<JsonObject>,
+        defaults: &ToolDefaults,
+    ) -> Result<GetPromptResult, McpError> {
+        let mut arguments = arguments.unwrap_or_default();
+        if let Some(unknown) = arguments
+            .keys()
+            .find(|name| !self.arguments.iter().any(|a| &a.name == *name))
+        {
+            return Err(McpError::invalid_params(
+                format!("Unknown argument '{unknown}' for prompt '{}'", self.name),
+                None,
+            ));
+        }
+
+        let mut values = HashMap::new();
+        for argument in &self.arguments {
+            let passed = match arguments.remove(&argument.name) {
+                Some(Value::String(value)) => Some(value.trim().to_string()),
+                Some(Value::Null) | None => None,
+                Some(value) => Some(value.to_string()),
+            }
+            .filter(|value| !value.is_empty());
+            let value = passed
+                .or_else(|| context_value(defaults, &argument.name))
+                .or_else(|| argument.default.clone());
+            let value = match value {
+                Some(value) => value,
+                None if CONTEXT_ARGUMENTS.contains(&argument.name.as_str()) => {
+                    return Err(McpError::invalid_params(
+                        format!(
+                            "Missing '{}': pass it, or set a default with azdo_set_context",
+                            argument.name
+                        ),
+                        None,
+                    ));
+                }
+                None if argument.required => {
+                    return Err(McpError::invalid_params(
+                        format!(
+                            "Missing required argument '{}' for prompt '{}'",
+                            argument.name, self.name
+                        ),
+                        None,
+                    ));
+                }
+                None => String::new(),
+            };
+            values.insert(argument.name.as_str(), value);
+        }
+
+        let messages = self
+            .messages
+            .iter()
+            .map(|message| {
+                let role = match message.role {
+                    MessageRole::User => Role::User,
+                    MessageRole::Assistant => Role::Assistant,
+                };
+                PromptMessage::new_text(role, substitute(message.text.trim(), &values))
+            })
+            .collect();
+        Ok(GetPromptResult::new(messages).with_description(&self.description))
+    }
+}
+
+fn context_value(defaults: &ToolDefaults, argument: &str) -> Option<String> {
+    match argument {
+        "organization" => defaults.organization.clone(),
+        "project" => defaults.project.clone(),
+        "team" => defaults.team.clone(),
+        _ => None,
+    }
+}
+
+/// Replaces the `{name}` placeholders of `text` whose name is in `values`,
+/// leaving any other braces untouched. Values are not scanned again, so a
+/// value containing a placeholder is inserted verbatim.
+fn substitute(text: &str, values: &HashMap<&str, String>) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after
+            .find('}')
+            .and_then(|end| values.get(&after[..end]).map(|value| (end, value)))
+        {
+            Some((end, value)) => {
+                output.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                output.push('{');
+                rest = after;
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// The prompt templates shipped with the server
+pub fn builtin_prompts() -> Vec<PromptTemplate> {
+    BUILTIN_PROMPTS
+        .iter()
+        .map(|(file, content)| {
+            PromptTemplate::parse(content, Path::new(file))
+                .unwrap_or_else(|e| panic!("Invalid built-in prompt: {e}"))
+        })
+        .collect()
+}
+
+/// Loads the user prompt templates from the `*.toml` files of `dir`
+pub fn load_prompts_dir(dir: &Path) -> Result<Vec<PromptTemplate>, PromptError> {
+    let read_error = |e| PromptError::Read {
+        path: dir.to_path_buf(),
+        source: e,
+    };
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(read_error)? {
+        let path = entry.map_err(read_error)?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "toml") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut prompts: Vec<PromptTemplate> = Vec::new();
+    for path in paths {
+        let content = std::fs::read_to_string(&path).map_err(|e| PromptError::Read {
+            path: path.clone(),
+            source: e,
+        })?;
+        let prompt = PromptTemplate::parse(&content, &path)?;
+        if prompts.iter().any(|other| other.name == prompt.name) {
+            return Err(PromptError::Invalid {
+                detail: format!(
+                    "another file of {} defines '{}'",
+                    dir.display(),
+                    prompt.name
+                ),
+                path,
+            });
+        }
+        prompts.push(prompt);
+    }
+    Ok(prompts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn object(value: Value) -> Option<JsonObject> {
+        value.as_object().cloned()
+    }
+
+    fn message_text(result: &GetPromptResult) -> String {
+        serde_json::to_value(&result.messages[0]).unwrap()["content"]["text"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    fn builtin(name: &str) -> PromptTemplate {
+        builtin_prompts()
+            .into_iter()
+            .find(|prompt| prompt.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_builtin_prompts_are_valid() {
+        let names: Vec<String> = builtin_prompts().into_iter().map(|p| p.name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "triage_bugs",
+                "sprint_review",
+                "feature_breakdown",
+                "standup_summary"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_builtin_prompts_list_the_tools_they_call() {
+        let tool_name = regex::Regex::new(r"azdo_[a-z_]+").unwrap();
+        for prompt in builtin_prompts() {
+            for message in &prompt.messages {
+                for tool in tool_name.find_iter(&message.text) {
+                    assert!(
+                        prompt.tools.iter().any(|listed| listed == tool.as_str()),
+                        "prompt '{}' calls {} without listing it in tools",
+                        prompt.name,
+                        tool.as_str()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_substitute() {
+        let values = HashMap::from([("id", "42".to_string()), ("team", "{id}".to_string())]);
+        assert_eq!(
+            substitute("Item {id} of {team}, {unknown} {", &values),
+            "Item 42 of {id}, {unknown} {"
+        );
+    }
+
+    #[test]
+    fn test_render_fills_context_and_defaults() {
+        let defaults = ToolDefaults {
+            organization: Some("contoso".to_string()),
+            project: Some("Fabrikam".to_string()),
+            ..Default::default()
+        };
+        let result = builtin("feature_breakdown")
+            .render(object(json!({"feature_id": "42"})), &defaults)
+            .unwrap();
+        let text = message_text(&result);
+        assert!(
+            text.contains("feature 42 in project \"Fabrikam\""),
+            "{text}"
+        );
+        assert!(text.contains("organization \"contoso\""), "{text}");
+        assert!(text.contains("into User Story work items"), "{text}");
+
+        let result = builtin("feature_breakdown")
+            .render(
+                object(json!({"feature_id": 7, "project": "Other", "work_item_type": "Task"})),
+                &defaults,
+            )
+            .unwrap();
+        let text = message_text(&result);
+        assert!(text.contains("feature 7 in project \"Other\""), "{text}");
+        assert!(text.contains("into Task work items"), "{text}");
+    }
+
+    #[test]
+    fn test_render_reports_missing_arguments() {
+        let defaults = ToolDefaults {
+            organization: Some("contoso".to_string()),
+            project: Some("Fabrikam".to_string()),
+            ..Default::default()
+        };
+        let error = builtin("sprint_review")
+            .render(None, &defaults)
+            .unwrap_err();
+        assert!(error.message.contains("'team'"), "{}", error.message);
+        assert!(error.message.contains("azdo_set_context"));
+
+        let error = builtin("triage_bugs").render(None, &defaults).unwrap_err();
+        assert!(error.message.contains("area_path"), "{}", error.message);
+
+        let error = builtin("triage_bugs")
+            .render(
+                object(json!({"area_path": "A", "areaPath": "A"})),
+                &defaults,
+            )
+            .unwrap_err();
+        assert!(error.message.contains("areaPath"), "{}", error.message);
+    }
+
+    #[test]
+    fn test_load_prompts_dir() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("release_notes.toml"),
+            r#"
+name = "release_notes"
+description = "Write release notes"
+
+[[arguments]]
+name = "version"
+required = true
+
+[[messages]]
+text = "Write the release notes of {version}."
+
+[[messages]]
+role = "assistant"
+text = "Which work items shipped in {version}?"
+"#,
+        )
+        .unwrap();
+        std::fs::write(tmp.path().join("README.md"), "not a prompt").unwrap();
+
+        let prompts = load_prompts_dir(tmp.path()).unwrap();
+        assert_eq!(prompts.len(), 1);
+        let result = prompts[0]
+            .render(object(json!({"version": "1.2"})), &ToolDefaults::default())
+            .unwrap();
+        assert_eq!(message_text(&result), "Write the release notes of 1.2.");
+        assert_eq!(result.messages[1].role, Role::Assistant);
+
+        std::fs::write(
+            tmp.path().join("invalid.toml"),
+            "name = \"bad name\"\ndescription = \"d\"\n[[messages]]\ntext = \"t\"",
+        )
+        .unwrap();
+        assert!(matches!(
+            load_prompts_dir(tmp.path()),
+            Err(PromptError::Invalid { .. })
+        ));
+        assert!(matches!(
+            load_prompts_dir(&tmp.path().join("missing")),
+            Err(PromptError::Read { .. })
+        ));
+    }
+}
@@ -0,0 +1,627 @@
+use crate::azure::api_trait::AzureDevOpsApi;
+use crate::azure::models::WorkItemQueryPage;
+use crate::compact_llm;
+use crate::mcp::defaults::ToolDefaults;
+use crate::mcp::tools::support::{
+    DEFAULT_QUERY_PAGE_SIZE, FORBIDDEN_ERROR_CODE, UNAUTHORIZED_ERROR_CODE,
+    UNTRUSTED_CONTENT_WARNING, azure_error_to_mcp, invalid_params, query_results_to_text,
+    simplify_work_item_json, work_items_to_csv,
+};
+use rmcp::{
+    ErrorData as McpError, Peer, RoleServer,
+    model::{
+        ReadResourceResult, Resource, ResourceContents, ResourceTemplate,
+        ResourceUpdatedNotificationParam,
+    },
+};
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+const URI_SCHEME: &str = "azdo://";
+/// Iteration segment of the URI that stands for the team's current iteration
+const CURRENT_ITERATION: &str = "current";
+/// Comments included when reading a work item resource
+const WORK_ITEM_COMMENTS: i32 = 5;
+/// How often subscribed resources are read again to detect changes, unless
+/// configured otherwise
+pub const DEFAULT_SUBSCRIPTION_POLL_INTERVAL: Duration = Duration::from_secs(60);
+/// Resources one session can subscribe to at a time, each read again every
+/// poll interval
+pub const MAX_SUBSCRIPTIONS_PER_SESSION: usize = 50;
+/// Consecutive polls rejected by Azure DevOps for lack of credentials or
+/// permission after which a subscription is dropped
+const MAX_SUBSCRIPTION_AUTH_FAILURES: u32 = 3;
+
+/// A resource addressed by an `azdo://` URI. Segments are percent-encoded,
+/// e.g. `azdo://contoso/My%20Project/workitems/42`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceUri {
+    WorkItem {
+        organization: String,
+        project: String,
+        id: u32,
+    },
+    Query {
+        organization: String,
+        project: String,
+        query_id: String,
+    },
+    Board {
+        organization: String,
+        project: String,
+        team: String,
+        board: String,
+    },
+    /// `iteration` is an iteration ID, or `current`
+    Iteration {
+        organization: String,
+        project: String,
+        team: String,
+        iteration: String,
+    },
+}
+
+impl ResourceUri {
+    pub fn parse(uri: &str) -> Option<ResourceUri> {
+        let segments = uri
+            .strip_prefix(URI_SCHEME)?
+            .split('/')
+            .map(|segment| urlencoding::decode(segment).ok().map(|s| s.into_owned()))
+            .collect::<Option<Vec<String>>>()?;
+        if segments.iter().any(|segment| segment.trim().is_empty()) {
+            return None;
+        }
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        let resource = match segments.as_slice() {
+            [organization, project, "workitems", id] => ResourceUri::WorkItem {
+                organization: organization.to_string(),
+                project: project.to_string(),
+                id: id.parse().ok()?,
+            },
+            [organization, project, "queries", query_id] => ResourceUri::Query {
+                organization: organization.to_string(),
+                project: project.to_string(),
+                query_id: query_id.to_string(),
+            },
+            [organization, project, "teams", team, "boards", board] => ResourceUri::Board {
+                organization: organization.to_string(),
+                project: project.to_string(),
+                team: team.to_string(),
+                board: board.to_string(),
+            },
+            [
+                organization,
+                project,
+                "teams",
+                team,
+                "iterations",
+                iteration,
+            ] => ResourceUri::Iteration {
+                organization: organization.to_string(),
+                project: project.to_string(),
+                team: team.to_string(),
+                iteration: iteration.to_string(),
+            },
+            _ => return None,
+        };
+        Some(resource)
+    }
+}
+
+impl fmt::Display for ResourceUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id;
+        let segments: Vec<&str> = match self {
+            ResourceUri::WorkItem {
+                organization,
+                project,
+                id: work_item_id,
+            } => {
+                id = work_item_id.to_string();
+                vec![organization, project, "workitems", &id]
+            }
+            ResourceUri::Query {
+                organization,
+                project,
+                query_id,
+            } => vec![organization, project, "queries", query_id],
+            ResourceUri::Board {
+                organization,
+                project,
+                team,
+                board,
+            } => vec![organization, project, "teams", team, "boards", board],
+            ResourceUri::Iteration {
+                organization,
+                project,
+                team,
+                iteration,
+            } => vec![
+                organization,
+                project,
+                "teams",
+                team,
+                "iterations",
+                iteration,
+            ],
+        };
+        let encoded: Vec<_> = segments.into_iter().map(urlencoding::encode).collect();
+        write!(f, "{URI_SCHEME}{}", encoded.join("/"))
+    }
+}
+
+/// The URI templates of the resources the server can read
+pub fn resource_templates() -> Vec<ResourceTemplate> {
+    vec![
+        ResourceTemplate::new(
+            "azdo://{organization}/{project}/workitems/{id}",
+            "work_item",
+        )
+        .with_title("Work item")
+        .with_description("A work item with its fields and latest comments, as CSV")
+        .with_mime_type("text/csv"),
+        ResourceTemplate::new(
+            "azdo://{organization}/{project}/queries/{queryId}",
+            "saved_query",
+        )
+        .with_title("Saved query results")
+        .with_description(format!(
+            "The first {DEFAULT_QUERY_PAGE_SIZE} work items matched by a saved (flat) query, as CSV"
+        ))
+        .with_mime_type("text/csv"),
+        ResourceTemplate::new(
+            "azdo://{organization}/{project}/teams/{team}/boards/{board}",
+            "board",
+        )
+        .with_title("Team board")
+        .with_description("A team board with its columns, rows and fields")
+        .with_mime_type("text/plain"),
+        ResourceTemplate::new(
+            "azdo://{organization}/{project}/teams/{team}/iterations/{iteration}",
+            "iteration",
+        )
+        .with_title("Team iteration")
+        .with_description(
+            "A team iteration (an iteration ID, or `current`) with the work items planned in it",
+        )
+        .with_mime_type("text/plain"),
+    ]
+}
+
+/// The resources listed to clients: the current iteration of the default
+/// team, when a default organization, project and team are set
+pub fn listed_resources(defaults: &ToolDefaults) -> Vec<Resource> {
+    let (Some(organization), Some(project), Some(team)) =
+        (&defaults.organization, &defaults.project, &defaults.team)
+    else {
+        return Vec::new();
+    };
+    let uri = ResourceUri::Iteration {
+        organization: organization.clone(),
+        project: project.clone(),
+        team: team.clone(),
+        iteration: CURRENT_ITERATION.to_string(),
+    };
+    vec![
+        Resource::new(uri.to_string(), "current_iteration")
+            .with_title(format!("Current iteration of {team}"))
+            .with_mime_type("text/plain"),
+    ]
+}
+
+/// Reads the resource at `uri`
+pub async fn read_resource(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    uri: &str,
+) -> Result<ReadResourceResult, McpError> {
+    let resource = ResourceUri::parse(uri).ok_or_else(|| unknown_resource(uri))?;
+    let (mime_type, text) = render(client, &resource).await?;
+    Ok(ReadResourceResult::new(vec![
+        ResourceContents::text(format!("{UNTRUSTED_CONTENT_WARNING}\n{text}"), uri)
+            .with_mime_type(mime_type),
+    ]))
+}
+
+fn unknown_resource(uri: &str) -> McpError {
+    McpError::resource_not_found(
+        format!("Unknown resource '{uri}': see resources/templates/list for the supported URIs"),
+        None,
+    )
+}
+
+async fn render(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    resource: &ResourceUri,
+) -> Result<(&'static str, String), McpError> {
+    match resource {
+        ResourceUri::WorkItem {
+            organization,
+            project,
+            id,
+        } => {
+            let work_item = client
+                .get_work_item(organization, project, *id, Some(WORK_ITEM_COMMENTS))
+                .await
+                .map_err(azure_error_to_mcp)?
+                .ok_or_else(|| {
+                    McpError::resource_not_found(format!("Work item {id} not found"), None)
+                })?;
+            let json_value = serde_json::to_value(&work_item)
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+            Ok(("text/csv", work_items_csv(json_value)?))
+        }
+        ResourceUri::Query {
+            organization,
+            project,
+            query_id,
+        } => {
+            let page = client
+                .run_saved_query(
+                    organization,
+                    project,
+                    query_id,
+                    0,
+                    DEFAULT_QUERY_PAGE_SIZE,
+                    None,
+                )
+                .await
+                .map_err(azure_error_to_mcp)?;
+            Ok(("text/csv", query_page_text(&page)?))
+        }
+        ResourceUri::Board {
+            organization,
+            project,
+            team,
+            board,
+        } => {
+            let board = client
+                .get_board(organization, project, team, board)
+                .await
+                .map_err(azure_error_to_mcp)?;
+            let output = compact_llm::to_compact_string(&board)
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+            Ok(("text/plain", output))
+        }
+        ResourceUri::Iteration {
+            organization,
+            project,
+            team,
+            iteration,
+        } => {
+            let found = if iteration.eq_ignore_ascii_case(CURRENT_ITERATION) {
+                client
+                    .get_team_current_iteration(organization, project, team)
+                    .await
+                    .map_err(azure_error_to_mcp)?
+            } else {
+                client
+                    .get_team_iterations(organization, project, team, None)
+                    .await
+                    .map_err(azure_error_to_mcp)?
+                    .into_iter()
+                    .find(|candidate| candidate.id.eq_ignore_ascii_case(iteration))
+            };
+            let iteration = found.ok_or_else(|| {
+                McpError::resource_not_found(
+                    format!("Iteration '{iteration}' not found for team '{team}'"),
+                    None,
+                )
+            })?;
+
+            let query = format!(
+                "SELECT [System.Id] FROM WorkItems WHERE [System.TeamProject] = @project AND [System.IterationPath] = '{}' ORDER BY [System.WorkItemType], [System.Id]",
+                iteration.path.replace('\'', "''")
+            );
+            let page = client
+                .query_work_items(
+                    organization,
+                    project,
+                    &query,
+                    0,
+                    DEFAULT_QUERY_PAGE_SIZE,
+                    None,
+                )
+                .await
+                .map_err(azure_error_to_mcp)?;
+            let date = |date: &Option<String>| {
+                date.as_deref()
+                    .and_then(|d| d.split('T').next())
+                    .unwrap_or("N/A")
+                    .to_string()
+            };
+            Ok((
+                "text/plain",
+                format!(
+                    "iteration={},path={},start_date={},finish_date={}\n{}",
+                    iteration.name,
+                    iteration.path,
+                    date(&iteration.attributes.start_date),
+                    date(&iteration.attributes.finish_date),
+                    query_page_text(&page)?
+                ),
+            ))
+        }
+    }
+}
+
+fn query_page_text(page: &WorkItemQueryPage) -> Result<String, McpError> {
+    query_results_to_text(page).map_err(|e| McpError::internal_error(e, None))
+}
+
+fn work_items_csv(mut json_value: serde_json::Value) -> Result<String, McpError> {
+    simplify_work_item_json(&mut json_value);
+    work_items_to_csv(&json_value).map_err(|e| McpError::internal_error(e, None))
+}
+
+/// A subscribed resource and the client it is read again with
+struct Watched {
+    client: Arc<dyn AzureDevOpsApi + Send + Sync>,
+    /// Fingerprint of the content when last read, `None` if it could not be read
+    fingerprint: Option<u64>,
+    /// Consecutive polls that failed with an authentication or permission error
+    auth_failures: u32,
+}
+
+/// Resources one MCP session subscribed to. They are read again every poll
+/// interval, and the session is notified of those whose content changed.
+pub struct Subscriptions {
+    poll_interval: Duration,
+    watched: Mutex<HashMap<String, Watched>>,
+    polling: Mutex<bool>,
+}
+
+impl Subscriptions {
+    pub fn new(poll_interval: Duration) -> Self {
+        Self {
+            poll_interval,
+            watched: Mutex::new(HashMap::new()),
+            polling: Mutex::new(false),
+        }
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        self.poll_interval
+    }
+
+    /// Starts watching `uri`, reading it with `client`. Fails when the
+    /// session already watches `MAX_SUBSCRIPTIONS_PER_SESSION` other resources.
+    pub async fn watch(
+        &self,
+        uri: &str,
+        client: Arc<dyn AzureDevOpsApi + Send + Sync>,
+    ) -> Result<(), McpError> {
+        let resource = ResourceUri::parse(uri).ok_or_else(|| unknown_resource(uri))?;
+        check_capacity(&self.watched.lock().unwrap_or_else(|e| e.into_inner()), uri)?;
+        let fingerprint = fingerprint(client.as_ref(), &resource).await.ok();
+        let mut watched = self.watched.lock().unwrap_or_else(|e| e.into_inner());
+        // Checked again as other resources may have been subscribed to while
+        // this one was being read
+        check_capacity(&watched, uri)?;
+        watched.insert(
+            uri.to_string(),
+            Watched {
+                client,
+                fingerprint,
+                auth_failures: 0,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn unwatch(&self, uri: &str) {
+        self.watched
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(uri);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.watched
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_empty()
+    }
+
+    /// Reads every subscribed resource again and returns the URIs of those
+    /// whose content changed since they were last read. A subscription whose
+    /// reads keep being rejected for lack of credentials or permission is
+    /// dropped.
+    pub async fn poll_changes(&self) -> Vec<String> {
+        let watched: Vec<(String, Arc<dyn AzureDevOpsApi + Send + Sync>)> = self
+            .watched
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|(uri, watched)| (uri.clone(), watched.client.clone()))
+            .collect();
+
+        let mut changed = Vec::new();
+        for (uri, client) in watched {
+            let Some(resource) = ResourceUri::parse(&uri) else {
+                continue;
+            };
+            let result = self::fingerprint(client.as_ref(), &resource).await;
+            let mut watched = self.watched.lock().unwrap_or_else(|e| e.into_inner());
+            // Skip resources unsubscribed while they were being read
+            let Some(entry) = watched.get_mut(&uri) else {
+                continue;
+            };
+            match result {
+                Ok(fingerprint) => {
+                    if entry
+                        .fingerprint
+                        .is_some_and(|previous| previous != fingerprint)
+                    {
+                        changed.push(uri);
+                    }
+                    entry.fingerprint = Some(fingerprint);
+                    entry.auth_failures = 0;
+                }
+                Err(e) if e.code == UNAUTHORIZED_ERROR_CODE || e.code == FORBIDDEN_ERROR_CODE => {
+                    entry.auth_failures += 1;
+                    if entry.auth_failures >= MAX_SUBSCRIPTION_AUTH_FAILURES {
+                        log::warn!(
+                            "Dropping subscription to {uri} after {} rejected reads",
+                            entry.auth_failures
+                        );
+                        watched.remove(&uri);
+                    }
+                }
+                Err(_) => {}
+            }
+        }
+        changed
+    }
+
+    /// Polls the subscribed resources in the background and notifies `peer`
+    /// of changes, until no subscription is left or the session ends. Does
+    /// nothing if the poller is already running.
+    pub fn start_polling(self: &Arc<Self>, peer: Peer<RoleServer>) {
+        {
+            let mut polling = self.polling.lock().unwrap_or_else(|e| e.into_inner());
+            if *polling {
+                return;
+            }
+            *polling = true;
+        }
+        let subscriptions: Weak<Self> = Arc::downgrade(self);
+        let poll_interval = self.poll_interval;
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(poll_interval).await;
+                let Some(subscriptions) = subscriptions.upgrade() else {
+                    return;
+                };
+                if subscriptions.stop_polling_if_idle(&peer) {
+                    return;
+                }
+                for uri in subscriptions.poll_changes().await {
+                    log::debug!("Subscribed resource changed: {uri}");
+                    let notification = ResourceUpdatedNotificationParam::new(uri);
+                    if let Err(e) = peer.notify_resource_updated(notification).await {
+                        log::warn!("Failed to notify resource update: {e}");
+                    }
+                }
+            }
+        });
+    }
+
+    fn stop_polling_if_idle(&self, peer: &Peer<RoleServer>) -> bool {
+        let mut polling = self.polling.lock().unwrap_or_else(|e| e.into_inner());
+        if self.is_empty() || peer.is_transport_closed() {
+            *polling = false;
+            return true;
+        }
+        false
+    }
+}
+
+impl Default for Subscriptions {
+    fn default() -> Self {
+        Self::new(DEFAULT_SUBSCRIPTION_POLL_INTERVAL)
+    }
+}
+
+fn check_capacity(watched: &HashMap<String, Watched>, uri: &str) -> Result<(), McpError> {
+    if !watched.contains_key(uri) && watched.len() >= MAX_SUBSCRIPTIONS_PER_SESSION {
+        return Err(invalid_params(format!(
+            "At most {MAX_SUBSCRIPTIONS_PER_SESSION} resources can be subscribed to per session; unsubscribe from one first"
+        )));
+    }
+    Ok(())
+}
+
+async fn fingerprint(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    resource: &ResourceUri,
+) -> Result<u64, McpError> {
+    match render(client, resource).await {
+        Ok((_, text)) => {
+            let mut hasher = DefaultHasher::new();
+            text.hash(&mut hasher);
+            Ok(hasher.finish())
+        }
+        Err(e) => {
+            log::warn!(
+                "Failed to read subscribed resource {resource}: {}",
+                e.message
+            );
+            Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_resource_uris() {
+        assert_eq!(
+            ResourceUri::parse("azdo://contoso/My%20Project/workitems/42"),
+            Some(ResourceUri::WorkItem {
+                organization: "contoso".to_string(),
+                project: "My Project".to_string(),
+                id: 42,
+            })
+        );
+        assert_eq!(
+            ResourceUri::parse("azdo://contoso/Fabrikam/queries/8f3c1a2b"),
+            Some(ResourceUri::Query {
+                organization: "contoso".to_string(),
+                project: "Fabrikam".to_string(),
+                query_id: "8f3c1a2b".to_string(),
+            })
+        );
+        assert_eq!(
+            ResourceUri::parse("azdo://contoso/Fabrikam/teams/Fabrikam%20Team/iterations/current"),
+            Some(ResourceUri::Iteration {
+                organization: "contoso".to_string(),
+                project: "Fabrikam".to_string(),
+                team: "Fabrikam Team".to_string(),
+                iteration: "current".to_string(),
+            })
+        );
+        for invalid in [
+            "https://contoso/Fabrikam/workitems/42",
+            "azdo://contoso/Fabrikam/workitems/abc",
+            "azdo://contoso//workitems/42",
+            "azdo://contoso/Fabrikam/teams/t/boards",
+            "azdo://contoso/Fabrikam/wiki/42",
+        ] {
+            assert_eq!(ResourceUri::parse(invalid), None, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        for uri in [
+            "azdo://contoso/My%20Project/workitems/42",
+            "azdo://contoso/Fabrikam/queries/8f3c1a2b",
+            "azdo://contoso/Fabrikam/teams/Fabrikam%20Team/boards/Stories",
+            "azdo://contoso/Fabrikam/teams/Fabrikam%20Team/iterations/current",
+        ] {
+            assert_eq!(ResourceUri::parse(uri).unwrap().to_string(), uri);
+        }
+    }
+
+    #[test]
+    fn test_listed_resources_need_default_team() {
+        let mut defaults = ToolDefaults {
+            organization: Some("contoso".to_string()),
+            project: Some("Fabrikam".to_string()),
+            ..Default::default()
+        };
+        assert!(listed_resources(&defaults).is_empty());
+        defaults.team = Some("Fabrikam Team".to_string());
+        let resources = listed_resources(&defaults);
+        assert_eq!(
+            resources[0].uri,
+            "azdo://contoso/Fabrikam/teams/Fabrikam%20Team/iterations/current"
+        );
+    }
+}
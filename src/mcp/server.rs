@@ -1,15 +1,57 @@
 use crate::azure::api_trait::AzureDevOpsApi;
-use crate::azure::client::AzureDevOpsClient;
+use crate::azure::client::{AzureDevOpsClient, AzureError, CallerAuthorization};
+use crate::mcp::defaults::{SetContextArgs, ToolDefaults, resolve_arguments};
+use crate::mcp::prompts::{self, PromptTemplate};
+use crate::mcp::resources::{self, Subscriptions};
+use crate::mcp::tool_filter::ToolFilter;
+use crate::mcp::tools::support::{azure_error_to_mcp, invalid_params, tool_text_success};
+use hyper::header::AUTHORIZATION;
+use hyper::http::request::Parts;
+// `McpError`, `Parameters`, `CallToolResult` and the `tool` and `tool_router`
+// macros are imported by the generated code included below
 use rmcp::{
+    RoleServer,
     handler::server::router::tool::ToolRouter,
-    model::{Implementation, ServerCapabilities, ServerInfo},
+    model::{
+        GetPromptRequestParams, GetPromptResult, Implementation, JsonObject, ListPromptsResult,
+        ListResourceTemplatesResult, ListResourcesResult, PaginatedRequestParams,
+        ReadResourceRequestParams, ReadResourceResult, ServerCapabilities, ServerInfo,
+        SubscribeRequestParams, UnsubscribeRequestParams,
+    },
+    schemars::JsonSchema,
+    serde::de::DeserializeOwned,
+    service::RequestContext,
     tool_handler,
 };
-use std::sync::Arc;
+use std::any::Any;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Tool arguments naming a file on the machine the server runs on
+const LOCAL_FILE_ARGUMENTS: &[&str] = &["file_path"];
+
+/// Builds the Azure DevOps client that acts with the credentials of one caller
+pub type CallerClientFactory =
+    Arc<dyn Fn(CallerAuthorization) -> Arc<dyn AzureDevOpsApi + Send + Sync> + Send + Sync>;
 
 #[derive(Clone)]
 pub struct AzureMcpServer {
     client: Arc<dyn AzureDevOpsApi + Send + Sync>,
+    caller_clients: Option<CallerClientFactory>,
+    /// Whether calls without an `Authorization` header run with the server's
+    /// identity when `caller_clients` is set, instead of being rejected
+    server_identity_fallback: bool,
+    /// Server-wide defaults of tool arguments
+    defaults: ToolDefaults,
+    /// Defaults set by `azdo_set_context`, overriding `defaults` for the
+    /// session this server instance serves
+    session_defaults: Arc<RwLock<ToolDefaults>>,
+    /// Resources the session this server instance serves subscribed to
+    subscriptions: Arc<Subscriptions>,
+    /// Built-in and user prompt templates
+    prompts: Arc<Vec<PromptTemplate>>,
+    /// Whether tools may read files of the machine the server runs on
+    local_files: bool,
     tool_router: ToolRouter<Self>,
 }
 
@@ -18,6 +60,228 @@ pub struct AzureMcpServer {
 // and generates the impl block in target/debug/build/.../out/generated_tools.rs
 include!(concat!(env!("OUT_DIR"), "/generated_tools.rs"));
 
+impl AzureMcpServer {
+    fn from_client(client: Arc<dyn AzureDevOpsApi + Send + Sync>) -> Self {
+        Self {
+            client,
+            caller_clients: None,
+            server_identity_fallback: false,
+            defaults: ToolDefaults::default(),
+            session_defaults: Arc::default(),
+            subscriptions: Arc::default(),
+            prompts: Arc::new(prompts::builtin_prompts()),
+            local_files: true,
+            tool_router: Self::tool_router() + Self::context_tool_router(),
+        }
+    }
+
+    /// Like [`AzureMcpServer::new`], but in HTTP server mode every tool call
+    /// whose request carries an `Authorization` header runs with the caller's
+    /// credentials instead of the server's identity, so Azure DevOps records
+    /// the real user. Calls without the header are rejected unless
+    /// [`AzureMcpServer::with_server_identity_fallback`] allows them.
+    pub fn new_with_caller_passthrough(client: AzureDevOpsClient) -> Self {
+        let client = Arc::new(client);
+        let server_client = client.clone();
+        let caller_clients: CallerClientFactory = Arc::new(move |authorization| {
+            Arc::new(server_client.for_caller(authorization))
+                as Arc<dyn AzureDevOpsApi + Send + Sync>
+        });
+        Self::from_client(client).with_caller_clients(caller_clients)
+    }
+
+    /// Builds the client of callers that send an `Authorization` header with
+    /// `factory` (see [`AzureMcpServer::new_with_caller_passthrough`])
+    pub fn with_caller_clients(mut self, factory: CallerClientFactory) -> Self {
+        self.caller_clients = Some(factory);
+        self
+    }
+
+    /// Runs calls without an `Authorization` header with the server's
+    /// identity when callers' credentials are passed through
+    pub fn with_server_identity_fallback(mut self, fallback: bool) -> Self {
+        self.server_identity_fallback = fallback;
+        self
+    }
+
+    /// Organization, project and team used by tool calls that omit them
+    pub fn with_defaults(mut self, defaults: ToolDefaults) -> Self {
+        self.defaults = defaults;
+        self
+    }
+
+    /// Adds user prompt templates; one named like a built-in prompt replaces it
+    pub fn with_prompts(mut self, user_prompts: Vec<PromptTemplate>) -> Self {
+        let mut prompts: Vec<PromptTemplate> = self.prompts.as_ref().clone();
+        for prompt in user_prompts {
+            match prompts.iter_mut().find(|other| other.name == prompt.name) {
+                Some(other) => {
+                    log::info!("Prompt '{}' replaces the built-in one", prompt.name);
+                    *other = prompt;
+                }
+                None => prompts.push(prompt),
+            }
+        }
+        self.prompts = Arc::new(prompts);
+        self
+    }
+
+    /// The prompts whose tools are all exposed
+    fn available_prompts(&self) -> impl Iterator<Item = &PromptTemplate> {
+        self.prompts.iter().filter(|prompt| {
+            prompt
+                .tools
+                .iter()
+                .all(|tool| self.tool_router.has_route(tool))
+        })
+    }
+
+    /// How often resources a session subscribed to are read again to
+    /// detect changes
+    pub fn with_subscription_poll_interval(mut self, interval: Duration) -> Self {
+        self.subscriptions = Arc::new(Subscriptions::new(interval));
+        self
+    }
+
+    /// A server instance for a new MCP session: it shares everything with
+    /// this one except the defaults set by `azdo_set_context` and the
+    /// resource subscriptions
+    pub fn new_session(&self) -> Self {
+        Self {
+            session_defaults: Arc::default(),
+            subscriptions: Arc::new(Subscriptions::new(self.subscriptions.poll_interval())),
+            ..self.clone()
+        }
+    }
+
+    /// The defaults in effect for this session
+    fn tool_defaults(&self) -> ToolDefaults {
+        let session = self
+            .session_defaults
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
+        self.defaults.overridden_by(&session)
+    }
+
+    /// Deserializes the arguments of a tool call, filling the organization,
+    /// project and team it omits from the defaults
+    fn resolve_args<T: DeserializeOwned + JsonSchema + Any>(
+        &self,
+        arguments: JsonObject,
+    ) -> Result<T, McpError> {
+        if !self.local_files
+            && let Some(name) = LOCAL_FILE_ARGUMENTS
+                .iter()
+                .find(|name| arguments.get(**name).is_some_and(|value| !value.is_null()))
+        {
+            return Err(invalid_params(format!(
+                "{name} is not accepted by a server reached over HTTP, it cannot read the caller's files; send the content as content_base64 with file_name"
+            )));
+        }
+        resolve_arguments(arguments, &self.tool_defaults())
+    }
+
+    /// Rejects tool arguments naming a local file, for servers whose clients
+    /// run on other machines and must not read the server's files
+    pub fn without_local_files(mut self) -> Self {
+        self.local_files = false;
+        self
+    }
+
+    /// Removes the tools `filter` does not allow, so they are neither listed
+    /// nor callable
+    pub fn with_tool_filter(mut self, filter: &ToolFilter) -> Self {
+        let tools = self.tool_router.list_all();
+        let names: Vec<&str> = tools.iter().map(|tool| tool.name.as_ref()).collect();
+        for pattern in filter.unmatched_patterns(&names) {
+            log::warn!("Tool pattern '{pattern}' does not match any tool");
+        }
+        for tool in tools.iter().filter(|tool| !filter.allows(tool)) {
+            self.tool_router.remove_route(&tool.name);
+        }
+        if !filter.is_empty() {
+            log::info!(
+                "Exposing {} of {} tools",
+                self.tool_router.list_all().len(),
+                tools.len()
+            );
+        }
+        self
+    }
+
+    /// Readiness of the server: whether its own credentials can currently
+    /// obtain a token for Azure DevOps. When every call runs with the
+    /// caller's credentials, the server's own are never used and not checked.
+    pub async fn check_ready(&self) -> Result<(), AzureError> {
+        if self.caller_clients.is_some() && !self.server_identity_fallback {
+            return Ok(());
+        }
+        self.client.check_credentials().await
+    }
+
+    /// Subscribed resources are polled with the server's identity, since
+    /// polling with a caller's credentials would keep them for the whole
+    /// session. Without the fallback, that identity is not meant to be used.
+    fn subscriptions_allowed(&self) -> bool {
+        self.caller_clients.is_none() || self.server_identity_fallback
+    }
+
+    /// The client a tool call runs with: the caller's own when passthrough is
+    /// enabled, otherwise the server's. Missing or unusable credentials are
+    /// rejected rather than silently replaced by the server's identity, unless
+    /// the fallback was enabled for calls without credentials.
+    fn client_for(
+        &self,
+        context: &RequestContext<RoleServer>,
+    ) -> Result<Arc<dyn AzureDevOpsApi + Send + Sync>, rmcp::ErrorData> {
+        let Some(caller_clients) = &self.caller_clients else {
+            return Ok(self.client.clone());
+        };
+        let header = context
+            .extensions
+            .get::<Parts>()
+            .and_then(|parts| parts.headers.get(AUTHORIZATION));
+        match header {
+            Some(header) => {
+                let authorization =
+                    CallerAuthorization::from_header(header).map_err(azure_error_to_mcp)?;
+                Ok(caller_clients(authorization))
+            }
+            None if self.server_identity_fallback => Ok(self.client.clone()),
+            None => Err(azure_error_to_mcp(AzureError::Unauthorized(
+                "this server acts with the caller's Azure DevOps credentials; send them in the Authorization header".to_string(),
+            ))),
+        }
+    }
+}
+
+#[tool_router(router = context_tool_router)]
+impl AzureMcpServer {
+    #[tool(
+        description = "Set the default organization, project and team of this session, used when a tool call omits them. An empty string clears a value; without arguments, shows the current defaults",
+        annotations(read_only_hint = true)
+    )]
+    async fn azdo_set_context(
+        &self,
+        args: Parameters<SetContextArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        crate::metrics::observe_tool_call("azdo_set_context", async {
+            log::info!("Tool invoked: azdo_set_context");
+            {
+                let mut session = self
+                    .session_defaults
+                    .write()
+                    .unwrap_or_else(|e| e.into_inner());
+                args.0.apply_to(&mut session);
+            }
+            let defaults = serde_json::to_string(&self.tool_defaults())
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+            Ok(tool_text_success(defaults))
+        })
+        .await
+    }
+}
+
 #[tool_handler(router = self.tool_router)]
 impl rmcp::ServerHandler for AzureMcpServer {
     fn get_info(&self) -> ServerInfo {
@@ -29,7 +293,108 @@ impl rmcp::ServerHandler for AzureMcpServer {
             .with_website_url(env!("CARGO_PKG_HOMEPAGE"));
         info.instructions =
             Some("Use this tool to interact with Azure DevOps Boards and Work Items".into());
-        info.capabilities = ServerCapabilities::builder().enable_tools().build();
+        info.capabilities = ServerCapabilities::builder()
+            .enable_tools()
+            .enable_prompts()
+            .enable_resources()
+            .enable_resources_subscribe()
+            .build();
+        if !self.subscriptions_allowed()
+            && let Some(resources) = info.capabilities.resources.as_mut()
+        {
+            resources.subscribe = None;
+        }
         info
     }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        Ok(ListPromptsResult::with_all_items(
+            self.available_prompts()
+                .map(PromptTemplate::to_prompt)
+                .collect(),
+        ))
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        log::info!("Prompt requested: {}", request.name);
+        let prompt = self
+            .available_prompts()
+            .find(|prompt| prompt.name == request.name)
+            .ok_or_else(|| {
+                McpError::invalid_params(format!("Unknown prompt '{}'", request.name), None)
+            })?;
+        prompt.render(request.arguments, &self.tool_defaults())
+    }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        Ok(ListResourcesResult::with_all_items(
+            resources::listed_resources(&self.tool_defaults()),
+        ))
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, McpError> {
+        Ok(ListResourceTemplatesResult::with_all_items(
+            resources::resource_templates(),
+        ))
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        log::info!("Resource read: {}", request.uri);
+        let client = self.client_for(&context)?;
+        resources::read_resource(client.as_ref(), &request.uri).await
+    }
+
+    async fn subscribe(
+        &self,
+        request: SubscribeRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        log::info!("Resource subscribed: {}", request.uri);
+        if !self.subscriptions_allowed() {
+            return Err(McpError::invalid_request(
+                "Subscriptions are not available when the server acts with each caller's credentials",
+                None,
+            ));
+        }
+        if self.caller_clients.is_some() {
+            // Polled with the server's identity, so only resources the
+            // caller can read are watched
+            let client = self.client_for(&context)?;
+            resources::read_resource(client.as_ref(), &request.uri).await?;
+        }
+        self.subscriptions
+            .watch(&request.uri, self.client.clone())
+            .await?;
+        self.subscriptions.start_polling(context.peer);
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        request: UnsubscribeRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        self.subscriptions.unwatch(&request.uri);
+        Ok(())
+    }
 }
@@ -0,0 +1,134 @@
+use rmcp::model::Tool;
+
+/// Selects the tools the server exposes. Tools that are filtered out are
+/// neither listed nor callable.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToolFilter {
+    /// Hide every tool that is not marked `read_only` in its `#[mcp_tool]`
+    pub read_only: bool,
+    /// When not empty, only tools matching one of these patterns are exposed
+    pub enabled: Vec<String>,
+    /// Tools matching one of these patterns are hidden, even when enabled
+    pub disabled: Vec<String>,
+}
+
+impl ToolFilter {
+    /// Whether the filter hides no tool at all
+    pub fn is_empty(&self) -> bool {
+        !self.read_only && self.enabled.is_empty() && self.disabled.is_empty()
+    }
+
+    pub fn allows(&self, tool: &Tool) -> bool {
+        let read_only = tool
+            .annotations
+            .as_ref()
+            .and_then(|annotations| annotations.read_only_hint)
+            .unwrap_or(false);
+        if self.read_only && !read_only {
+            return false;
+        }
+        if !self.enabled.is_empty()
+            && !self
+                .enabled
+                .iter()
+                .any(|pattern| matches_pattern(pattern, &tool.name))
+        {
+            return false;
+        }
+        !self
+            .disabled
+            .iter()
+            .any(|pattern| matches_pattern(pattern, &tool.name))
+    }
+
+    /// The `enabled` and `disabled` patterns that match none of `names`,
+    /// most likely typos
+    pub fn unmatched_patterns<'a>(&'a self, names: &[&str]) -> Vec<&'a str> {
+        self.enabled
+            .iter()
+            .chain(&self.disabled)
+            .filter(|pattern| !names.iter().any(|name| matches_pattern(pattern, name)))
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// Matches a tool name against a pattern in which `*` stands for any
+/// sequence of characters, e.g. `azdo_list_*` or `*_work_item`
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No wildcard: the pattern must match the whole name
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::ToolAnnotations;
+    use serde_json::Map;
+    use std::sync::Arc;
+
+    fn tool(name: &'static str, read_only: bool) -> Tool {
+        Tool::new(name, "test tool", Arc::new(Map::new()))
+            .annotate(ToolAnnotations::new().read_only(read_only))
+    }
+
+    #[test]
+    fn test_matches_pattern() {
+        assert!(matches_pattern("azdo_get_work_item", "azdo_get_work_item"));
+        assert!(!matches_pattern(
+            "azdo_get_work_item",
+            "azdo_get_work_items"
+        ));
+        assert!(matches_pattern("azdo_list_*", "azdo_list_tags"));
+        assert!(matches_pattern("*_work_item", "azdo_create_work_item"));
+        assert!(!matches_pattern("*_work_item", "azdo_get_work_items"));
+        assert!(matches_pattern("azdo_*_work_*", "azdo_update_work_item"));
+        assert!(matches_pattern("*", "azdo_list_tags"));
+        assert!(!matches_pattern("azdo_*_tags_*", "azdo_list_tags"));
+    }
+
+    #[test]
+    fn test_read_only_hides_mutating_tools() {
+        let filter = ToolFilter {
+            read_only: true,
+            ..Default::default()
+        };
+        assert!(filter.allows(&tool("azdo_get_work_item", true)));
+        assert!(!filter.allows(&tool("azdo_create_work_item", false)));
+        let unannotated = Tool::new("azdo_unmarked", "test tool", Arc::new(Map::new()));
+        assert!(!filter.allows(&unannotated));
+        assert!(ToolFilter::default().allows(&unannotated));
+    }
+
+    #[test]
+    fn test_enabled_and_disabled_patterns() {
+        let filter = ToolFilter {
+            read_only: false,
+            enabled: vec!["azdo_list_*".to_string(), "azdo_get_work_item".to_string()],
+            disabled: vec!["azdo_list_tags".to_string()],
+        };
+        assert!(filter.allows(&tool("azdo_list_projects", true)));
+        assert!(filter.allows(&tool("azdo_get_work_item", true)));
+        assert!(!filter.allows(&tool("azdo_list_tags", true)));
+        assert!(!filter.allows(&tool("azdo_create_work_item", false)));
+        assert_eq!(
+            filter.unmatched_patterns(&["azdo_list_projects", "azdo_list_tags"]),
+            vec!["azdo_get_work_item"]
+        );
+    }
+}
@@ -1,10 +1,12 @@
 use crate::azure::api_trait::AzureDevOpsApi;
 use crate::azure::classification_nodes::ClassificationNode;
-use crate::mcp::tools::support::{deserialize_non_empty_string, tool_text_success};
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, tool_text_success,
+};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, ErrorCode},
+    model::CallToolResult,
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -24,7 +26,8 @@ pub struct ListAreaPathsArgs {
 
 #[mcp_tool(
     name = "azdo_list_area_paths",
-    description = "List area paths for a project"
+    description = "List area paths for a project",
+    read_only = true
 )]
 pub async fn list_area_paths(
     client: &(dyn AzureDevOpsApi + Send + Sync),
@@ -40,11 +43,7 @@ pub async fn list_area_paths(
             10, // depth
         )
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(azure_error_to_mcp)?;
 
     // Flatten the tree into a list of paths
     fn collect_paths(node: &ClassificationNode, paths: &mut Vec<String>) {
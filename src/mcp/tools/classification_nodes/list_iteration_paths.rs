@@ -1,5 +1,7 @@
 use crate::azure::api_trait::AzureDevOpsApi;
-use crate::mcp::tools::support::{deserialize_non_empty_string, tool_text_success};
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, tool_text_success,
+};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
@@ -26,7 +28,8 @@ pub struct ListIterationPathsArgs {
 
 #[mcp_tool(
     name = "azdo_list_iteration_paths",
-    description = "List iteration paths for a project or team"
+    description = "List iteration paths for a project or team",
+    read_only = true
 )]
 pub async fn list_iteration_paths(
     client: &(dyn AzureDevOpsApi + Send + Sync),
@@ -59,11 +62,7 @@ pub async fn list_iteration_paths(
                 None, // Get all iterations first
             )
             .await
-            .map_err(|e| McpError {
-                code: ErrorCode(-32000),
-                message: e.to_string().into(),
-                data: None,
-            })?;
+            .map_err(azure_error_to_mcp)?;
 
         // Filter by timeframe if provided (post-acquisition filtering)
         if let Some(ref timeframe) = args.timeframe {
@@ -114,11 +113,7 @@ pub async fn list_iteration_paths(
                 10, // depth
             )
             .await
-            .map_err(|e| McpError {
-                code: ErrorCode(-32000),
-                message: e.to_string().into(),
-                data: None,
-            })?;
+            .map_err(azure_error_to_mcp)?;
 
         // Flatten the tree into a list of paths and return as CSV
         let mut paths = Vec::new();
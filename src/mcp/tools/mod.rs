@@ -2,6 +2,7 @@
 pub mod classification_nodes;
 pub mod organizations;
 pub mod projects;
+pub mod queries;
 pub mod support;
 pub mod tags;
 pub mod teams;
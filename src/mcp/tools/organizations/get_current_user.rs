@@ -1,5 +1,5 @@
 use crate::azure::api_trait::AzureDevOpsApi;
-use crate::mcp::tools::support::tool_text_success;
+use crate::mcp::tools::support::{azure_error_to_mcp, tool_text_success};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
@@ -13,18 +13,15 @@ pub struct GetCurrentUserArgs {}
 
 #[mcp_tool(
     name = "azdo_get_current_user",
-    description = "Get current user profile"
+    description = "Get current user profile",
+    read_only = true
 )]
 pub async fn get_current_user(
     client: &(dyn AzureDevOpsApi + Send + Sync),
     _args: GetCurrentUserArgs,
 ) -> Result<CallToolResult, McpError> {
     log::info!("Tool invoked: azdo_get_current_user");
-    let profile = client.get_profile().await.map_err(|e| McpError {
-        code: ErrorCode(-32000),
-        message: e.to_string().into(),
-        data: None,
-    })?;
+    let profile = client.get_profile().await.map_err(azure_error_to_mcp)?;
 
     let mut wtr = csv::WriterBuilder::new()
         .has_headers(false)
@@ -1,10 +1,10 @@
 use crate::azure::api_trait::AzureDevOpsApi;
-use crate::mcp::tools::support::tool_text_success;
+use crate::mcp::tools::support::{azure_error_to_mcp, azure_error_with_context, tool_text_success};
 
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, ErrorCode},
+    model::CallToolResult,
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -12,7 +12,11 @@ use rmcp::{
 #[derive(Deserialize, JsonSchema)]
 pub struct ListOrganizationsArgs {}
 
-#[mcp_tool(name = "azdo_list_organizations", description = "List organizations")]
+#[mcp_tool(
+    name = "azdo_list_organizations",
+    description = "List organizations",
+    read_only = true
+)]
 pub async fn list_organizations(
     client: &(dyn AzureDevOpsApi + Send + Sync),
     _args: ListOrganizationsArgs,
@@ -20,21 +24,16 @@ pub async fn list_organizations(
     log::info!("Tool invoked: azdo_list_organizations");
 
     // First, get the user's profile to obtain their member ID
-    let profile = client.get_profile().await.map_err(|e| McpError {
-        code: ErrorCode(-32000),
-        message: format!("Failed to get user profile: {}", e).into(),
-        data: None,
-    })?;
+    let profile = client
+        .get_profile()
+        .await
+        .map_err(|e| azure_error_with_context("Failed to get user profile", e))?;
 
     // Then, list all organizations for this member ID
     let orgs = client
         .list_organizations(&profile.id)
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(azure_error_to_mcp)?;
 
     // Extract just the organization names for compact response
     let org_names: Vec<String> = orgs.into_iter().map(|org| org.account_name).collect();
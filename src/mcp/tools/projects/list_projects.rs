@@ -1,10 +1,12 @@
 use crate::azure::api_trait::AzureDevOpsApi;
 
-use crate::mcp::tools::support::{deserialize_non_empty_string, tool_text_success};
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, tool_text_success,
+};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, ErrorCode},
+    model::CallToolResult,
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -18,7 +20,8 @@ pub struct ListProjectsArgs {
 
 #[mcp_tool(
     name = "azdo_list_projects",
-    description = "List projects in an organization"
+    description = "List projects in an organization",
+    read_only = true
 )]
 pub async fn list_projects(
     client: &(dyn AzureDevOpsApi + Send + Sync),
@@ -28,11 +31,7 @@ pub async fn list_projects(
     let projects = client
         .list_projects(&args.organization)
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(azure_error_to_mcp)?;
 
     // Extract just the project names for compact response
     let project_names: Vec<String> = projects.into_iter().map(|project| project.name).collect();
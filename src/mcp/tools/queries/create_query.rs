@@ -0,0 +1,63 @@
+use crate::azure::api_trait::AzureDevOpsApi;
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, tool_text_success,
+};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::CallToolResult,
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct CreateQueryArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Parent folder ID or path, e.g. "My Queries" or "Shared Queries/Team"
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub folder: String,
+    /// Query name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub name: String,
+    /// WIQL query string; use FROM WorkItemLinks for tree and one-hop queries
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub wiql: String,
+}
+
+#[mcp_tool(
+    name = "azdo_create_query",
+    description = "Save a new query from WIQL in a query folder",
+    read_only = false
+)]
+pub async fn create_query(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    args: CreateQueryArgs,
+) -> Result<CallToolResult, McpError> {
+    log::info!(
+        "Tool invoked: azdo_create_query(folder={}, name={})",
+        args.folder,
+        args.name
+    );
+    let query = client
+        .create_query(
+            &args.organization,
+            &args.project,
+            &args.folder,
+            &args.name,
+            &args.wiql,
+        )
+        .await
+        .map_err(azure_error_to_mcp)?;
+
+    Ok(tool_text_success(format!(
+        "Created query {} (id={},query_type={})",
+        query.path,
+        query.id,
+        query.query_type.as_deref().unwrap_or("flat")
+    )))
+}
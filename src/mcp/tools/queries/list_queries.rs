@@ -0,0 +1,109 @@
+use crate::azure::api_trait::AzureDevOpsApi;
+use crate::azure::queries::{MAX_QUERY_TREE_DEPTH, QueryHierarchyItem};
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, invalid_params, sanitize_csv_value,
+    tool_text_success,
+};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ListQueriesArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Folder ID or path to list, e.g. "Shared Queries/Team" (default: the root folders)
+    #[serde(default)]
+    pub folder: Option<String>,
+    /// Folder levels to expand (default: 1, max: 2)
+    #[serde(default)]
+    pub depth: Option<u32>,
+}
+
+/// Appends `item` and its expanded content as `path,type,id` rows. Folders
+/// whose content was not expanded are typed `folder+`.
+fn push_rows(item: &QueryHierarchyItem, rows: &mut Vec<[String; 3]>) {
+    let kind = match (&item.query_type, item.is_folder) {
+        (_, true) if item.has_children && item.children.is_empty() => "folder+",
+        (_, true) => "folder",
+        (Some(query_type), false) => query_type.as_str(),
+        (None, false) => "flat",
+    };
+    rows.push([
+        sanitize_csv_value(&item.path),
+        kind.to_string(),
+        item.id.clone(),
+    ]);
+    for child in &item.children {
+        push_rows(child, rows);
+    }
+}
+
+#[mcp_tool(
+    name = "azdo_list_queries",
+    description = "List saved queries and query folders (path, type: folder/flat/tree/oneHop, id)",
+    read_only = true
+)]
+pub async fn list_queries(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    args: ListQueriesArgs,
+) -> Result<CallToolResult, McpError> {
+    log::info!(
+        "Tool invoked: azdo_list_queries(folder={:?}, depth={:?})",
+        args.folder,
+        args.depth
+    );
+    let depth = args.depth.unwrap_or(1);
+    if depth == 0 || depth > MAX_QUERY_TREE_DEPTH {
+        return Err(invalid_params(format!(
+            "depth must be between 1 and {}",
+            MAX_QUERY_TREE_DEPTH
+        )));
+    }
+
+    let items = match args.folder.as_deref().map(str::trim) {
+        Some(folder) if !folder.is_empty() => vec![
+            client
+                .get_query(&args.organization, &args.project, folder, depth)
+                .await
+                .map_err(azure_error_to_mcp)?,
+        ],
+        _ => client
+            .list_queries(&args.organization, &args.project, depth)
+            .await
+            .map_err(azure_error_to_mcp)?,
+    };
+
+    let mut rows = Vec::new();
+    for item in &items {
+        push_rows(item, &mut rows);
+    }
+    let csv_output = rows_to_csv(&rows).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert to CSV: {}", e).into(),
+        data: None,
+    })?;
+    Ok(tool_text_success(csv_output))
+}
+
+fn rows_to_csv(rows: &[[String; 3]]) -> Result<String, String> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record(["path", "type", "id"])
+        .map_err(|e| format!("Failed to write CSV header: {}", e))?;
+    for row in rows {
+        wtr.write_record(row)
+            .map_err(|e| format!("Failed to write CSV row: {}", e))?;
+    }
+    let csv_bytes = wtr
+        .into_inner()
+        .map_err(|e| format!("Failed to get CSV bytes: {}", e))?;
+    String::from_utf8(csv_bytes).map_err(|e| format!("Failed to convert CSV to string: {}", e))
+}
@@ -0,0 +1,9 @@
+// Saved queries module
+pub mod create_query;
+pub mod list_queries;
+pub mod run_saved_query;
+
+// Re-export the public items
+pub use create_query::{CreateQueryArgs, create_query};
+pub use list_queries::{ListQueriesArgs, list_queries};
+pub use run_saved_query::{RunSavedQueryArgs, run_saved_query};
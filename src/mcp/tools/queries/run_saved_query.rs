@@ -0,0 +1,89 @@
+use crate::azure::api_trait::AzureDevOpsApi;
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, invalid_params, query_results_to_text,
+    resolve_query_page, tool_text_success,
+};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct RunSavedQueryArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Saved query ID or path, e.g. "Shared Queries/Team/Active Bugs"
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub query: String,
+    /// Include the latest N comments (optional). Set to -1 for all comments.
+    #[serde(default)]
+    pub include_latest_n_comments: Option<i32>,
+
+    /// Maximum number of work items to return (default: 200, max: 1000)
+    #[serde(default)]
+    pub top: Option<u32>,
+
+    /// Number of matching work items to skip (mutually exclusive with cursor)
+    #[serde(default)]
+    pub skip: Option<u32>,
+
+    /// Continuation cursor: the next_cursor value reported by the previous page
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+#[mcp_tool(
+    name = "azdo_run_saved_query",
+    description = "Run a saved query by ID or path (flat, tree and one-hop link queries)",
+    read_only = true
+)]
+pub async fn run_saved_query(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    args: RunSavedQueryArgs,
+) -> Result<CallToolResult, McpError> {
+    log::info!("Tool invoked: azdo_run_saved_query(query={})", args.query);
+    let (skip, top) = resolve_query_page(args.skip, args.cursor.as_deref(), args.top)?;
+
+    // Resolve the path to an ID: stored queries can only be run by ID
+    let query = client
+        .get_query(&args.organization, &args.project, &args.query, 0)
+        .await
+        .map_err(azure_error_to_mcp)?;
+    if query.is_folder {
+        return Err(invalid_params(format!(
+            "'{}' is a query folder; use azdo_list_queries to list its queries",
+            query.path
+        )));
+    }
+
+    let page = client
+        .run_saved_query(
+            &args.organization,
+            &args.project,
+            &query.id,
+            skip,
+            top,
+            args.include_latest_n_comments,
+        )
+        .await
+        .map_err(azure_error_to_mcp)?;
+
+    let results = query_results_to_text(&page).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: e.into(),
+        data: None,
+    })?;
+    Ok(tool_text_success(format!(
+        "query={},query_type={}\n{}",
+        query.path,
+        query.query_type.as_deref().unwrap_or("flat"),
+        results
+    )))
+}
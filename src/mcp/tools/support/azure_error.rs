@@ -0,0 +1,204 @@
+use crate::azure::client::AzureError;
+use rmcp::{ErrorData as McpError, model::ErrorCode};
+
+/// MCP error codes for Azure DevOps failures, in the JSON-RPC server error
+/// range. Validation errors are reported as invalid params since they are
+/// caused by the values the caller sent.
+pub const UNAUTHORIZED_ERROR_CODE: ErrorCode = ErrorCode(-32001);
+pub const NOT_FOUND_ERROR_CODE: ErrorCode = ErrorCode(-32002);
+pub const FORBIDDEN_ERROR_CODE: ErrorCode = ErrorCode(-32003);
+pub const CONFLICT_ERROR_CODE: ErrorCode = ErrorCode(-32009);
+pub const RATE_LIMITED_ERROR_CODE: ErrorCode = ErrorCode(-32029);
+pub const VALIDATION_ERROR_CODE: ErrorCode = ErrorCode::INVALID_PARAMS;
+pub const AZURE_ERROR_CODE: ErrorCode = ErrorCode(-32000);
+
+/// Converts an Azure DevOps error into an MCP error with a distinct code per
+/// error class and a message telling the model what it can do about it. The
+/// error class is repeated in `data.kind` for clients that branch on it.
+pub fn azure_error_to_mcp(error: AzureError) -> McpError {
+    let (code, kind, message) = match &error {
+        AzureError::NotFound { message, .. } => (
+            NOT_FOUND_ERROR_CODE,
+            "not_found",
+            format!("Not found (or no permission to read it): {}", message),
+        ),
+        AzureError::Unauthorized(message) => (
+            UNAUTHORIZED_ERROR_CODE,
+            "unauthorized",
+            format!(
+                "Azure DevOps rejected the credentials; sign in again or check the configured credential: {}",
+                message
+            ),
+        ),
+        AzureError::Forbidden(message) => (
+            FORBIDDEN_ERROR_CODE,
+            "forbidden",
+            format!(
+                "The signed-in identity lacks permission for this operation: {}",
+                message
+            ),
+        ),
+        AzureError::Conflict(message) => (
+            CONFLICT_ERROR_CODE,
+            "conflict",
+            format!(
+                "The resource was changed concurrently; read it again and retry: {}",
+                message
+            ),
+        ),
+        AzureError::RateLimited {
+            retry_after,
+            message,
+        } => (
+            RATE_LIMITED_ERROR_CODE,
+            "rate_limited",
+            match retry_after {
+                Some(retry_after) => format!(
+                    "Rate limited by Azure DevOps; retry after {} seconds: {}",
+                    retry_after.as_secs(),
+                    message
+                ),
+                None => format!("Rate limited by Azure DevOps; retry later: {}", message),
+            },
+        ),
+        AzureError::Validation { type_key, message } => (
+            VALIDATION_ERROR_CODE,
+            "validation",
+            format!(
+                "Azure DevOps rejected the request ({}): {}",
+                type_key, message
+            ),
+        ),
+        AzureError::TooLarge { max_size } => (
+            AZURE_ERROR_CODE,
+            "too_large",
+            format!(
+                "The content is larger than the maximum of {} bytes that can be returned",
+                max_size
+            ),
+        ),
+        AzureError::AuthError(_) => (AZURE_ERROR_CODE, "authentication", error.to_string()),
+        AzureError::HttpError(_) | AzureError::SerdeJson(_) | AzureError::ApiError(_) => {
+            (AZURE_ERROR_CODE, "api", error.to_string())
+        }
+    };
+
+    let mut data = serde_json::json!({ "kind": kind });
+    if let Some(type_key) = error.type_key() {
+        data["type_key"] = serde_json::json!(type_key);
+    }
+    if let AzureError::RateLimited {
+        retry_after: Some(retry_after),
+        ..
+    } = &error
+    {
+        data["retry_after_seconds"] = serde_json::json!(retry_after.as_secs());
+    }
+
+    McpError {
+        code,
+        message: message.into(),
+        data: Some(data),
+    }
+}
+
+/// Like `azure_error_to_mcp`, with the message prefixed by what was being done
+pub fn azure_error_with_context(context: &str, error: AzureError) -> McpError {
+    let mut mcp_error = azure_error_to_mcp(error);
+    mcp_error.message = format!("{}: {}", context, mcp_error.message).into();
+    mcp_error
+}
+
+/// Invalid params error (-32602) for arguments the tool rejects itself
+pub fn invalid_params(message: impl Into<String>) -> McpError {
+    McpError {
+        code: ErrorCode::INVALID_PARAMS,
+        message: message.into().into(),
+        data: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_azure_error_to_mcp_uses_distinct_codes() {
+        let cases = vec![
+            (
+                AzureError::NotFound {
+                    type_key: None,
+                    message: "gone".to_string(),
+                },
+                NOT_FOUND_ERROR_CODE,
+                "not_found",
+            ),
+            (
+                AzureError::Unauthorized("expired".to_string()),
+                UNAUTHORIZED_ERROR_CODE,
+                "unauthorized",
+            ),
+            (
+                AzureError::Forbidden("denied".to_string()),
+                FORBIDDEN_ERROR_CODE,
+                "forbidden",
+            ),
+            (
+                AzureError::Conflict("stale".to_string()),
+                CONFLICT_ERROR_CODE,
+                "conflict",
+            ),
+            (
+                AzureError::RateLimited {
+                    retry_after: Some(Duration::from_secs(7)),
+                    message: "slow down".to_string(),
+                },
+                RATE_LIMITED_ERROR_CODE,
+                "rate_limited",
+            ),
+            (
+                AzureError::Validation {
+                    type_key: "RuleValidationException".to_string(),
+                    message: "bad state".to_string(),
+                },
+                VALIDATION_ERROR_CODE,
+                "validation",
+            ),
+            (
+                AzureError::ApiError("boom".to_string()),
+                AZURE_ERROR_CODE,
+                "api",
+            ),
+        ];
+        for (error, code, kind) in cases {
+            let description = format!("{error:?}");
+            let mcp_error = azure_error_to_mcp(error);
+            assert_eq!(mcp_error.code, code, "{description}");
+            assert_eq!(mcp_error.data.unwrap()["kind"], kind, "{description}");
+        }
+    }
+
+    #[test]
+    fn test_azure_error_to_mcp_reports_retry_after_and_type_key() {
+        let mcp_error = azure_error_to_mcp(AzureError::RateLimited {
+            retry_after: Some(Duration::from_secs(7)),
+            message: "slow down".to_string(),
+        });
+        assert!(mcp_error.message.contains("retry after 7 seconds"));
+        assert_eq!(mcp_error.data.unwrap()["retry_after_seconds"], 7);
+
+        let mcp_error = azure_error_with_context(
+            "Failed to update",
+            AzureError::Validation {
+                type_key: "RuleValidationException".to_string(),
+                message: "bad state".to_string(),
+            },
+        );
+        assert!(mcp_error.message.starts_with("Failed to update: "));
+        assert_eq!(
+            mcp_error.data.unwrap()["type_key"],
+            "RuleValidationException"
+        );
+    }
+}
@@ -0,0 +1,25 @@
+/// Size fields of the process templates: Agile, Scrum and CMMI
+pub const STORY_POINTS_FIELDS: [&str; 3] = [
+    "Microsoft.VSTS.Scheduling.StoryPoints",
+    "Microsoft.VSTS.Scheduling.Effort",
+    "Microsoft.VSTS.Scheduling.Size",
+];
+pub const REMAINING_WORK_FIELD: &str = "Microsoft.VSTS.Scheduling.RemainingWork";
+
+/// Formats a sum of estimates with at most two decimals, without a trailing
+/// `.0` for whole numbers
+pub fn format_number(value: f64) -> String {
+    ((value * 100.0).round() / 100.0).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(8.0), "8");
+        assert_eq!(format_number(0.1 + 0.2), "0.3");
+        assert_eq!(format_number(-2.5), "-2.5");
+    }
+}
@@ -0,0 +1,233 @@
+use crate::azure::api_trait::AzureDevOpsApi;
+use crate::azure::artifact_links::{
+    BUILD_LINK_NAME, COMMIT_LINK_NAME, PULL_REQUEST_LINK_NAME, build_artifact_uri,
+    commit_artifact_uri, pull_request_artifact_uri,
+};
+use crate::azure::models::WorkItemRelationType;
+use crate::azure::work_items::work_item_url;
+use crate::mcp::tools::support::{azure_error_with_context, invalid_params};
+use rmcp::ErrorData as McpError;
+
+/// Names accepted by earlier versions of the link tool that are not link type
+/// names in Azure DevOps
+const LINK_TYPE_ALIASES: &[(&str, &str)] = &[("dependency", "System.LinkTypes.Dependency-Forward")];
+
+/// The mutually exclusive target arguments shared by the link tools
+pub struct LinkTargetArgs<'a> {
+    pub target_id: Option<u32>,
+    pub url: Option<&'a str>,
+    pub repository: Option<&'a str>,
+    pub commit_id: Option<&'a str>,
+    pub pull_request_id: Option<u32>,
+    pub build_id: Option<u32>,
+}
+
+/// What a work item relation points to
+#[derive(Debug, PartialEq)]
+pub enum LinkTarget {
+    WorkItem(u32),
+    Hyperlink(String),
+    Artifact { uri: String, name: &'static str },
+}
+
+impl LinkTarget {
+    /// URL stored in the relation
+    pub fn url(&self) -> String {
+        match self {
+            LinkTarget::WorkItem(id) => work_item_url(*id),
+            LinkTarget::Hyperlink(url) => url.clone(),
+            LinkTarget::Artifact { uri, .. } => uri.clone(),
+        }
+    }
+}
+
+/// Resolves the target arguments of the link tools. Commit and pull request
+/// links need the project and repository IDs, which are looked up from the
+/// repository name.
+pub async fn resolve_link_target(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    organization: &str,
+    project: &str,
+    args: &LinkTargetArgs<'_>,
+) -> Result<LinkTarget, McpError> {
+    let provided = [
+        args.target_id.is_some(),
+        args.url.is_some(),
+        args.commit_id.is_some(),
+        args.pull_request_id.is_some(),
+        args.build_id.is_some(),
+    ]
+    .iter()
+    .filter(|provided| **provided)
+    .count();
+    if provided != 1 {
+        return Err(invalid_params(
+            "Exactly one of target_id, url, commit_id, pull_request_id or build_id must be provided",
+        ));
+    }
+    if args.repository.is_some() && args.commit_id.is_none() && args.pull_request_id.is_none() {
+        return Err(invalid_params(
+            "repository only applies to commit and pull request links",
+        ));
+    }
+
+    if let Some(target_id) = args.target_id {
+        return Ok(LinkTarget::WorkItem(target_id));
+    }
+    if let Some(url) = args.url {
+        if url.trim().is_empty() {
+            return Err(invalid_params("url cannot be empty"));
+        }
+        return Ok(LinkTarget::Hyperlink(url.to_string()));
+    }
+    if let Some(build_id) = args.build_id {
+        return Ok(LinkTarget::Artifact {
+            uri: build_artifact_uri(build_id),
+            name: BUILD_LINK_NAME,
+        });
+    }
+
+    let repository = args.repository.ok_or_else(|| {
+        invalid_params("repository is required for commit and pull request links")
+    })?;
+    let repository = client
+        .get_repository(organization, project, repository)
+        .await
+        .map_err(|e| {
+            azure_error_with_context(&format!("Failed to resolve repository '{}'", repository), e)
+        })?;
+
+    if let Some(commit_id) = args.commit_id {
+        return Ok(LinkTarget::Artifact {
+            uri: commit_artifact_uri(&repository.project.id, &repository.id, commit_id),
+            name: COMMIT_LINK_NAME,
+        });
+    }
+    let pull_request_id = args.pull_request_id.unwrap_or_default();
+    Ok(LinkTarget::Artifact {
+        uri: pull_request_artifact_uri(&repository.project.id, &repository.id, pull_request_id),
+        name: PULL_REQUEST_LINK_NAME,
+    })
+}
+
+/// Finds the reference name of a work item link type given either its name
+/// ("Parent", "Successor") or its reference name
+/// ("System.LinkTypes.Hierarchy-Reverse"), both case-insensitive.
+pub fn match_work_item_link_type(
+    types: &[WorkItemRelationType],
+    link_type: &str,
+) -> Result<String, String> {
+    let wanted = LINK_TYPE_ALIASES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(link_type))
+        .map(|(_, reference_name)| *reference_name)
+        .unwrap_or(link_type);
+
+    let found = types.iter().find(|relation_type| {
+        relation_type.reference_name.eq_ignore_ascii_case(wanted)
+            || relation_type.name.eq_ignore_ascii_case(wanted)
+    });
+
+    match found {
+        Some(relation_type) if relation_type.is_work_item_link() => {
+            Ok(relation_type.reference_name.clone())
+        }
+        Some(relation_type) => Err(format!(
+            "'{}' does not link work items; use url, commit_id, pull_request_id or build_id instead",
+            relation_type.name
+        )),
+        None => {
+            let mut names: Vec<&str> = types
+                .iter()
+                .filter(|relation_type| {
+                    relation_type.is_work_item_link() && relation_type.is_enabled()
+                })
+                .map(|relation_type| relation_type.name.as_str())
+                .collect();
+            names.sort_unstable();
+            Err(format!(
+                "Unknown link type '{}'. Valid link types: {}",
+                link_type,
+                names.join(", ")
+            ))
+        }
+    }
+}
+
+/// Resolves a work item link type name against the link types of the organization
+pub async fn resolve_work_item_link_type(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    organization: &str,
+    link_type: &str,
+) -> Result<String, McpError> {
+    let types = client
+        .list_work_item_relation_types(organization)
+        .await
+        .map_err(|e| azure_error_with_context("Failed to list link types", e))?;
+    match_work_item_link_type(&types, link_type).map_err(invalid_params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relation_type(name: &str, reference_name: &str, usage: &str) -> WorkItemRelationType {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "referenceName": reference_name,
+            "attributes": {"usage": usage, "enabled": true}
+        }))
+        .unwrap()
+    }
+
+    fn relation_types() -> Vec<WorkItemRelationType> {
+        vec![
+            relation_type(
+                "Parent",
+                "System.LinkTypes.Hierarchy-Reverse",
+                "workItemLink",
+            ),
+            relation_type(
+                "Child",
+                "System.LinkTypes.Hierarchy-Forward",
+                "workItemLink",
+            ),
+            relation_type("Related", "System.LinkTypes.Related", "workItemLink"),
+            relation_type(
+                "Successor",
+                "System.LinkTypes.Dependency-Forward",
+                "workItemLink",
+            ),
+            relation_type("Hyperlink", "Hyperlink", "resourceLink"),
+        ]
+    }
+
+    #[test]
+    fn test_match_work_item_link_type_by_name_reference_or_alias() {
+        let cases = vec![
+            ("Parent", "System.LinkTypes.Hierarchy-Reverse"),
+            ("child", "System.LinkTypes.Hierarchy-Forward"),
+            ("system.linktypes.related", "System.LinkTypes.Related"),
+            ("Dependency", "System.LinkTypes.Dependency-Forward"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                match_work_item_link_type(&relation_types(), input).unwrap(),
+                expected,
+                "input '{input}'"
+            );
+        }
+    }
+
+    #[test]
+    fn test_match_work_item_link_type_rejects_resource_and_unknown_types() {
+        let error = match_work_item_link_type(&relation_types(), "Hyperlink").unwrap_err();
+        assert!(error.contains("does not link work items"), "{error}");
+
+        let error = match_work_item_link_type(&relation_types(), "Blocks").unwrap_err();
+        assert!(
+            error.contains("Valid link types: Child, Parent, Related, Successor"),
+            "{error}"
+        );
+    }
+}
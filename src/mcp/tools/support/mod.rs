@@ -1,16 +1,42 @@
 // Support module for shared utility functions
+mod azure_error;
 mod board_columns_to_csv;
 mod csv_sanitize;
 mod default_text_format;
 mod deserialize_non_empty_string;
+mod estimates;
+mod link_targets;
+mod query_paging;
+mod query_results_to_text;
 mod simplify_work_item_json;
+mod team_iteration;
 mod tool_text_success;
 mod work_items_to_csv;
+mod working_days;
 
+pub use azure_error::{
+    FORBIDDEN_ERROR_CODE, UNAUTHORIZED_ERROR_CODE, azure_error_to_mcp, azure_error_with_context,
+    invalid_params,
+};
 pub use board_columns_to_csv::board_columns_to_csv;
 pub use csv_sanitize::sanitize_csv_value;
 pub use default_text_format::default_text_format;
 pub use deserialize_non_empty_string::deserialize_non_empty_string;
-pub use simplify_work_item_json::simplify_work_item_json;
+pub use estimates::{REMAINING_WORK_FIELD, STORY_POINTS_FIELDS, format_number};
+pub use link_targets::{
+    LinkTarget, LinkTargetArgs, match_work_item_link_type, resolve_link_target,
+    resolve_work_item_link_type,
+};
+pub use query_paging::{
+    DEFAULT_QUERY_PAGE_SIZE, MAX_QUERY_PAGE_SIZE, query_page_summary, resolve_query_page,
+};
+pub use query_results_to_text::query_results_to_text;
+pub use simplify_work_item_json::{
+    simplify_field_name, simplify_identity_value, simplify_work_item_json,
+};
+pub use team_iteration::resolve_team_iteration;
 pub use tool_text_success::{UNTRUSTED_CONTENT_WARNING, tool_text_success};
 pub use work_items_to_csv::work_items_to_csv;
+pub use working_days::{
+    DayOffArgs, count_working_days, days_off_to_ranges, format_day, format_days_off, parse_day,
+};
@@ -0,0 +1,119 @@
+use crate::azure::models::WorkItemQueryPage;
+use crate::mcp::tools::support::invalid_params;
+use rmcp::ErrorData as McpError;
+
+/// Number of work items returned per page when `top` is not specified
+pub const DEFAULT_QUERY_PAGE_SIZE: usize = 200;
+/// Upper bound for `top`, matching the batch limit of the work items API
+pub const MAX_QUERY_PAGE_SIZE: usize = 1000;
+
+/// Resolves the `skip` / `cursor` / `top` paging arguments of the query tools
+/// into a `(skip, top)` pair. `cursor` is the `next_cursor` value reported by a
+/// previous page and is mutually exclusive with `skip`.
+pub fn resolve_query_page(
+    skip: Option<u32>,
+    cursor: Option<&str>,
+    top: Option<u32>,
+) -> Result<(usize, usize), McpError> {
+    let skip = match (skip, cursor) {
+        (Some(_), Some(_)) => {
+            return Err(invalid_params("Provide either skip or cursor, not both"));
+        }
+        (Some(skip), None) => skip as usize,
+        (None, Some(cursor)) => cursor
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| invalid_params(format!("Invalid cursor '{}'", cursor)))?,
+        (None, None) => 0,
+    };
+
+    let top = match top {
+        Some(0) => return Err(invalid_params("top must be greater than 0")),
+        Some(top) if top as usize > MAX_QUERY_PAGE_SIZE => {
+            return Err(invalid_params(format!(
+                "top must be at most {}",
+                MAX_QUERY_PAGE_SIZE
+            )));
+        }
+        Some(top) => top as usize,
+        None => DEFAULT_QUERY_PAGE_SIZE,
+    };
+
+    Ok((skip, top))
+}
+
+/// One-line paging summary prepended to query results, so the model knows
+/// whether the list is complete and how to ask for the next page.
+pub fn query_page_summary(page: &WorkItemQueryPage) -> String {
+    let mut summary = format!(
+        "total_count={},returned={},skip={},truncated={}",
+        page.total_count,
+        page.work_items.len(),
+        page.skip,
+        page.truncated
+    );
+    if page.truncated {
+        summary.push_str(&format!(
+            ",next_cursor={}",
+            page.skip + page.work_items.len()
+        ));
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::azure::models::WorkItem;
+
+    #[test]
+    fn test_resolve_query_page() {
+        assert_eq!(
+            resolve_query_page(None, None, None),
+            Ok((0, DEFAULT_QUERY_PAGE_SIZE))
+        );
+        assert_eq!(resolve_query_page(Some(50), None, Some(25)), Ok((50, 25)));
+        assert_eq!(resolve_query_page(None, Some("400"), None).unwrap().0, 400);
+        assert_eq!(
+            resolve_query_page(Some(1), Some("1"), None)
+                .unwrap_err()
+                .code,
+            rmcp::model::ErrorCode::INVALID_PARAMS
+        );
+        assert!(resolve_query_page(None, Some("abc"), None).is_err());
+        assert!(resolve_query_page(None, None, Some(0)).is_err());
+        assert!(resolve_query_page(None, None, Some(MAX_QUERY_PAGE_SIZE as u32 + 1)).is_err());
+    }
+
+    #[test]
+    fn test_query_page_summary() {
+        let page = WorkItemQueryPage {
+            work_items: vec![],
+            total_count: 10,
+            skip: 10,
+            truncated: false,
+            links: Vec::new(),
+        };
+        assert_eq!(
+            query_page_summary(&page),
+            "total_count=10,returned=0,skip=10,truncated=false"
+        );
+
+        let page = WorkItemQueryPage {
+            work_items: vec![WorkItem {
+                id: 1,
+                fields: Default::default(),
+                url: None,
+                comments: None,
+            }],
+            total_count: 10,
+            skip: 4,
+            truncated: true,
+            links: Vec::new(),
+        };
+        assert_eq!(
+            query_page_summary(&page),
+            "total_count=10,returned=1,skip=4,truncated=true,next_cursor=5"
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use super::{query_page_summary, simplify_work_item_json, work_items_to_csv};
+use crate::azure::models::{WorkItem, WorkItemLink, WorkItemQueryPage};
+use crate::azure::work_items::HIERARCHY_FORWARD_REL;
+use std::collections::{HashMap, HashSet};
+
+const LINK_TYPE_PREFIX: &str = "System.LinkTypes.";
+
+/// Renders one page of query results: the paging summary, the work items as
+/// CSV and, for link queries, the links between them as an indented hierarchy.
+pub fn query_results_to_text(page: &WorkItemQueryPage) -> Result<String, String> {
+    let summary = query_page_summary(page);
+    if page.work_items.is_empty() {
+        return Ok(format!("No work items found\n{}", summary));
+    }
+
+    let mut json_value = serde_json::to_value(&page.work_items)
+        .map_err(|e| format!("Failed to serialize response: {}", e))?;
+    simplify_work_item_json(&mut json_value);
+    let csv_output = work_items_to_csv(&json_value)?;
+    if page.links.is_empty() {
+        return Ok(format!("{}\n{}", summary, csv_output));
+    }
+    Ok(format!(
+        "{}\nhierarchy:\n{}\n{}",
+        summary,
+        links_to_hierarchy(&page.work_items, &page.links),
+        csv_output
+    ))
+}
+
+/// Renders the links of a link query as one line per work item, indented two
+/// spaces per level below its source. Links other than parent-child ones are
+/// labelled with their type. An item whose source is not on the page starts a
+/// new root labelled with that source, and an item reached again (through a
+/// second link or a cycle) is not expanded twice.
+fn links_to_hierarchy(work_items: &[WorkItem], links: &[WorkItemLink]) -> String {
+    let work_items: HashMap<u32, &WorkItem> = work_items.iter().map(|wi| (wi.id, wi)).collect();
+    let on_page: HashSet<u32> = links.iter().map(|link| link.target).collect();
+
+    let mut children: HashMap<u32, Vec<&WorkItemLink>> = HashMap::new();
+    let mut roots = Vec::new();
+    for link in links {
+        match link.source {
+            Some(source) if on_page.contains(&source) => {
+                children.entry(source).or_default().push(link)
+            }
+            _ => roots.push(link),
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut rendered = HashSet::new();
+    // Depth-first, with an explicit stack so deep trees cannot overflow
+    let mut stack: Vec<(&WorkItemLink, usize)> = roots.into_iter().rev().map(|l| (l, 0)).collect();
+    while let Some((link, depth)) = stack.pop() {
+        let mut line = format!("{}{}", "  ".repeat(depth), link.target);
+        if let Some(work_item) = work_items.get(&link.target) {
+            line.push_str(&describe(work_item));
+        }
+        if let Some(label) = link_label(link, depth) {
+            line.push_str(&format!(" [{}]", label));
+        }
+        if !rendered.insert(link.target) {
+            line.push_str(" (see above)");
+            lines.push(line);
+            continue;
+        }
+        lines.push(line);
+        if let Some(links) = children.get(&link.target) {
+            stack.extend(links.iter().rev().map(|child| (*child, depth + 1)));
+        }
+    }
+    lines.join("\n")
+}
+
+/// ` Type: Title`, from the fields returned for the work item
+fn describe(work_item: &WorkItem) -> String {
+    let field = |name: &str| {
+        work_item
+            .fields
+            .get(name)
+            .and_then(|value| value.as_str())
+            .map(|value| value.replace(['\n', '\r'], " "))
+    };
+    match (field("System.WorkItemType"), field("System.Title")) {
+        (Some(work_item_type), Some(title)) => format!(" {}: {}", work_item_type, title),
+        (Some(work_item_type), None) => format!(" {}", work_item_type),
+        (None, Some(title)) => format!(" {}", title),
+        (None, None) => String::new(),
+    }
+}
+
+/// Label of a link: its type unless it is a parent-child link shown by the
+/// indentation, and its source when that is not the line above
+fn link_label(link: &WorkItemLink, depth: usize) -> Option<String> {
+    let link_type = link
+        .link_type
+        .as_deref()
+        .filter(|link_type| *link_type != HIERARCHY_FORWARD_REL)
+        .map(|link_type| link_type.trim_start_matches(LINK_TYPE_PREFIX));
+    match (link.source, depth) {
+        (Some(source), 0) => Some(match link_type {
+            Some(link_type) => format!("{} from {}", link_type, source),
+            None => format!("child of {}", source),
+        }),
+        _ => link_type.map(str::to_string),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn work_item(id: u32, work_item_type: &str, title: &str) -> WorkItem {
+        let mut fields = HashMap::new();
+        fields.insert(
+            "System.WorkItemType".to_string(),
+            serde_json::json!(work_item_type),
+        );
+        fields.insert("System.Title".to_string(), serde_json::json!(title));
+        WorkItem {
+            id,
+            fields,
+            url: None,
+            comments: None,
+        }
+    }
+
+    fn link(source: Option<u32>, target: u32, link_type: Option<&str>) -> WorkItemLink {
+        WorkItemLink {
+            source,
+            target,
+            link_type: link_type.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_links_to_hierarchy_indents_tree_queries() {
+        let work_items = vec![
+            work_item(1, "Epic", "Checkout"),
+            work_item(2, "Feature", "Payments"),
+            work_item(3, "User Story", "Card form"),
+            work_item(4, "Task", "Validate CVV"),
+            work_item(5, "Feature", "Shipping"),
+        ];
+        let links = vec![
+            link(None, 1, None),
+            link(Some(1), 2, Some(HIERARCHY_FORWARD_REL)),
+            link(Some(2), 3, Some(HIERARCHY_FORWARD_REL)),
+            link(Some(3), 4, Some(HIERARCHY_FORWARD_REL)),
+            link(Some(1), 5, Some(HIERARCHY_FORWARD_REL)),
+        ];
+        assert_eq!(
+            links_to_hierarchy(&work_items, &links),
+            "1 Epic: Checkout\n  2 Feature: Payments\n    3 User Story: Card form\n      4 Task: Validate CVV\n  5 Feature: Shipping"
+        );
+    }
+
+    #[test]
+    fn test_links_to_hierarchy_labels_other_links_and_orphans() {
+        let work_items = vec![work_item(1, "Bug", "Crash"), work_item(2, "Bug", "Dupe")];
+        let links = vec![
+            link(None, 1, None),
+            link(Some(1), 2, Some("System.LinkTypes.Duplicate-Forward")),
+            link(Some(9), 3, Some(HIERARCHY_FORWARD_REL)),
+            // A cycle back to the root is reported, not expanded again
+            link(Some(2), 1, Some("System.LinkTypes.Related")),
+        ];
+        assert_eq!(
+            links_to_hierarchy(&work_items, &links),
+            "1 Bug: Crash\n  2 Bug: Dupe [Duplicate-Forward]\n    1 Bug: Crash [Related] (see above)\n3 [child of 9]"
+        );
+    }
+}
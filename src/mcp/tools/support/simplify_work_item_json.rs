@@ -12,6 +12,57 @@ static RE_IMAGE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\[image\]").unwrap(
 
 const MAX_RECURSION_DEPTH: usize = 64;
 
+/// Shortens a work item field reference name for LLM output, e.g.
+/// `System.IterationPath` -> `Iteration`, `Microsoft.VSTS.Scheduling.StoryPoints`
+/// -> `StoryPoints` and `WEF_..._Kanban.Column` -> `Column`.
+pub fn simplify_field_name(key: &str) -> String {
+    let short_key = if let Some(stripped) = key.strip_prefix("System.") {
+        stripped
+    } else if let Some(stripped) = key.strip_prefix("Microsoft.VSTS.Common.") {
+        stripped
+    } else if let Some(stripped) = key.strip_prefix("Microsoft.VSTS.Scheduling.") {
+        stripped
+    } else if let Some(stripped) = key.strip_prefix("Microsoft.VSTS.CMMI.") {
+        stripped
+    } else if key.contains("_Kanban.Column") {
+        // Handle dynamic WEF_..._Kanban.Column -> Column
+        "Column"
+    } else if key.contains("_Kanban.Lane") {
+        // Handle dynamic WEF_..._Kanban.Lane -> Lane
+        "Lane"
+    } else {
+        key
+    };
+
+    // Rename BoardColumn to Column and BoardLane to Lane
+    match short_key {
+        "BoardColumn" => "Column",
+        "BoardLane" => "Lane",
+        "AcceptanceCriteria" => "Acceptance",
+        "TeamProject" => "Project",
+        "WorkItemType" => "Type",
+        "IterationPath" => "Iteration",
+        other => other,
+    }
+    .to_string()
+}
+
+/// Collapses an identity object (with `displayName`/`uniqueName`) into a
+/// `Name <unique@name>` string. Any other value is returned unchanged.
+pub fn simplify_identity_value(val: Value) -> Value {
+    if let Value::Object(ref obj) = val
+        && let Some(Value::String(name)) = obj.get("displayName")
+    {
+        if let Some(Value::String(unique_name)) = obj.get("uniqueName")
+            && !unique_name.is_empty()
+        {
+            return Value::String(format!("{} <{}>", name, unique_name));
+        }
+        return Value::String(name.clone());
+    }
+    val
+}
+
 /// Recursively simplifies the JSON output to reduce token usage for LLMs.
 /// It removes "_links", "url", "descriptor", "imageUrl", "avatar" and simplifies field names.
 /// It also flattens the "fields" object to the root level and removes redundant properties.
@@ -42,46 +93,14 @@ fn simplify_work_item_json_inner(value: &mut Value, depth: usize) {
                 for key in keys {
                     if let Some(mut val) = fields_map.remove(&key) {
                         // Simplify Identity fields (objects with displayName, uniqueName, etc.)
-                        if let Value::Object(ref obj) = val
-                            && let Some(Value::String(name)) = obj.get("displayName")
-                        {
-                            let mut display_value = name.clone();
-                            if let Some(Value::String(unique_name)) = obj.get("uniqueName")
-                                && !unique_name.is_empty()
-                            {
-                                display_value = format!("{} <{}>", name, unique_name);
-                            }
-                            val = Value::String(display_value);
-                        }
+                        val = simplify_identity_value(val);
 
                         // Simplify field names and filter out unwanted fields
-                        let new_key = if key.starts_with("System.") {
-                            key.strip_prefix("System.").unwrap().to_string()
-                        } else if key.starts_with("Microsoft.VSTS.Common.") {
-                            key.strip_prefix("Microsoft.VSTS.Common.")
-                                .unwrap()
-                                .to_string()
-                        } else if key.starts_with("Microsoft.VSTS.Scheduling.") {
-                            key.strip_prefix("Microsoft.VSTS.Scheduling.")
-                                .unwrap()
-                                .to_string()
-                        } else if key.starts_with("Microsoft.VSTS.CMMI.") {
-                            key.strip_prefix("Microsoft.VSTS.CMMI.")
-                                .unwrap()
-                                .to_string()
-                        } else if key.contains("_Kanban.Column") {
-                            // Handle dynamic WEF_..._Kanban.Column -> Column
-                            "Column".to_string()
-                        } else if key.contains("_Kanban.Lane") {
-                            // Handle dynamic WEF_..._Kanban.Lane -> Lane
-                            "Lane".to_string()
-                        } else {
-                            key
-                        };
+                        let final_key = simplify_field_name(&key);
 
                         // Skip unwanted fields
                         if matches!(
-                            new_key.as_str(),
+                            final_key.as_str(),
                             "ActivatedBy"
                                 | "ActivatedDate"
                                 | "BoardColumnDone"
@@ -97,17 +116,6 @@ fn simplify_work_item_json_inner(value: &mut Value, depth: usize) {
                             continue;
                         }
 
-                        // Rename BoardColumn to Column and BoardLane to Lane
-                        let final_key = match new_key.as_str() {
-                            "BoardColumn" => "Column".to_string(),
-                            "BoardLane" => "Lane".to_string(),
-                            "AcceptanceCriteria" => "Acceptance".to_string(),
-                            "TeamProject" => "Project".to_string(),
-                            "WorkItemType" => "Type".to_string(),
-                            "IterationPath" => "Iteration".to_string(),
-                            _ => new_key,
-                        };
-
                         // Convert HTML to text for specific fields
                         if matches!(
                             final_key.as_str(),
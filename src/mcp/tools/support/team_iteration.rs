@@ -0,0 +1,29 @@
+use super::{azure_error_to_mcp, invalid_params};
+use crate::azure::api_trait::AzureDevOpsApi;
+use crate::azure::iterations::TeamSettingsIteration;
+use rmcp::ErrorData as McpError;
+
+/// Iteration argument that stands for the team's current iteration
+const CURRENT_ITERATION: &str = "current";
+
+/// Looks up a team iteration by ID, or the team's current iteration when
+/// `iteration_id` is omitted or `current`
+pub async fn resolve_team_iteration(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    organization: &str,
+    project: &str,
+    team_id: &str,
+    iteration_id: Option<&str>,
+) -> Result<TeamSettingsIteration, McpError> {
+    match iteration_id.map(str::trim) {
+        Some(id) if !id.is_empty() && !id.eq_ignore_ascii_case(CURRENT_ITERATION) => client
+            .get_team_iteration(organization, project, team_id, id)
+            .await
+            .map_err(azure_error_to_mcp),
+        _ => client
+            .get_team_current_iteration(organization, project, team_id)
+            .await
+            .map_err(azure_error_to_mcp)?
+            .ok_or_else(|| invalid_params("The team has no current iteration; pass iteration_id")),
+    }
+}
@@ -0,0 +1,169 @@
+use crate::azure::capacity::DateRange;
+use azure_core::time::{Duration, OffsetDateTime, parse_rfc3339};
+use rmcp::{
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+/// A day or an inclusive range of days off, as passed to the capacity tools
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DayOffArgs {
+    /// First day off (YYYY-MM-DD)
+    pub start: String,
+    /// Last day off (YYYY-MM-DD, default: start)
+    #[serde(default)]
+    pub end: Option<String>,
+}
+
+/// Converts days off arguments into the date ranges of the capacity API
+pub fn days_off_to_ranges(days_off: &[DayOffArgs]) -> Result<Vec<DateRange>, String> {
+    days_off
+        .iter()
+        .map(|day_off| {
+            let start = parse_day(&day_off.start)?;
+            let end = match &day_off.end {
+                Some(end) => parse_day(end)?,
+                None => start,
+            };
+            if end < start {
+                return Err(format!(
+                    "Days off end {} is before start {}",
+                    format_day(end),
+                    format_day(start)
+                ));
+            }
+            Ok(DateRange {
+                start: format!("{}T00:00:00Z", format_day(start)),
+                end: format!("{}T00:00:00Z", format_day(end)),
+            })
+        })
+        .collect()
+}
+
+/// `;`-separated days off, a range written `start..end`
+pub fn format_days_off(days_off: &[DateRange]) -> String {
+    days_off
+        .iter()
+        .map(|range| {
+            let start = range.start.split('T').next().unwrap_or_default();
+            let end = range.end.split('T').next().unwrap_or_default();
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{}..{}", start, end)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// Parses a `YYYY-MM-DD` day or an RFC 3339 timestamp into midnight UTC of
+/// that day
+pub fn parse_day(value: &str) -> Result<OffsetDateTime, String> {
+    let day = value.trim().split('T').next().unwrap_or_default();
+    parse_rfc3339(&format!("{}T00:00:00Z", day))
+        .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", value))
+}
+
+/// `YYYY-MM-DD` of a timestamp
+pub fn format_day(day: OffsetDateTime) -> String {
+    day.date().to_string()
+}
+
+/// Number of days from `from` to `to` (both inclusive) that are working days
+/// (lowercase day names, e.g. "monday") and not within any of `days_off`.
+/// Days off that cannot be parsed are ignored.
+pub fn count_working_days(
+    from: OffsetDateTime,
+    to: OffsetDateTime,
+    working_days: &[String],
+    days_off: &[&DateRange],
+) -> u32 {
+    let days_off: Vec<(OffsetDateTime, OffsetDateTime)> = days_off
+        .iter()
+        .filter_map(|range| Some((parse_day(&range.start).ok()?, parse_day(&range.end).ok()?)))
+        .collect();
+    let mut count = 0;
+    let mut day = from;
+    while day <= to {
+        let weekday = day.weekday().to_string().to_lowercase();
+        let working = working_days
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(&weekday));
+        let off = days_off
+            .iter()
+            .any(|(start, end)| *start <= day && day <= *end);
+        if working && !off {
+            count += 1;
+        }
+        day += Duration::days(1);
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weekdays() -> Vec<String> {
+        ["monday", "tuesday", "wednesday", "thursday", "friday"]
+            .iter()
+            .map(|day| day.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_day() {
+        assert_eq!(
+            format_day(parse_day("2026-10-05T00:00:00Z").unwrap()),
+            "2026-10-05"
+        );
+        assert_eq!(format_day(parse_day("2026-10-05").unwrap()), "2026-10-05");
+        assert!(parse_day("next monday").is_err());
+    }
+
+    #[test]
+    fn test_count_working_days() {
+        // Monday 2026-10-05 to Friday 2026-10-16: two working weeks
+        let from = parse_day("2026-10-05").unwrap();
+        let to = parse_day("2026-10-16").unwrap();
+        assert_eq!(count_working_days(from, to, &weekdays(), &[]), 10);
+
+        let off = DateRange {
+            start: "2026-10-09T00:00:00Z".to_string(),
+            end: "2026-10-12T00:00:00Z".to_string(),
+        };
+        // Friday to Monday off: two working days
+        assert_eq!(count_working_days(from, to, &weekdays(), &[&off]), 8);
+        assert_eq!(count_working_days(to, from, &weekdays(), &[]), 0);
+    }
+
+    #[test]
+    fn test_days_off_to_ranges() {
+        let ranges = days_off_to_ranges(&[
+            DayOffArgs {
+                start: "2026-10-09".to_string(),
+                end: Some("2026-10-12".to_string()),
+            },
+            DayOffArgs {
+                start: "2026-10-14".to_string(),
+                end: None,
+            },
+        ])
+        .unwrap();
+        assert_eq!(ranges[1].start, "2026-10-14T00:00:00Z");
+        assert_eq!(ranges[1].end, "2026-10-14T00:00:00Z");
+        assert_eq!(
+            format_days_off(&ranges),
+            "2026-10-09..2026-10-12;2026-10-14"
+        );
+
+        assert!(
+            days_off_to_ranges(&[DayOffArgs {
+                start: "2026-10-09".to_string(),
+                end: Some("2026-10-08".to_string()),
+            }])
+            .is_err()
+        );
+    }
+}
@@ -1,10 +1,12 @@
 use crate::azure::api_trait::AzureDevOpsApi;
 
-use crate::mcp::tools::support::{deserialize_non_empty_string, tool_text_success};
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, tool_text_success,
+};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, ErrorCode},
+    model::CallToolResult,
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -19,7 +21,7 @@ pub struct ListTagsArgs {
     pub project: String,
 }
 
-#[mcp_tool(name = "azdo_list_tags", description = "List tags")]
+#[mcp_tool(name = "azdo_list_tags", description = "List tags", read_only = true)]
 pub async fn list_tags(
     client: &(dyn AzureDevOpsApi + Send + Sync),
     args: ListTagsArgs,
@@ -28,11 +30,7 @@ pub async fn list_tags(
     let tags = client
         .list_tags(&args.organization, &args.project)
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(azure_error_to_mcp)?;
 
     // Extract just the tag names for compact response
     let tag_names: Vec<String> = tags.into_iter().map(|tag| tag.name).collect();
@@ -1,6 +1,8 @@
 use crate::azure::api_trait::AzureDevOpsApi;
 use crate::compact_llm;
-use crate::mcp::tools::support::{deserialize_non_empty_string, tool_text_success};
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, tool_text_success,
+};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
@@ -25,7 +27,11 @@ pub struct GetBoardArgs {
     pub board_id: String,
 }
 
-#[mcp_tool(name = "azdo_get_team_board", description = "Get board details")]
+#[mcp_tool(
+    name = "azdo_get_team_board",
+    description = "Get board details",
+    read_only = true
+)]
 pub async fn get_team_board(
     client: &(dyn AzureDevOpsApi + Send + Sync),
     args: GetBoardArgs,
@@ -43,11 +49,7 @@ pub async fn get_team_board(
             &args.board_id,
         )
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(azure_error_to_mcp)?;
 
     let output = compact_llm::to_compact_string(&board).map_err(|e| McpError {
         code: ErrorCode(-32000),
@@ -1,6 +1,6 @@
 use crate::azure::api_trait::AzureDevOpsApi;
 use crate::mcp::tools::support::{
-    board_columns_to_csv, deserialize_non_empty_string, tool_text_success,
+    azure_error_to_mcp, board_columns_to_csv, deserialize_non_empty_string, tool_text_success,
 };
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
@@ -26,7 +26,11 @@ pub struct ListBoardColumnsArgs {
     pub board_id: String,
 }
 
-#[mcp_tool(name = "azdo_list_board_columns", description = "List board columns")]
+#[mcp_tool(
+    name = "azdo_list_board_columns",
+    description = "List board columns",
+    read_only = true
+)]
 pub async fn list_board_columns(
     client: &(dyn AzureDevOpsApi + Send + Sync),
     args: ListBoardColumnsArgs,
@@ -44,11 +48,7 @@ pub async fn list_board_columns(
             &args.board_id,
         )
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(azure_error_to_mcp)?;
 
     let csv_data = board_columns_to_csv(&columns).map_err(|e| McpError {
         code: ErrorCode(-32000),
@@ -1,6 +1,8 @@
 use crate::azure::api_trait::AzureDevOpsApi;
 use crate::compact_llm;
-use crate::mcp::tools::support::{deserialize_non_empty_string, tool_text_success};
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, tool_text_success,
+};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
@@ -27,7 +29,8 @@ pub struct ListBoardRowsArgs {
 
 #[mcp_tool(
     name = "azdo_list_board_rows",
-    description = "List board rows (swimlanes)"
+    description = "List board rows (swimlanes)",
+    read_only = true
 )]
 pub async fn list_board_rows(
     client: &(dyn AzureDevOpsApi + Send + Sync),
@@ -46,11 +49,7 @@ pub async fn list_board_rows(
             &args.board_id,
         )
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(azure_error_to_mcp)?;
 
     // Extract row names into an array
     let row_names: Vec<String> = rows
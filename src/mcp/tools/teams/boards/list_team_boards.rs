@@ -1,6 +1,8 @@
 use crate::azure::api_trait::AzureDevOpsApi;
 use crate::compact_llm;
-use crate::mcp::tools::support::{deserialize_non_empty_string, tool_text_success};
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, tool_text_success,
+};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
@@ -22,7 +24,11 @@ pub struct ListBoardsArgs {
     pub team_id: String,
 }
 
-#[mcp_tool(name = "azdo_list_team_boards", description = "List boards")]
+#[mcp_tool(
+    name = "azdo_list_team_boards",
+    description = "List boards",
+    read_only = true
+)]
 pub async fn list_team_boards(
     client: &(dyn AzureDevOpsApi + Send + Sync),
     args: ListBoardsArgs,
@@ -34,11 +40,7 @@ pub async fn list_team_boards(
     let boards = client
         .list_boards(&args.organization, &args.project, &args.team_id)
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(azure_error_to_mcp)?;
 
     // Extract just the board names for compact response
     let board_names: Vec<String> = boards.into_iter().map(|board| board.name).collect();
@@ -0,0 +1,118 @@
+use crate::azure::api_trait::AzureDevOpsApi;
+use crate::azure::capacity::TeamMemberCapacity;
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, format_days_off, resolve_team_iteration,
+    sanitize_csv_value, tool_text_success,
+};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GetSprintCapacityArgs {
+    /// AzDO org
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub team_id: String,
+    /// Iteration ID (default: the team's current iteration)
+    #[serde(default)]
+    pub iteration_id: Option<String>,
+}
+
+/// Converts member capacities to CSV, one row per member and activity.
+/// Columns: member, activity, capacity_per_day, days_off
+fn capacities_to_csv(members: &[TeamMemberCapacity]) -> Result<String, String> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record(["member", "activity", "capacity_per_day", "days_off"])
+        .map_err(|e| format!("Failed to write CSV header: {}", e))?;
+
+    for member in members {
+        let name = sanitize_csv_value(&member.team_member.display_name);
+        let days_off = format_days_off(&member.days_off);
+        if member.activities.is_empty() {
+            wtr.write_record([name.as_str(), "", "0", &days_off])
+                .map_err(|e| format!("Failed to write CSV row: {}", e))?;
+        }
+        for activity in &member.activities {
+            wtr.write_record([
+                name.clone(),
+                sanitize_csv_value(&activity.name),
+                activity.capacity_per_day.to_string(),
+                days_off.clone(),
+            ])
+            .map_err(|e| format!("Failed to write CSV row: {}", e))?;
+        }
+    }
+
+    let csv_bytes = wtr
+        .into_inner()
+        .map_err(|e| format!("Failed to get CSV bytes: {}", e))?;
+    String::from_utf8(csv_bytes).map_err(|e| format!("Failed to convert CSV to string: {}", e))
+}
+
+#[mcp_tool(
+    name = "azdo_get_sprint_capacity",
+    description = "Get the capacity per day and activity of each team member, and the team and individual days off, for an iteration",
+    read_only = true
+)]
+pub async fn get_sprint_capacity(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    args: GetSprintCapacityArgs,
+) -> Result<CallToolResult, McpError> {
+    log::info!(
+        "Tool invoked: azdo_get_sprint_capacity(team_id={}, iteration_id={:?})",
+        args.team_id,
+        args.iteration_id
+    );
+    let iteration = resolve_team_iteration(
+        client,
+        &args.organization,
+        &args.project,
+        &args.team_id,
+        args.iteration_id.as_deref(),
+    )
+    .await?;
+
+    let capacity = client
+        .get_iteration_capacities(
+            &args.organization,
+            &args.project,
+            &args.team_id,
+            &iteration.id,
+        )
+        .await
+        .map_err(azure_error_to_mcp)?;
+    let team_days_off = client
+        .get_team_days_off(
+            &args.organization,
+            &args.project,
+            &args.team_id,
+            &iteration.id,
+        )
+        .await
+        .map_err(azure_error_to_mcp)?;
+
+    let csv_output = capacities_to_csv(&capacity.team_members).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert to CSV: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(tool_text_success(format!(
+        "iteration={},id={},team_days_off={},total_capacity_per_day={}\n{}",
+        iteration.name,
+        iteration.id,
+        format_days_off(&team_days_off),
+        capacity.total_capacity_per_day,
+        csv_output
+    )))
+}
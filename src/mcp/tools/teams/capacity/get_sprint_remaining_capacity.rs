@@ -0,0 +1,285 @@
+use crate::azure::api_trait::AzureDevOpsApi;
+use crate::azure::capacity::DateRange;
+use crate::azure::models::WorkItem;
+use crate::mcp::tools::support::{
+    MAX_QUERY_PAGE_SIZE, REMAINING_WORK_FIELD, azure_error_to_mcp, count_working_days,
+    deserialize_non_empty_string, format_day, format_days_off, format_number, invalid_params,
+    parse_day, resolve_team_iteration, sanitize_csv_value, tool_text_success,
+};
+use azure_core::time::OffsetDateTime;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use serde_json::Value;
+
+const UNASSIGNED: &str = "(unassigned)";
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GetSprintRemainingCapacityArgs {
+    /// AzDO org
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub team_id: String,
+    /// Iteration ID (default: the team's current iteration)
+    #[serde(default)]
+    pub iteration_id: Option<String>,
+}
+
+/// Capacity left and work remaining of one team member (or of the
+/// assignees without capacity, and of unassigned work)
+struct MemberBalance {
+    name: String,
+    unique_name: Option<String>,
+    capacity_per_day: f64,
+    working_days_left: u32,
+    remaining_work: f64,
+}
+
+impl MemberBalance {
+    fn remaining_capacity(&self) -> f64 {
+        self.capacity_per_day * self.working_days_left as f64
+    }
+}
+
+/// Adds the remaining work of `item` to its assignee, matched by unique
+/// name then display name, or to a new row for that assignee
+fn add_remaining_work(balances: &mut Vec<MemberBalance>, item: &WorkItem) {
+    let Some(remaining_work) = item
+        .fields
+        .get(REMAINING_WORK_FIELD)
+        .and_then(Value::as_f64)
+    else {
+        return;
+    };
+    let assignee = item.fields.get("System.AssignedTo");
+    let unique_name = assignee
+        .and_then(|identity| identity.get("uniqueName"))
+        .and_then(Value::as_str);
+    let display_name = assignee
+        .and_then(|identity| identity.get("displayName"))
+        .and_then(Value::as_str)
+        .unwrap_or(UNASSIGNED);
+
+    let position = unique_name
+        .and_then(|unique_name| {
+            balances.iter().position(|balance| {
+                balance
+                    .unique_name
+                    .as_deref()
+                    .is_some_and(|name| name.eq_ignore_ascii_case(unique_name))
+            })
+        })
+        .or_else(|| {
+            balances
+                .iter()
+                .position(|balance| balance.name == display_name)
+        });
+    match position {
+        Some(position) => balances[position].remaining_work += remaining_work,
+        None => balances.push(MemberBalance {
+            name: display_name.to_string(),
+            unique_name: unique_name.map(str::to_string),
+            capacity_per_day: 0.0,
+            working_days_left: 0,
+            remaining_work,
+        }),
+    }
+}
+
+/// Converts balances to CSV with a final total row.
+/// Columns: member, capacity_per_day, working_days_left, remaining_capacity,
+/// remaining_work, balance
+fn balances_to_csv(balances: &[MemberBalance]) -> Result<String, String> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record([
+        "member",
+        "capacity_per_day",
+        "working_days_left",
+        "remaining_capacity",
+        "remaining_work",
+        "balance",
+    ])
+    .map_err(|e| format!("Failed to write CSV header: {}", e))?;
+
+    let total_capacity_per_day: f64 = balances.iter().map(|b| b.capacity_per_day).sum();
+    let total_capacity: f64 = balances.iter().map(MemberBalance::remaining_capacity).sum();
+    let total_work: f64 = balances.iter().map(|b| b.remaining_work).sum();
+    for balance in balances {
+        wtr.write_record([
+            sanitize_csv_value(&balance.name),
+            format_number(balance.capacity_per_day),
+            balance.working_days_left.to_string(),
+            format_number(balance.remaining_capacity()),
+            format_number(balance.remaining_work),
+            format_number(balance.remaining_capacity() - balance.remaining_work),
+        ])
+        .map_err(|e| format!("Failed to write CSV row: {}", e))?;
+    }
+    wtr.write_record([
+        "(total)".to_string(),
+        format_number(total_capacity_per_day),
+        String::new(),
+        format_number(total_capacity),
+        format_number(total_work),
+        format_number(total_capacity - total_work),
+    ])
+    .map_err(|e| format!("Failed to write CSV row: {}", e))?;
+
+    let csv_bytes = wtr
+        .into_inner()
+        .map_err(|e| format!("Failed to get CSV bytes: {}", e))?;
+    String::from_utf8(csv_bytes).map_err(|e| format!("Failed to convert CSV to string: {}", e))
+}
+
+#[mcp_tool(
+    name = "azdo_get_sprint_remaining_capacity",
+    description = "Compare each team member's capacity left in an iteration (working days from today, minus days off) with the RemainingWork of the work items assigned to them in the iteration",
+    read_only = true
+)]
+pub async fn get_sprint_remaining_capacity(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    args: GetSprintRemainingCapacityArgs,
+) -> Result<CallToolResult, McpError> {
+    log::info!(
+        "Tool invoked: azdo_get_sprint_remaining_capacity(team_id={}, iteration_id={:?})",
+        args.team_id,
+        args.iteration_id
+    );
+    let iteration = resolve_team_iteration(
+        client,
+        &args.organization,
+        &args.project,
+        &args.team_id,
+        args.iteration_id.as_deref(),
+    )
+    .await?;
+    let invalid_dates = || {
+        invalid_params(format!(
+            "Iteration '{}' has no valid start and finish dates",
+            iteration.name
+        ))
+    };
+    let start = iteration
+        .attributes
+        .start_date
+        .as_deref()
+        .and_then(|date| parse_day(date).ok())
+        .ok_or_else(invalid_dates)?;
+    let finish = iteration
+        .attributes
+        .finish_date
+        .as_deref()
+        .and_then(|date| parse_day(date).ok())
+        .ok_or_else(invalid_dates)?;
+    let today = parse_day(&format_day(OffsetDateTime::now_utc())).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: e.into(),
+        data: None,
+    })?;
+    let from = start.max(today);
+
+    let working_days = client
+        .get_team_working_days(&args.organization, &args.project, &args.team_id)
+        .await
+        .map_err(azure_error_to_mcp)?;
+    let capacity = client
+        .get_iteration_capacities(
+            &args.organization,
+            &args.project,
+            &args.team_id,
+            &iteration.id,
+        )
+        .await
+        .map_err(azure_error_to_mcp)?;
+    let team_days_off = client
+        .get_team_days_off(
+            &args.organization,
+            &args.project,
+            &args.team_id,
+            &iteration.id,
+        )
+        .await
+        .map_err(azure_error_to_mcp)?;
+
+    let team_working_days_left = count_working_days(
+        from,
+        finish,
+        &working_days,
+        &team_days_off.iter().collect::<Vec<_>>(),
+    );
+    let mut balances: Vec<MemberBalance> = capacity
+        .team_members
+        .iter()
+        .map(|member| {
+            let days_off: Vec<&DateRange> = team_days_off.iter().chain(&member.days_off).collect();
+            MemberBalance {
+                name: member.team_member.display_name.clone(),
+                unique_name: member.team_member.unique_name.clone(),
+                capacity_per_day: member
+                    .activities
+                    .iter()
+                    .map(|activity| activity.capacity_per_day)
+                    .sum(),
+                working_days_left: count_working_days(from, finish, &working_days, &days_off),
+                remaining_work: 0.0,
+            }
+        })
+        .collect();
+
+    let query = format!(
+        "SELECT [System.Id] FROM WorkItems WHERE [System.TeamProject] = '{}' AND [System.IterationPath] = '{}' AND [System.State] <> 'Removed'",
+        args.project.replace("'", "''"),
+        iteration.path.replace("'", "''")
+    );
+    log::debug!("Executing WIQL query: {}", query);
+    let mut skip = 0;
+    let mut items = 0;
+    loop {
+        let page = client
+            .query_work_items(
+                &args.organization,
+                &args.project,
+                &query,
+                skip,
+                MAX_QUERY_PAGE_SIZE,
+                None,
+            )
+            .await
+            .map_err(azure_error_to_mcp)?;
+        for item in &page.work_items {
+            add_remaining_work(&mut balances, item);
+        }
+        items += page.work_items.len();
+        skip += page.work_items.len();
+        if !page.truncated || page.work_items.is_empty() {
+            break;
+        }
+    }
+
+    let csv_output = balances_to_csv(&balances).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert to CSV: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(tool_text_success(format!(
+        "iteration={},id={},from={},to={},team_working_days_left={},team_days_off={},work_items={}\n{}",
+        iteration.name,
+        iteration.id,
+        format_day(from),
+        format_day(finish),
+        team_working_days_left,
+        format_days_off(&team_days_off),
+        items,
+        csv_output
+    )))
+}
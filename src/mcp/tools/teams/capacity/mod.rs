@@ -0,0 +1,13 @@
+// Sprint capacity module
+pub mod get_sprint_capacity;
+pub mod get_sprint_remaining_capacity;
+pub mod set_member_capacity;
+pub mod set_team_days_off;
+
+// Re-export the public items
+pub use get_sprint_capacity::{GetSprintCapacityArgs, get_sprint_capacity};
+pub use get_sprint_remaining_capacity::{
+    GetSprintRemainingCapacityArgs, get_sprint_remaining_capacity,
+};
+pub use set_member_capacity::{ActivityArgs, SetMemberCapacityArgs, set_member_capacity};
+pub use set_team_days_off::{SetTeamDaysOffArgs, set_team_days_off};
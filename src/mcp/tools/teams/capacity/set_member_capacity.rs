@@ -0,0 +1,209 @@
+use crate::azure::api_trait::AzureDevOpsApi;
+use crate::azure::capacity::{Activity, CapacityPatch};
+use crate::mcp::tools::support::{
+    DayOffArgs, azure_error_to_mcp, days_off_to_ranges, deserialize_non_empty_string,
+    format_days_off, invalid_params, resolve_team_iteration, tool_text_success,
+};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::CallToolResult,
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ActivityArgs {
+    /// Activity name, e.g. "Development" or "Testing" (omit for unassigned)
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Hours per day available for the activity
+    pub capacity_per_day: f64,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct SetMemberCapacityArgs {
+    /// AzDO org
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub team_id: String,
+    /// Iteration ID (default: the team's current iteration)
+    #[serde(default)]
+    pub iteration_id: Option<String>,
+    /// Team member ID, display name or unique name (email)
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub member: String,
+    /// Replaces the member's activities and their capacity per day
+    #[serde(default)]
+    pub activities: Option<Vec<ActivityArgs>>,
+    /// Replaces the member's days off ([] clears them)
+    #[serde(default)]
+    pub days_off: Option<Vec<DayOffArgs>>,
+}
+
+/// Picks the ID of the only identity matching `member` by ID, display name or
+/// unique name
+fn match_member<'a>(
+    member: &str,
+    identities: impl Iterator<Item = (&'a str, &'a str, Option<&'a str>)>,
+) -> Result<Option<String>, McpError> {
+    let matches: Vec<(&str, &str)> = identities
+        .filter(|(id, display_name, unique_name)| {
+            id.eq_ignore_ascii_case(member)
+                || display_name.eq_ignore_ascii_case(member)
+                || unique_name.is_some_and(|unique_name| unique_name.eq_ignore_ascii_case(member))
+        })
+        .map(|(id, display_name, _)| (id, display_name))
+        .collect();
+    match matches.as_slice() {
+        [] => Ok(None),
+        [(id, _)] => Ok(Some(id.to_string())),
+        _ => Err(invalid_params(format!(
+            "'{}' matches several team members ({}); pass the member's unique name or ID",
+            member,
+            matches
+                .iter()
+                .map(|(_, display_name)| *display_name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+#[mcp_tool(
+    name = "azdo_set_member_capacity",
+    description = "Set the capacity per day and activity and/or the days off of a team member for an iteration",
+    read_only = false
+)]
+pub async fn set_member_capacity(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    args: SetMemberCapacityArgs,
+) -> Result<CallToolResult, McpError> {
+    log::info!(
+        "Tool invoked: azdo_set_member_capacity(team_id={}, iteration_id={:?}, member={})",
+        args.team_id,
+        args.iteration_id,
+        args.member
+    );
+    if args.activities.is_none() && args.days_off.is_none() {
+        return Err(invalid_params("Provide activities and/or days_off"));
+    }
+    if let Some(activity) = args
+        .activities
+        .iter()
+        .flatten()
+        .find(|activity| activity.capacity_per_day < 0.0)
+    {
+        return Err(invalid_params(format!(
+            "capacity_per_day must not be negative, got {}",
+            activity.capacity_per_day
+        )));
+    }
+    let patch = CapacityPatch {
+        activities: args.activities.as_ref().map(|activities| {
+            activities
+                .iter()
+                .map(|activity| Activity {
+                    capacity_per_day: activity.capacity_per_day,
+                    name: activity.name.clone().unwrap_or_default(),
+                })
+                .collect()
+        }),
+        days_off: args
+            .days_off
+            .as_deref()
+            .map(days_off_to_ranges)
+            .transpose()
+            .map_err(invalid_params)?,
+    };
+
+    let iteration = resolve_team_iteration(
+        client,
+        &args.organization,
+        &args.project,
+        &args.team_id,
+        args.iteration_id.as_deref(),
+    )
+    .await?;
+
+    // Members without capacity yet are only listed by the team members API
+    let capacity = client
+        .get_iteration_capacities(
+            &args.organization,
+            &args.project,
+            &args.team_id,
+            &iteration.id,
+        )
+        .await
+        .map_err(azure_error_to_mcp)?;
+    let mut member_id = match_member(
+        &args.member,
+        capacity.team_members.iter().map(|member| {
+            (
+                member.team_member.id.as_str(),
+                member.team_member.display_name.as_str(),
+                member.team_member.unique_name.as_deref(),
+            )
+        }),
+    )?;
+    if member_id.is_none() {
+        let members = client
+            .list_team_members(&args.organization, &args.project, &args.team_id)
+            .await
+            .map_err(azure_error_to_mcp)?;
+        member_id = match_member(
+            &args.member,
+            members.iter().map(|member| {
+                (
+                    member.identity.id.as_str(),
+                    member.identity.display_name.as_str(),
+                    Some(member.identity.unique_name.as_str()),
+                )
+            }),
+        )?;
+    }
+    let member_id = member_id.ok_or_else(|| {
+        invalid_params(format!(
+            "'{}' is not a member of team '{}'",
+            args.member, args.team_id
+        ))
+    })?;
+
+    let updated = client
+        .update_member_capacity(
+            &args.organization,
+            &args.project,
+            &args.team_id,
+            &iteration.id,
+            &member_id,
+            patch,
+        )
+        .await
+        .map_err(azure_error_to_mcp)?;
+
+    let activities = updated
+        .activities
+        .iter()
+        .map(|activity| {
+            let name = if activity.name.is_empty() {
+                "Unassigned"
+            } else {
+                &activity.name
+            };
+            format!("{}={}", name, activity.capacity_per_day)
+        })
+        .collect::<Vec<_>>()
+        .join(";");
+    Ok(tool_text_success(format!(
+        "Updated capacity of {} for {}: activities={},days_off={}",
+        updated.team_member.display_name,
+        iteration.name,
+        activities,
+        format_days_off(&updated.days_off)
+    )))
+}
@@ -0,0 +1,73 @@
+use crate::azure::api_trait::AzureDevOpsApi;
+use crate::mcp::tools::support::{
+    DayOffArgs, azure_error_to_mcp, days_off_to_ranges, deserialize_non_empty_string,
+    format_days_off, invalid_params, resolve_team_iteration, tool_text_success,
+};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::CallToolResult,
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct SetTeamDaysOffArgs {
+    /// AzDO org
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Team ID or name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub team_id: String,
+    /// Iteration ID (default: the team's current iteration)
+    #[serde(default)]
+    pub iteration_id: Option<String>,
+    /// Replaces the days off of the whole team ([] clears them)
+    pub days_off: Vec<DayOffArgs>,
+}
+
+#[mcp_tool(
+    name = "azdo_set_team_days_off",
+    description = "Set the days off of the whole team for an iteration",
+    read_only = false
+)]
+pub async fn set_team_days_off(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    args: SetTeamDaysOffArgs,
+) -> Result<CallToolResult, McpError> {
+    log::info!(
+        "Tool invoked: azdo_set_team_days_off(team_id={}, iteration_id={:?})",
+        args.team_id,
+        args.iteration_id
+    );
+    let days_off = days_off_to_ranges(&args.days_off).map_err(invalid_params)?;
+
+    let iteration = resolve_team_iteration(
+        client,
+        &args.organization,
+        &args.project,
+        &args.team_id,
+        args.iteration_id.as_deref(),
+    )
+    .await?;
+
+    let days_off = client
+        .set_team_days_off(
+            &args.organization,
+            &args.project,
+            &args.team_id,
+            &iteration.id,
+            days_off,
+        )
+        .await
+        .map_err(azure_error_to_mcp)?;
+
+    Ok(tool_text_success(format!(
+        "Updated team days off for {}: {}",
+        iteration.name,
+        format_days_off(&days_off)
+    )))
+}
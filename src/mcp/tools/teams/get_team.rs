@@ -1,10 +1,12 @@
 use crate::azure::api_trait::AzureDevOpsApi;
 
-use crate::mcp::tools::support::{deserialize_non_empty_string, tool_text_success};
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, tool_text_success,
+};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, ErrorCode},
+    model::CallToolResult,
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -22,7 +24,11 @@ pub struct GetTeamArgs {
     pub team_id: String,
 }
 
-#[mcp_tool(name = "azdo_get_team", description = "Get team details")]
+#[mcp_tool(
+    name = "azdo_get_team",
+    description = "Get team details",
+    read_only = true
+)]
 pub async fn get_team(
     client: &(dyn AzureDevOpsApi + Send + Sync),
     args: GetTeamArgs,
@@ -31,11 +37,7 @@ pub async fn get_team(
     let team = client
         .get_team(&args.organization, &args.project, &args.team_id)
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(azure_error_to_mcp)?;
 
     let description = team.description.unwrap_or_default();
     let output = format!("{},{}", team.name, description);
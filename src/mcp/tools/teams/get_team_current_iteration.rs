@@ -1,9 +1,11 @@
 use crate::azure::api_trait::AzureDevOpsApi;
-use crate::mcp::tools::support::{deserialize_non_empty_string, tool_text_success};
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, tool_text_success,
+};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, ErrorCode},
+    model::CallToolResult,
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -23,7 +25,8 @@ pub struct GetTeamCurrentIterationArgs {
 
 #[mcp_tool(
     name = "azdo_get_team_current_iteration",
-    description = "Get current iteration/sprint for team"
+    description = "Get current iteration/sprint for team",
+    read_only = true
 )]
 pub async fn get_team_current_iteration(
     client: &(dyn AzureDevOpsApi + Send + Sync),
@@ -37,11 +40,7 @@ pub async fn get_team_current_iteration(
     let iteration = client
         .get_team_current_iteration(&args.organization, &args.project, &args.team_id)
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(azure_error_to_mcp)?;
 
     match iteration {
         Some(iteration) => {
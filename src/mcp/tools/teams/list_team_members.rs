@@ -1,5 +1,7 @@
 use crate::azure::api_trait::AzureDevOpsApi;
-use crate::mcp::tools::support::{deserialize_non_empty_string, tool_text_success};
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, tool_text_success,
+};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
@@ -21,7 +23,11 @@ pub struct ListTeamMembersArgs {
     pub team_id: String,
 }
 
-#[mcp_tool(name = "azdo_list_team_members", description = "List team members")]
+#[mcp_tool(
+    name = "azdo_list_team_members",
+    description = "List team members",
+    read_only = true
+)]
 pub async fn list_team_members(
     client: &(dyn AzureDevOpsApi + Send + Sync),
     args: ListTeamMembersArgs,
@@ -30,11 +36,7 @@ pub async fn list_team_members(
     let members = client
         .list_team_members(&args.organization, &args.project, &args.team_id)
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(azure_error_to_mcp)?;
 
     let mut wtr = csv::WriterBuilder::new()
         .has_headers(false)
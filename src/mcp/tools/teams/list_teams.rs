@@ -1,10 +1,12 @@
 use crate::azure::api_trait::AzureDevOpsApi;
 
-use crate::mcp::tools::support::{deserialize_non_empty_string, tool_text_success};
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, tool_text_success,
+};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, ErrorCode},
+    model::CallToolResult,
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -19,7 +21,11 @@ pub struct ListTeamsArgs {
     pub project: String,
 }
 
-#[mcp_tool(name = "azdo_list_teams", description = "List teams in the project")]
+#[mcp_tool(
+    name = "azdo_list_teams",
+    description = "List teams in the project",
+    read_only = true
+)]
 pub async fn list_teams(
     client: &(dyn AzureDevOpsApi + Send + Sync),
     args: ListTeamsArgs,
@@ -28,11 +34,7 @@ pub async fn list_teams(
     let teams = client
         .list_teams(&args.organization, &args.project)
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(azure_error_to_mcp)?;
 
     // Extract just the team names for compact response
     let team_names: Vec<String> = teams.into_iter().map(|team| team.name).collect();
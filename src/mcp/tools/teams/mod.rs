@@ -1,5 +1,6 @@
 // Teams module
 pub mod boards;
+pub mod capacity;
 pub mod get_team;
 pub mod get_team_current_iteration;
 pub mod list_team_members;
@@ -1,6 +1,8 @@
 use crate::azure::api_trait::AzureDevOpsApi;
 use crate::compact_llm;
-use crate::mcp::tools::support::{deserialize_non_empty_string, tool_text_success};
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, tool_text_success,
+};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
@@ -21,7 +23,8 @@ pub struct ListWorkItemTypesArgs {
 
 #[mcp_tool(
     name = "azdo_list_work_item_types",
-    description = "List work item types"
+    description = "List work item types",
+    read_only = true
 )]
 pub async fn list_work_item_types(
     client: &(dyn AzureDevOpsApi + Send + Sync),
@@ -31,11 +34,7 @@ pub async fn list_work_item_types(
     let types = client
         .list_work_item_types(&args.organization, &args.project)
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(azure_error_to_mcp)?;
 
     // Extract just the work item type names for compact response
     let type_names: Vec<String> = types.into_iter().map(|wit| wit.name).collect();
@@ -0,0 +1,211 @@
+use crate::azure::api_trait::AzureDevOpsApi;
+use crate::azure::attachments::attachment_id_from_url;
+use crate::compact_llm;
+use crate::mcp::tools::support::{
+    azure_error_with_context, deserialize_non_empty_string, invalid_params, tool_text_success,
+};
+use base64::Engine;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use std::path::Path;
+
+/// Largest payload accepted by the single-request attachment upload API (130 MB)
+const MAX_ATTACHMENT_SIZE: u64 = 130 * 1024 * 1024;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct AddAttachmentArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Work item ID to attach the file to
+    pub work_item_id: u32,
+    /// Path of a local file to upload (mutually exclusive with content_base64; not accepted in HTTP server mode)
+    #[serde(default)]
+    pub file_path: Option<String>,
+    /// Base64-encoded file content (mutually exclusive with file_path; requires file_name)
+    #[serde(default)]
+    pub content_base64: Option<String>,
+    /// Attachment file name (defaults to the name of file_path)
+    #[serde(default)]
+    pub file_name: Option<String>,
+    /// Optional comment stored on the attachment link
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+/// Resolves the attachment file name and content from either a local file or
+/// a base64 payload.
+async fn resolve_attachment_content(
+    args: &AddAttachmentArgs,
+) -> Result<(String, Vec<u8>), McpError> {
+    match (&args.file_path, &args.content_base64) {
+        (Some(file_path), None) => {
+            let path = Path::new(file_path);
+            let metadata = tokio::fs::metadata(path)
+                .await
+                .map_err(|e| invalid_params(format!("Cannot read file '{}': {}", file_path, e)))?;
+            if !metadata.is_file() {
+                return Err(invalid_params(format!("'{}' is not a file", file_path)));
+            }
+            if metadata.len() > MAX_ATTACHMENT_SIZE {
+                return Err(invalid_params(format!(
+                    "File '{}' is {} bytes, the maximum attachment size is {} bytes",
+                    file_path,
+                    metadata.len(),
+                    MAX_ATTACHMENT_SIZE
+                )));
+            }
+
+            let file_name = match &args.file_name {
+                Some(name) => name.clone(),
+                None => path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .map(|n| n.to_string())
+                    .ok_or_else(|| {
+                        invalid_params(format!("Cannot derive a file name from '{}'", file_path))
+                    })?,
+            };
+            let content = tokio::fs::read(path)
+                .await
+                .map_err(|e| invalid_params(format!("Cannot read file '{}': {}", file_path, e)))?;
+            Ok((file_name, content))
+        }
+        (None, Some(content_base64)) => {
+            let file_name = args
+                .file_name
+                .clone()
+                .ok_or_else(|| invalid_params("file_name is required with content_base64"))?;
+            let content = base64::engine::general_purpose::STANDARD
+                .decode(content_base64.trim())
+                .map_err(|e| invalid_params(format!("Invalid base64 content: {}", e)))?;
+            if content.len() as u64 > MAX_ATTACHMENT_SIZE {
+                return Err(invalid_params(format!(
+                    "Content is {} bytes, the maximum attachment size is {} bytes",
+                    content.len(),
+                    MAX_ATTACHMENT_SIZE
+                )));
+            }
+            Ok((file_name, content))
+        }
+        _ => Err(invalid_params(
+            "Exactly one of file_path or content_base64 must be provided",
+        )),
+    }
+}
+
+#[mcp_tool(
+    name = "azdo_add_attachment",
+    description = "Upload a file and attach it to a work item",
+    read_only = false
+)]
+pub async fn add_attachment(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    args: AddAttachmentArgs,
+) -> Result<CallToolResult, McpError> {
+    let (file_name, content) = resolve_attachment_content(&args).await?;
+    if file_name.trim().is_empty() {
+        return Err(invalid_params("file_name cannot be empty"));
+    }
+
+    log::info!(
+        "Tool invoked: azdo_add_attachment(work_item_id={}, file_name={}, size={})",
+        args.work_item_id,
+        file_name,
+        content.len()
+    );
+
+    let size = content.len();
+    let attachment = client
+        .upload_attachment(&args.organization, &args.project, &file_name, content)
+        .await
+        .map_err(|e| azure_error_with_context("Failed to upload attachment", e))?;
+
+    client
+        .add_attachment_to_work_item(
+            &args.organization,
+            &args.project,
+            args.work_item_id,
+            &attachment.url,
+            args.comment.clone(),
+        )
+        .await
+        .map_err(|e| azure_error_with_context("Failed to attach file to work item", e))?;
+
+    let output = compact_llm::to_compact_string(&serde_json::json!({
+        "id": attachment_id_from_url(&attachment.url),
+        "name": file_name,
+        "size": size,
+        "work_item_id": args.work_item_id,
+    }))
+    .map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to serialize response: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(tool_text_success(output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(json: &str) -> AddAttachmentArgs {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_resolve_attachment_content_from_base64() {
+        let (name, content) = resolve_attachment_content(&args(
+            r#"{"organization":"org","project":"proj","work_item_id":1,"content_base64":"aGVsbG8=","file_name":"hello.txt"}"#,
+        ))
+        .await
+        .unwrap();
+        assert_eq!(name, "hello.txt");
+        assert_eq!(content, b"hello");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_attachment_content_from_file_defaults_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("build.log");
+        std::fs::write(&path, b"log line").unwrap();
+
+        let json = serde_json::json!({
+            "organization": "org",
+            "project": "proj",
+            "work_item_id": 1,
+            "file_path": path.to_str().unwrap(),
+        });
+        let (name, content) = resolve_attachment_content(&serde_json::from_value(json).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(name, "build.log");
+        assert_eq!(content, b"log line");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_attachment_content_rejects_invalid_combinations() {
+        let cases = vec![
+            r#"{"organization":"org","project":"proj","work_item_id":1}"#,
+            r#"{"organization":"org","project":"proj","work_item_id":1,"file_path":"a","content_base64":"aGVsbG8=","file_name":"a"}"#,
+            r#"{"organization":"org","project":"proj","work_item_id":1,"content_base64":"aGVsbG8="}"#,
+            r#"{"organization":"org","project":"proj","work_item_id":1,"content_base64":"not base64!","file_name":"a"}"#,
+        ];
+        for json in cases {
+            let error = resolve_attachment_content(&args(json))
+                .await
+                .expect_err(json);
+            assert_eq!(error.code, ErrorCode(-32602), "case {json}");
+        }
+    }
+}
@@ -0,0 +1,495 @@
+use crate::azure::api_trait::AzureDevOpsApi;
+use crate::azure::models::WorkItemBatchResponse;
+use crate::azure::work_items::{
+    JsonPatchOperation, WorkItemBatchRequest, field_operations, work_item_url,
+};
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, default_text_format, deserialize_non_empty_string, invalid_params,
+    sanitize_csv_value, tool_text_success,
+};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Largest number of requests accepted by the work item `$batch` endpoint
+const MAX_BATCH_SIZE: usize = 200;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct BatchWorkItemsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Format for large text fields (description, acceptance criteria): "markdown" or "html" (default: "markdown")
+    #[serde(default = "default_text_format")]
+    pub format: String,
+    /// Operations, executed in order (at most 200)
+    pub operations: Vec<BatchWorkItemOperation>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct BatchWorkItemOperation {
+    /// "create" or "update"
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub op: String,
+    /// For create: a temporary negative ID (-1, -2, ...) that later operations can use as parent_id. For update: the ID of the existing work item
+    pub id: i32,
+    /// Type of work item to create (User Story, Task, Bug, etc.)
+    #[serde(default)]
+    pub work_item_type: Option<String>,
+    /// Work item title (required for create)
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Work item description
+    #[serde(default)]
+    pub description: Option<String>,
+    /// User to assign the work item to (email or display name)
+    #[serde(default)]
+    pub assigned_to: Option<String>,
+    /// Area path
+    #[serde(default)]
+    pub area_path: Option<String>,
+    /// Iteration path
+    #[serde(default)]
+    pub iteration_path: Option<String>,
+    /// State (New, Active, Resolved, Closed, etc.)
+    #[serde(default)]
+    pub state: Option<String>,
+    /// Priority (1-4, where 1 is highest)
+    #[serde(default)]
+    pub priority: Option<u32>,
+    /// Story points for estimation
+    #[serde(default)]
+    pub story_points: Option<f64>,
+    /// Remaining work in hours
+    #[serde(default)]
+    pub remaining_work: Option<f64>,
+    /// Comma-separated tags
+    #[serde(default)]
+    pub tags: Option<String>,
+    /// Acceptance criteria
+    #[serde(default)]
+    pub acceptance_criteria: Option<String>,
+    /// Parent work item: an existing ID, or the temporary negative ID of a work item created earlier in this batch
+    #[serde(default)]
+    pub parent_id: Option<i32>,
+    /// Optional extra fields as JSON string (for custom fields)
+    #[serde(default)]
+    pub fields: Option<String>,
+}
+
+/// Builds the JSON Patch operations of one batch entry
+fn operation_patch(
+    index: usize,
+    operation: &BatchWorkItemOperation,
+    markdown: bool,
+) -> Result<Vec<JsonPatchOperation>, McpError> {
+    let mut fields: Vec<(String, Value)> = Vec::new();
+    let mut text_fields: Vec<&str> = Vec::new();
+
+    let mut set = |name: &str, value: Value| fields.push((name.to_string(), value));
+    if let Some(title) = &operation.title {
+        set("System.Title", serde_json::json!(title));
+    }
+    if let Some(description) = &operation.description {
+        set("System.Description", serde_json::json!(description));
+        text_fields.push("System.Description");
+    }
+    if let Some(assigned_to) = &operation.assigned_to {
+        set("System.AssignedTo", serde_json::json!(assigned_to));
+    }
+    if let Some(area_path) = &operation.area_path {
+        set("System.AreaPath", serde_json::json!(area_path));
+    }
+    if let Some(iteration_path) = &operation.iteration_path {
+        set("System.IterationPath", serde_json::json!(iteration_path));
+    }
+    if let Some(state) = &operation.state {
+        set("System.State", serde_json::json!(state));
+    }
+    if let Some(priority) = operation.priority {
+        set(
+            "Microsoft.VSTS.Common.Priority",
+            serde_json::json!(priority),
+        );
+    }
+    if let Some(story_points) = operation.story_points {
+        set(
+            "Microsoft.VSTS.Scheduling.StoryPoints",
+            serde_json::json!(story_points),
+        );
+    }
+    if let Some(remaining_work) = operation.remaining_work {
+        set(
+            "Microsoft.VSTS.Scheduling.RemainingWork",
+            serde_json::json!(remaining_work),
+        );
+    }
+    if let Some(tags) = &operation.tags {
+        set("System.Tags", serde_json::json!(tags));
+    }
+    if let Some(acceptance_criteria) = &operation.acceptance_criteria {
+        set(
+            "Microsoft.VSTS.Common.AcceptanceCriteria",
+            serde_json::json!(acceptance_criteria),
+        );
+        text_fields.push("Microsoft.VSTS.Common.AcceptanceCriteria");
+    }
+    if let Some(extra) = &operation.fields {
+        let extra_json =
+            serde_json::from_str::<serde_json::Map<String, Value>>(extra).map_err(|_| {
+                invalid_params(format!("operations[{}]: invalid JSON in fields", index))
+            })?;
+        fields.extend(extra_json);
+    }
+
+    let multiline_formats: Vec<(String, String)> = if markdown {
+        text_fields
+            .into_iter()
+            .map(|field| (field.to_string(), "Markdown".to_string()))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut patch = field_operations(&fields, &multiline_formats);
+    if let Some(parent_id) = operation.parent_id {
+        patch.push(JsonPatchOperation {
+            op: "add".to_string(),
+            path: "/relations/-".to_string(),
+            value: Some(serde_json::json!({
+                "rel": "System.LinkTypes.Hierarchy-Reverse",
+                "url": work_item_url(parent_id),
+            })),
+            from: None,
+        });
+    }
+    Ok(patch)
+}
+
+/// Validates the operations and converts them into `$batch` requests
+fn build_batch_requests(
+    project: &str,
+    operations: &[BatchWorkItemOperation],
+    markdown: bool,
+) -> Result<Vec<WorkItemBatchRequest>, McpError> {
+    if operations.is_empty() {
+        return Err(invalid_params("operations cannot be empty"));
+    }
+    if operations.len() > MAX_BATCH_SIZE {
+        return Err(invalid_params(format!(
+            "At most {} operations can be submitted in one batch",
+            MAX_BATCH_SIZE
+        )));
+    }
+
+    let mut created: HashSet<i32> = HashSet::new();
+    let mut requests = Vec::with_capacity(operations.len());
+
+    for (index, operation) in operations.iter().enumerate() {
+        if let Some(parent_id) = operation.parent_id
+            && parent_id < 0
+            && !created.contains(&parent_id)
+        {
+            return Err(invalid_params(format!(
+                "operations[{}]: parent_id {} does not refer to a work item created earlier in the batch",
+                index, parent_id
+            )));
+        }
+
+        let patch = operation_patch(index, operation, markdown)?;
+        let request = match operation.op.to_lowercase().as_str() {
+            "create" => {
+                if operation.id >= 0 {
+                    return Err(invalid_params(format!(
+                        "operations[{}]: create needs a temporary negative id",
+                        index
+                    )));
+                }
+                if !created.insert(operation.id) {
+                    return Err(invalid_params(format!(
+                        "operations[{}]: temporary id {} is used twice",
+                        index, operation.id
+                    )));
+                }
+                let work_item_type = operation.work_item_type.as_deref().ok_or_else(|| {
+                    invalid_params(format!(
+                        "operations[{}]: create needs work_item_type",
+                        index
+                    ))
+                })?;
+                if operation.title.is_none() {
+                    return Err(invalid_params(format!(
+                        "operations[{}]: create needs a title",
+                        index
+                    )));
+                }
+                WorkItemBatchRequest::create(project, work_item_type, operation.id, patch)
+            }
+            "update" => {
+                if operation.id <= 0 {
+                    return Err(invalid_params(format!(
+                        "operations[{}]: update needs the id of an existing work item",
+                        index
+                    )));
+                }
+                if patch.is_empty() {
+                    return Err(invalid_params(format!(
+                        "operations[{}]: update has nothing to change",
+                        index
+                    )));
+                }
+                WorkItemBatchRequest::update(project, operation.id as u32, patch)
+            }
+            other => {
+                return Err(invalid_params(format!(
+                    "operations[{}]: unknown op '{}', expected \"create\" or \"update\"",
+                    index, other
+                )));
+            }
+        };
+        requests.push(request);
+    }
+
+    Ok(requests)
+}
+
+/// Error message of a failed batch entry, falling back to the raw body
+fn response_error_message(body: &Value, raw: &str) -> String {
+    body.get("message")
+        .or_else(|| body.get("value").and_then(|value| value.get("Message")))
+        .and_then(|message| message.as_str())
+        .map(|message| message.to_string())
+        .unwrap_or_else(|| raw.to_string())
+}
+
+/// Converts the batch responses to a per-operation report.
+/// Columns: index, op, ref_id, id, status, message
+fn batch_report(
+    operations: &[BatchWorkItemOperation],
+    responses: &[WorkItemBatchResponse],
+) -> Result<String, String> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    let mut succeeded = 0;
+
+    wtr.write_record(["index", "op", "ref_id", "id", "status", "message"])
+        .map_err(|e| format!("Failed to write CSV header: {}", e))?;
+
+    for (index, operation) in operations.iter().enumerate() {
+        let (id, status, message) = match responses.get(index) {
+            Some(response) => {
+                let body: Value = serde_json::from_str(&response.body).unwrap_or(Value::Null);
+                if (200..300).contains(&response.code) {
+                    succeeded += 1;
+                    let id = body.get("id").map(|id| id.to_string()).unwrap_or_default();
+                    let title = body
+                        .pointer("/fields/System.Title")
+                        .and_then(|title| title.as_str())
+                        .unwrap_or_default()
+                        .to_string();
+                    (id, response.code.to_string(), title)
+                } else {
+                    (
+                        String::new(),
+                        response.code.to_string(),
+                        response_error_message(&body, &response.body),
+                    )
+                }
+            }
+            None => (
+                String::new(),
+                String::new(),
+                "No response (not executed)".to_string(),
+            ),
+        };
+        wtr.write_record([
+            &index.to_string(),
+            &operation.op.to_lowercase(),
+            &operation.id.to_string(),
+            &id,
+            &status,
+            &sanitize_csv_value(&message),
+        ])
+        .map_err(|e| format!("Failed to write CSV row: {}", e))?;
+    }
+
+    wtr.flush()
+        .map_err(|e| format!("Failed to flush CSV writer: {}", e))?;
+
+    let csv_bytes = wtr
+        .into_inner()
+        .map_err(|e| format!("Failed to get CSV bytes: {}", e))?;
+    let csv = String::from_utf8(csv_bytes)
+        .map_err(|e| format!("Failed to convert CSV to string: {}", e))?;
+
+    Ok(format!(
+        "succeeded={},failed={}\n{}",
+        succeeded,
+        operations.len() - succeeded,
+        csv
+    ))
+}
+
+#[mcp_tool(
+    name = "azdo_batch_work_items",
+    description = "Create and update many work items in one call, with temporary negative ids for parent/child wiring. Operations run in order and are not transactional; returns a per-operation report",
+    read_only = false
+)]
+pub async fn batch_work_items(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    args: BatchWorkItemsArgs,
+) -> Result<CallToolResult, McpError> {
+    let format = args.format.to_lowercase();
+    if format != "markdown" && format != "html" {
+        return Err(invalid_params(
+            "Invalid format: must be \"markdown\" or \"html\"",
+        ));
+    }
+
+    log::info!(
+        "Tool invoked: azdo_batch_work_items(operations={}, format={})",
+        args.operations.len(),
+        format
+    );
+
+    let requests = build_batch_requests(&args.project, &args.operations, format == "markdown")?;
+
+    let responses = client
+        .batch_work_items(&args.organization, requests)
+        .await
+        .map_err(azure_error_to_mcp)?;
+
+    let report = batch_report(&args.operations, &responses).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert to CSV: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(tool_text_success(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn operations(json: Value) -> Vec<BatchWorkItemOperation> {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_build_batch_requests_wires_temporary_parent_ids() {
+        let ops = operations(serde_json::json!([
+            {"op": "create", "id": -1, "work_item_type": "Feature", "title": "Checkout", "description": "**Spec**"},
+            {"op": "create", "id": -2, "work_item_type": "User Story", "title": "Pay by card", "parent_id": -1},
+            {"op": "update", "id": 7, "state": "Active", "parent_id": -1}
+        ]));
+        let requests = build_batch_requests("My Project", &ops, true).unwrap();
+        let json = serde_json::to_value(&requests).unwrap();
+
+        assert_eq!(
+            json[0]["uri"],
+            "/My%20Project/_apis/wit/workitems/$Feature?api-version=7.1"
+        );
+        assert_eq!(json[0]["method"], "PATCH");
+        assert_eq!(
+            json[0]["headers"]["Content-Type"],
+            "application/json-patch+json"
+        );
+        assert_eq!(
+            json[0]["body"][0],
+            serde_json::json!({"op": "add", "path": "/id", "value": "-1"})
+        );
+        assert!(
+            json[0]["body"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|op| op["path"] == "/multilineFieldsFormat/System.Description")
+        );
+        let relation = json[1]["body"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|op| op["path"] == "/relations/-")
+            .unwrap();
+        assert_eq!(
+            relation["value"]["rel"],
+            "System.LinkTypes.Hierarchy-Reverse"
+        );
+        assert!(
+            relation["value"]["url"]
+                .as_str()
+                .unwrap()
+                .ends_with("/workitems/-1")
+        );
+        assert_eq!(
+            json[2]["uri"],
+            "/My%20Project/_apis/wit/workitems/7?api-version=7.1"
+        );
+    }
+
+    #[test]
+    fn test_build_batch_requests_rejects_invalid_operations() {
+        let cases = vec![
+            serde_json::json!([]),
+            serde_json::json!([{"op": "create", "id": 1, "work_item_type": "Task", "title": "t"}]),
+            serde_json::json!([{"op": "create", "id": -1, "title": "t"}]),
+            serde_json::json!([{"op": "create", "id": -1, "work_item_type": "Task"}]),
+            serde_json::json!([
+                {"op": "create", "id": -1, "work_item_type": "Task", "title": "a"},
+                {"op": "create", "id": -1, "work_item_type": "Task", "title": "b"}
+            ]),
+            serde_json::json!([
+                {"op": "create", "id": -2, "work_item_type": "Task", "title": "child", "parent_id": -1},
+                {"op": "create", "id": -1, "work_item_type": "Feature", "title": "parent"}
+            ]),
+            serde_json::json!([{"op": "update", "id": -1, "title": "t"}]),
+            serde_json::json!([{"op": "update", "id": 5}]),
+            serde_json::json!([{"op": "delete", "id": 5}]),
+        ];
+        for case in cases {
+            let error = build_batch_requests("proj", &operations(case.clone()), true)
+                .err()
+                .unwrap_or_else(|| panic!("case {case} should be rejected"));
+            assert_eq!(error.code, ErrorCode(-32602), "case {case}");
+        }
+    }
+
+    #[test]
+    fn test_batch_report_lists_each_operation() {
+        let ops = operations(serde_json::json!([
+            {"op": "create", "id": -1, "work_item_type": "Feature", "title": "Checkout"},
+            {"op": "create", "id": -2, "work_item_type": "Task", "title": "Bad"},
+            {"op": "update", "id": 7, "state": "Active"}
+        ]));
+        let responses = vec![
+            WorkItemBatchResponse {
+                code: 200,
+                body: r#"{"id":101,"fields":{"System.Title":"Checkout"}}"#.to_string(),
+            },
+            WorkItemBatchResponse {
+                code: 400,
+                body: r#"{"message":"TF401320: Rule Error for field Area Path"}"#.to_string(),
+            },
+        ];
+        let report = batch_report(&ops, &responses).unwrap();
+        assert!(report.starts_with("succeeded=1,failed=2\n"), "{report}");
+        assert!(report.contains("0,create,-1,101,200,Checkout"), "{report}");
+        assert!(
+            report.contains("1,create,-2,,400,TF401320: Rule Error for field Area Path"),
+            "{report}"
+        );
+        assert!(
+            report.contains("2,update,7,,,No response (not executed)"),
+            "{report}"
+        );
+    }
+}
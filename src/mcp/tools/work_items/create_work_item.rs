@@ -1,7 +1,10 @@
 use crate::azure::api_trait::AzureDevOpsApi;
+use crate::azure::models::WorkItemRelation;
+use crate::azure::work_items::work_item_url;
 use crate::compact_llm;
 use crate::mcp::tools::support::{
-    default_text_format, deserialize_non_empty_string, simplify_work_item_json, tool_text_success,
+    azure_error_to_mcp, azure_error_with_context, default_text_format,
+    deserialize_non_empty_string, simplify_work_item_json, tool_text_success,
 };
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
@@ -10,6 +13,7 @@ use rmcp::{
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
+use std::collections::HashMap;
 
 #[derive(Deserialize, JsonSchema)]
 pub struct CreateWorkItemArgs {
@@ -126,7 +130,11 @@ pub struct CreateWorkItemArgs {
     pub fields: Option<String>,
 }
 
-#[mcp_tool(name = "azdo_create_work_item", description = "Create work item")]
+#[mcp_tool(
+    name = "azdo_create_work_item",
+    description = "Create work item",
+    read_only = false
+)]
 pub async fn create_work_item(
     client: &(dyn AzureDevOpsApi + Send + Sync),
     args: CreateWorkItemArgs,
@@ -323,11 +331,7 @@ pub async fn create_work_item(
             &multiline_formats,
         )
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(azure_error_to_mcp)?;
 
     // If parent_id is provided, create parent-child link
     if let Some(parent_id) = args.parent_id {
@@ -336,20 +340,15 @@ pub async fn create_work_item(
             work_item.id,
             parent_id
         );
+        let relation = WorkItemRelation {
+            rel: "System.LinkTypes.Hierarchy-Reverse".to_string(),
+            url: work_item_url(parent_id),
+            attributes: HashMap::new(),
+        };
         client
-            .link_work_items(
-                &args.organization,
-                &args.project,
-                work_item.id,
-                parent_id,
-                "System.LinkTypes.Hierarchy-Reverse",
-            )
+            .link_work_items(&args.organization, &args.project, work_item.id, relation)
             .await
-            .map_err(|e| McpError {
-                code: ErrorCode(-32000),
-                message: format!("Failed to create parent link: {}", e).into(),
-                data: None,
-            })?;
+            .map_err(|e| azure_error_with_context("Failed to create parent link", e))?;
     }
 
     let mut json_value = serde_json::to_value(&work_item).map_err(|e| McpError {
@@ -0,0 +1,182 @@
+use crate::azure::api_trait::AzureDevOpsApi;
+use crate::mcp::tools::support::{
+    UNTRUSTED_CONTENT_WARNING, azure_error_to_mcp, deserialize_non_empty_string, tool_text_success,
+};
+use base64::Engine;
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, ContentBlock, ResourceContents},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+/// Largest attachment downloaded; larger ones are refused before they are
+/// buffered, as the whole content ends up in the model's context (10 MB)
+const MAX_DOWNLOAD_SIZE: u64 = 10 * 1024 * 1024;
+/// Text attachments are returned inline up to this many bytes
+const MAX_INLINE_TEXT_LENGTH: usize = 64 * 1024;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GetAttachmentArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Attachment ID (as returned by azdo_list_attachments)
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub attachment_id: String,
+    /// Attachment file name, used to detect the content type (optional)
+    #[serde(default)]
+    pub file_name: Option<String>,
+}
+
+/// Guesses the MIME type of an attachment from its file name extension.
+fn mime_type_from_file_name(file_name: Option<&str>) -> &'static str {
+    let extension = file_name
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, ext)| ext.to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "html" | "htm" => "text/html",
+        "csv" => "text/csv",
+        "md" => "text/markdown",
+        "txt" | "log" => "text/plain",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Returns the content as text when it is valid UTF-8 without NUL bytes,
+/// which is a reliable enough heuristic to tell logs and source files apart
+/// from images and archives.
+fn content_as_text(content: &[u8]) -> Option<&str> {
+    if content.contains(&0) {
+        return None;
+    }
+    std::str::from_utf8(content).ok()
+}
+
+/// The first `MAX_INLINE_TEXT_LENGTH` bytes of `text`, cut at a character
+/// boundary, with a note telling how much was left out
+fn truncate_text(text: &str) -> String {
+    if text.len() <= MAX_INLINE_TEXT_LENGTH {
+        return text.to_string();
+    }
+    let mut end = MAX_INLINE_TEXT_LENGTH;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!(
+        "{}\n[truncated: showing the first {} of {} bytes]",
+        &text[..end],
+        end,
+        text.len()
+    )
+}
+
+#[mcp_tool(
+    name = "azdo_get_attachment",
+    description = "Download a work item attachment",
+    read_only = true
+)]
+pub async fn get_attachment(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    args: GetAttachmentArgs,
+) -> Result<CallToolResult, McpError> {
+    log::info!(
+        "Tool invoked: azdo_get_attachment(attachment_id={}, file_name={:?})",
+        args.attachment_id,
+        args.file_name
+    );
+
+    let content = client
+        .get_attachment(
+            &args.organization,
+            &args.project,
+            &args.attachment_id,
+            MAX_DOWNLOAD_SIZE,
+        )
+        .await
+        .map_err(azure_error_to_mcp)?;
+
+    if let Some(text) = content_as_text(&content) {
+        return Ok(tool_text_success(truncate_text(text)));
+    }
+
+    let mime_type = mime_type_from_file_name(args.file_name.as_deref());
+    let uri = format!(
+        "azdo://{}/{}/attachments/{}",
+        urlencoding::encode(&args.organization),
+        urlencoding::encode(&args.project),
+        urlencoding::encode(&args.attachment_id)
+    );
+    let blob = base64::engine::general_purpose::STANDARD.encode(&content);
+
+    Ok(CallToolResult::success(vec![
+        ContentBlock::text(format!(
+            "{}\nBinary attachment: mime_type={}, size={}",
+            UNTRUSTED_CONTENT_WARNING,
+            mime_type,
+            content.len()
+        )),
+        ContentBlock::resource(ResourceContents::blob(blob, uri).with_mime_type(mime_type)),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mime_type_from_file_name() {
+        let cases = vec![
+            (Some("screenshot.PNG"), "image/png"),
+            (Some("photo.jpeg"), "image/jpeg"),
+            (Some("report.pdf"), "application/pdf"),
+            (Some("build.log"), "text/plain"),
+            (Some("archive.tar.gz"), "application/gzip"),
+            (Some("no_extension"), "application/octet-stream"),
+            (None, "application/octet-stream"),
+        ];
+        for (file_name, expected) in cases {
+            assert_eq!(
+                mime_type_from_file_name(file_name),
+                expected,
+                "file name {file_name:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_content_as_text() {
+        assert_eq!(content_as_text(b"plain log\n"), Some("plain log\n"));
+        assert_eq!(content_as_text(&[0x89, b'P', b'N', b'G', 0, 0]), None);
+        assert_eq!(content_as_text(&[0xff, 0xfe, 0xfd]), None);
+    }
+
+    #[test]
+    fn test_truncate_text() {
+        assert_eq!(truncate_text("short log"), "short log");
+        let long = "é".repeat(MAX_INLINE_TEXT_LENGTH);
+        let truncated = truncate_text(&long);
+        assert!(truncated.starts_with(&"é".repeat(MAX_INLINE_TEXT_LENGTH / 2)));
+        assert!(truncated.ends_with(&format!(
+            "[truncated: showing the first {} of {} bytes]",
+            MAX_INLINE_TEXT_LENGTH,
+            long.len()
+        )));
+    }
+}
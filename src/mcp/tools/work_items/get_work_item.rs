@@ -1,6 +1,7 @@
 use crate::azure::api_trait::AzureDevOpsApi;
 use crate::mcp::tools::support::{
-    deserialize_non_empty_string, simplify_work_item_json, tool_text_success, work_items_to_csv,
+    azure_error_to_mcp, deserialize_non_empty_string, simplify_work_item_json, tool_text_success,
+    work_items_to_csv,
 };
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
@@ -25,7 +26,11 @@ pub struct GetWorkItemArgs {
     pub include_latest_n_comments: Option<i32>,
 }
 
-#[mcp_tool(name = "azdo_get_work_item", description = "Get work item by ID")]
+#[mcp_tool(
+    name = "azdo_get_work_item",
+    description = "Get work item by ID",
+    read_only = true
+)]
 pub async fn get_work_item(
     client: &(dyn AzureDevOpsApi + Send + Sync),
     args: GetWorkItemArgs,
@@ -39,11 +44,7 @@ pub async fn get_work_item(
             args.include_latest_n_comments,
         )
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(azure_error_to_mcp)?;
 
     match work_item {
         Some(work_item) => {
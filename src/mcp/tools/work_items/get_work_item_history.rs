@@ -0,0 +1,275 @@
+use crate::azure::api_trait::AzureDevOpsApi;
+use crate::azure::models::{WorkItemRelation, WorkItemUpdate};
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, sanitize_csv_value, simplify_field_name,
+    simplify_identity_value, tool_text_success,
+};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use serde_json::Value;
+
+/// Longer old/new values (typically HTML descriptions) are cut to keep the
+/// history compact; the current value is available through azdo_get_work_item.
+const MAX_HISTORY_VALUE_LENGTH: usize = 200;
+
+/// Bookkeeping fields that change on every revision and carry no information
+/// beyond the revision's own date and author.
+const NOISE_FIELDS: &[&str] = &[
+    "System.Rev",
+    "System.ChangedDate",
+    "System.ChangedBy",
+    "System.AuthorizedDate",
+    "System.AuthorizedAs",
+    "System.RevisedDate",
+    "System.PersonId",
+    "System.Watermark",
+    "System.CommentCount",
+];
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GetWorkItemHistoryArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Work item ID
+    pub id: u32,
+    /// Only report changes to these fields (e.g., ["State", "AssignedTo"] or reference names like "System.State")
+    #[serde(default)]
+    pub fields: Vec<String>,
+}
+
+fn value_to_string(value: Option<&Value>) -> String {
+    let text = match value.cloned().map(simplify_identity_value) {
+        Some(Value::String(s)) => s,
+        Some(Value::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    };
+    if text.chars().count() > MAX_HISTORY_VALUE_LENGTH {
+        let truncated: String = text.chars().take(MAX_HISTORY_VALUE_LENGTH).collect();
+        format!("{}...", truncated)
+    } else {
+        text
+    }
+}
+
+fn relations_to_string(relations: &[WorkItemRelation]) -> String {
+    relations
+        .iter()
+        .map(|relation| {
+            let rel = relation
+                .rel
+                .strip_prefix("System.LinkTypes.")
+                .unwrap_or(&relation.rel);
+            let target = relation.url.rsplit('/').next().unwrap_or(&relation.url);
+            format!("{} {}", rel, target)
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn field_matches(reference_name: &str, filter: &[String]) -> bool {
+    let short_name = simplify_field_name(reference_name);
+    filter.iter().any(|wanted| {
+        wanted.eq_ignore_ascii_case(reference_name) || wanted.eq_ignore_ascii_case(&short_name)
+    })
+}
+
+/// Converts work item updates to CSV, one row per changed field.
+/// Columns: rev, date, by, field, old, new
+fn updates_to_csv(updates: &[WorkItemUpdate], filter: &[String]) -> Result<String, String> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    let mut rows = 0;
+
+    wtr.write_record(["rev", "date", "by", "field", "old", "new"])
+        .map_err(|e| format!("Failed to write CSV header: {}", e))?;
+
+    for update in updates {
+        let rev = update.rev.map(|rev| rev.to_string()).unwrap_or_default();
+        let by = sanitize_csv_value(&value_to_string(update.revised_by.as_ref()));
+        // revisedDate is when the revision was superseded; the change itself
+        // happened at the new ChangedDate, when present.
+        let date = update
+            .fields
+            .as_ref()
+            .and_then(|fields| fields.get("System.ChangedDate"))
+            .and_then(|change| change.new_value.as_ref())
+            .and_then(|value| value.as_str())
+            .or(update.revised_date.as_deref())
+            .unwrap_or_default()
+            .to_string();
+
+        let mut changes: Vec<(String, String, String)> = Vec::new();
+        if let Some(fields) = &update.fields {
+            let mut names: Vec<&String> = fields.keys().collect();
+            names.sort();
+            for name in names {
+                let include = if filter.is_empty() {
+                    !NOISE_FIELDS.contains(&name.as_str())
+                } else {
+                    field_matches(name, filter)
+                };
+                if !include {
+                    continue;
+                }
+                let change = &fields[name];
+                changes.push((
+                    simplify_field_name(name),
+                    value_to_string(change.old_value.as_ref()),
+                    value_to_string(change.new_value.as_ref()),
+                ));
+            }
+        }
+        if let Some(relations) = &update.relations
+            && (filter.is_empty() || field_matches("Relations", filter))
+            && (!relations.added.is_empty() || !relations.removed.is_empty())
+        {
+            changes.push((
+                "Relations".to_string(),
+                relations_to_string(&relations.removed),
+                relations_to_string(&relations.added),
+            ));
+        }
+
+        for (field, old, new) in changes {
+            wtr.write_record([
+                rev.as_str(),
+                &date,
+                &by,
+                &sanitize_csv_value(&field),
+                &sanitize_csv_value(&old),
+                &sanitize_csv_value(&new),
+            ])
+            .map_err(|e| format!("Failed to write CSV row: {}", e))?;
+            rows += 1;
+        }
+    }
+
+    if rows == 0 {
+        return Ok(String::new());
+    }
+
+    wtr.flush()
+        .map_err(|e| format!("Failed to flush CSV writer: {}", e))?;
+
+    let csv_bytes = wtr
+        .into_inner()
+        .map_err(|e| format!("Failed to get CSV bytes: {}", e))?;
+
+    String::from_utf8(csv_bytes).map_err(|e| format!("Failed to convert CSV to string: {}", e))
+}
+
+#[mcp_tool(
+    name = "azdo_get_work_item_history",
+    description = "Get work item revision history (changed fields, old and new values)",
+    read_only = true
+)]
+pub async fn get_work_item_history(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    args: GetWorkItemHistoryArgs,
+) -> Result<CallToolResult, McpError> {
+    log::info!(
+        "Tool invoked: azdo_get_work_item_history(id={}, fields={:?})",
+        args.id,
+        args.fields
+    );
+
+    let updates = client
+        .get_work_item_updates(&args.organization, &args.project, args.id)
+        .await
+        .map_err(azure_error_to_mcp)?;
+
+    let csv_output = updates_to_csv(&updates, &args.fields).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert to CSV: {}", e).into(),
+        data: None,
+    })?;
+
+    if csv_output.is_empty() {
+        return Ok(tool_text_success("No changes found"));
+    }
+
+    Ok(tool_text_success(csv_output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(json: Value) -> WorkItemUpdate {
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn sample_updates() -> Vec<WorkItemUpdate> {
+        vec![
+            update(serde_json::json!({
+                "id": 1,
+                "rev": 1,
+                "revisedBy": {"displayName": "Alice", "uniqueName": "alice@example.com"},
+                "revisedDate": "2025-01-02T00:00:00Z",
+                "fields": {
+                    "System.State": {"newValue": "New"},
+                    "System.Rev": {"newValue": 1},
+                    "System.ChangedDate": {"newValue": "2025-01-01T09:00:00Z"}
+                }
+            })),
+            update(serde_json::json!({
+                "id": 2,
+                "rev": 2,
+                "revisedBy": {"displayName": "Bob", "uniqueName": "bob@example.com"},
+                "revisedDate": "9999-01-01T00:00:00Z",
+                "fields": {
+                    "System.State": {"oldValue": "Resolved", "newValue": "Active"},
+                    "Microsoft.VSTS.Scheduling.StoryPoints": {"oldValue": 3.0, "newValue": 5.0},
+                    "System.ChangedDate": {"oldValue": "2025-01-01T09:00:00Z", "newValue": "2025-01-05T10:30:00Z"}
+                },
+                "relations": {
+                    "added": [{"rel": "System.LinkTypes.Hierarchy-Reverse", "url": "https://dev.azure.com/org/_apis/wit/workItems/7"}]
+                }
+            })),
+        ]
+    }
+
+    #[test]
+    fn test_updates_to_csv_lists_changes_with_short_names() {
+        let csv = updates_to_csv(&sample_updates(), &[]).unwrap();
+        assert!(csv.starts_with("rev,date,by,field,old,new\n"));
+        assert!(csv.contains("1,2025-01-01T09:00:00Z,Alice <alice@example.com>,State,,New"));
+        assert!(csv.contains("2,2025-01-05T10:30:00Z,Bob <bob@example.com>,State,Resolved,Active"));
+        assert!(csv.contains("StoryPoints,3.0,5.0"));
+        assert!(csv.contains("Relations,,Hierarchy-Reverse 7"));
+        assert!(!csv.contains(",Rev,"), "noise fields must be skipped");
+        assert!(!csv.contains("ChangedDate"), "noise fields must be skipped");
+    }
+
+    #[test]
+    fn test_updates_to_csv_filters_fields_by_short_or_reference_name() {
+        for filter in ["state", "System.State"] {
+            let csv = updates_to_csv(&sample_updates(), &[filter.to_string()]).unwrap();
+            assert_eq!(csv.lines().count(), 3, "filter '{filter}': {csv}");
+            assert!(!csv.contains("StoryPoints"));
+            assert!(!csv.contains("Relations"));
+        }
+    }
+
+    #[test]
+    fn test_updates_to_csv_empty_when_nothing_matches() {
+        let csv = updates_to_csv(&sample_updates(), &["Priority".to_string()]).unwrap();
+        assert!(csv.is_empty());
+    }
+
+    #[test]
+    fn test_value_to_string_truncates_long_values() {
+        let long = "x".repeat(MAX_HISTORY_VALUE_LENGTH + 50);
+        let value = value_to_string(Some(&Value::String(long)));
+        assert_eq!(value.len(), MAX_HISTORY_VALUE_LENGTH + 3);
+        assert!(value.ends_with("..."));
+    }
+}
@@ -0,0 +1,314 @@
+use crate::azure::api_trait::AzureDevOpsApi;
+use crate::azure::models::WorkItemWithRelations;
+use crate::azure::work_items::{HIERARCHY_FORWARD_REL, work_item_id_from_url};
+use crate::mcp::tools::support::{
+    MAX_QUERY_PAGE_SIZE, REMAINING_WORK_FIELD, STORY_POINTS_FIELDS, azure_error_to_mcp,
+    deserialize_non_empty_string, format_number, invalid_params, tool_text_success,
+};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::CallToolResult,
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// Levels walked below the root when `depth` is not specified: enough for
+/// Epic > Feature > Story > Task
+const DEFAULT_TREE_DEPTH: u32 = 3;
+const MAX_TREE_DEPTH: u32 = 10;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GetWorkItemTreeArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Root work item ID (e.g., an epic or feature)
+    pub id: u32,
+    /// Levels of children to walk below the root (default: 3, max: 10)
+    #[serde(default)]
+    pub depth: Option<u32>,
+}
+
+/// The work items reached from the root, walked level by level
+struct WorkItemTree<'a> {
+    project: &'a str,
+    items: HashMap<u32, WorkItemWithRelations>,
+    /// Parent through which each walked item was first reached
+    parents: HashMap<u32, u32>,
+}
+
+/// Story points and remaining work of a node or of its descendants
+#[derive(Default, Clone, Copy)]
+struct Rollup {
+    story_points: Option<f64>,
+    remaining_work: Option<f64>,
+}
+
+impl Rollup {
+    fn of(item: &WorkItemWithRelations) -> Self {
+        Rollup {
+            story_points: STORY_POINTS_FIELDS
+                .iter()
+                .find_map(|field| item.fields.get(*field).and_then(Value::as_f64)),
+            remaining_work: item
+                .fields
+                .get(REMAINING_WORK_FIELD)
+                .and_then(Value::as_f64),
+        }
+    }
+
+    fn add(&mut self, other: Rollup) {
+        fn sum(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(a + b),
+                (a, b) => a.or(b),
+            }
+        }
+        self.story_points = sum(self.story_points, other.story_points);
+        self.remaining_work = sum(self.remaining_work, other.remaining_work);
+    }
+}
+
+/// Counts reported in the summary line
+#[derive(Default)]
+struct TreeStats {
+    cycles: usize,
+    outside_project: usize,
+    not_accessible: usize,
+}
+
+fn field_str<'a>(item: &'a WorkItemWithRelations, name: &str) -> Option<&'a str> {
+    item.fields.get(name).and_then(Value::as_str)
+}
+
+/// IDs of the children of `item`, in link order
+fn child_ids(item: &WorkItemWithRelations) -> Vec<u32> {
+    item.relations
+        .iter()
+        .filter(|relation| relation.rel == HIERARCHY_FORWARD_REL)
+        .filter_map(|relation| work_item_id_from_url(&relation.url))
+        .collect()
+}
+
+impl WorkItemTree<'_> {
+    /// The project of `item` when it is not the caller's project; its
+    /// children are not walked
+    fn other_project(&self, item: &WorkItemWithRelations) -> Option<String> {
+        field_str(item, "System.TeamProject")
+            .filter(|project| !project.eq_ignore_ascii_case(self.project))
+            .map(str::to_string)
+    }
+
+    /// Renders `id` and its walked descendants, one line per work item
+    /// indented two spaces per level, and returns its rollup: for each
+    /// estimate, the sum of its children's rollups, or its own value when
+    /// no child has one. An own value replaced by the children's sum is
+    /// reported separately rather than added to it.
+    fn render(
+        &self,
+        id: u32,
+        level: usize,
+        ancestors: &mut Vec<u32>,
+        stats: &mut TreeStats,
+        lines: &mut Vec<String>,
+    ) -> Rollup {
+        let indent = "  ".repeat(level);
+        let Some(item) = self.items.get(&id) else {
+            stats.not_accessible += 1;
+            lines.push(format!("{}{} [not found or not accessible]", indent, id));
+            return Rollup::default();
+        };
+        if let Some(project) = self.other_project(item) {
+            stats.outside_project += 1;
+            lines.push(format!(
+                "{}{}{} [other project: {}, not rolled up]",
+                indent,
+                id,
+                describe(item),
+                project
+            ));
+            return Rollup::default();
+        }
+
+        let line_index = lines.len();
+        lines.push(String::new());
+        let own = Rollup::of(item);
+        let mut children = Rollup::default();
+        let mut not_expanded = 0;
+        ancestors.push(id);
+        for child in child_ids(item) {
+            if ancestors.contains(&child) {
+                stats.cycles += 1;
+                lines.push(format!("{}  {} [cycle]", indent, child));
+            } else if self.parents.get(&child) == Some(&id) {
+                children.add(self.render(child, level + 1, ancestors, stats, lines));
+            } else if self.parents.contains_key(&child) {
+                lines.push(format!("{}  {} [see above]", indent, child));
+            } else {
+                not_expanded += 1;
+            }
+        }
+        ancestors.pop();
+        let rollup = Rollup {
+            story_points: children.story_points.or(own.story_points),
+            remaining_work: children.remaining_work.or(own.remaining_work),
+        };
+
+        let mut details = Vec::new();
+        if let Some(state) = field_str(item, "System.State") {
+            details.push(format!("state={}", state));
+        }
+        if let Some(assignee) = item
+            .fields
+            .get("System.AssignedTo")
+            .and_then(|identity| identity.get("displayName"))
+            .and_then(Value::as_str)
+        {
+            details.push(format!("assigned_to={}", assignee));
+        }
+        if let Some(story_points) = rollup.story_points {
+            details.push(format!("story_points={}", format_number(story_points)));
+        }
+        if let (Some(_), Some(own_story_points)) = (children.story_points, own.story_points) {
+            details.push(format!(
+                "own_story_points={}",
+                format_number(own_story_points)
+            ));
+        }
+        if let Some(remaining_work) = rollup.remaining_work {
+            details.push(format!("remaining_work={}", format_number(remaining_work)));
+        }
+        if let (Some(_), Some(own_remaining_work)) = (children.remaining_work, own.remaining_work) {
+            details.push(format!(
+                "own_remaining_work={}",
+                format_number(own_remaining_work)
+            ));
+        }
+        if not_expanded > 0 {
+            details.push(format!("children_not_expanded={}", not_expanded));
+        }
+        let mut line = format!("{}{}{}", indent, id, describe(item));
+        if !details.is_empty() {
+            line.push_str(&format!(" ({})", details.join(",")));
+        }
+        lines[line_index] = line;
+        rollup
+    }
+}
+
+/// ` Type: Title` of a work item
+fn describe(item: &WorkItemWithRelations) -> String {
+    let title = field_str(item, "System.Title")
+        .unwrap_or_default()
+        .replace(['\n', '\r'], " ");
+    match field_str(item, "System.WorkItemType") {
+        Some(work_item_type) => format!(" {}: {}", work_item_type, title),
+        None => format!(" {}", title),
+    }
+}
+
+#[mcp_tool(
+    name = "azdo_get_work_item_tree",
+    description = "Get the tree of child work items under a work item (e.g., an epic), with state, assignee and rolled-up story points and remaining work",
+    read_only = true
+)]
+pub async fn get_work_item_tree(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    args: GetWorkItemTreeArgs,
+) -> Result<CallToolResult, McpError> {
+    log::info!(
+        "Tool invoked: azdo_get_work_item_tree(id={}, depth={:?})",
+        args.id,
+        args.depth
+    );
+    let depth = args.depth.unwrap_or(DEFAULT_TREE_DEPTH);
+    if depth > MAX_TREE_DEPTH {
+        return Err(invalid_params(format!(
+            "depth must be at most {}",
+            MAX_TREE_DEPTH
+        )));
+    }
+
+    let mut tree = WorkItemTree {
+        project: &args.project,
+        items: HashMap::new(),
+        parents: HashMap::new(),
+    };
+    let mut walked = HashSet::from([args.id]);
+    let mut truncated = false;
+    let mut level = vec![args.id];
+    for level_depth in 0..=depth {
+        if level.is_empty() {
+            break;
+        }
+        // One batched request per level of the tree
+        let fetched = client
+            .get_work_items_with_relations(&args.organization, &args.project, &level)
+            .await
+            .map_err(azure_error_to_mcp)?;
+        tree.items
+            .extend(fetched.into_iter().map(|item| (item.id, item)));
+        if level_depth == depth {
+            break;
+        }
+
+        let mut next = Vec::new();
+        for parent in &level {
+            let Some(item) = tree.items.get(parent) else {
+                continue;
+            };
+            if tree.other_project(item).is_some() {
+                continue;
+            }
+            for child in child_ids(item) {
+                if walked.contains(&child) {
+                    continue;
+                }
+                if walked.len() >= MAX_QUERY_PAGE_SIZE {
+                    truncated = true;
+                    break;
+                }
+                walked.insert(child);
+                tree.parents.insert(child, *parent);
+                next.push(child);
+            }
+        }
+        level = next;
+    }
+
+    if !tree.items.contains_key(&args.id) {
+        return Ok(tool_text_success("Work item not found"));
+    }
+
+    let mut stats = TreeStats::default();
+    let mut lines = Vec::new();
+    tree.render(args.id, 0, &mut Vec::new(), &mut stats, &mut lines);
+
+    let mut summary = format!(
+        "root={},depth={},items={},truncated={}",
+        args.id,
+        depth,
+        tree.items.len(),
+        truncated
+    );
+    for (name, count) in [
+        ("cycles", stats.cycles),
+        ("outside_project", stats.outside_project),
+        ("not_accessible", stats.not_accessible),
+    ] {
+        if count > 0 {
+            summary.push_str(&format!(",{}={}", name, count));
+        }
+    }
+    Ok(tool_text_success(format!(
+        "{}\n{}",
+        summary,
+        lines.join("\n")
+    )))
+}
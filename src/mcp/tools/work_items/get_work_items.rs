@@ -1,6 +1,7 @@
 use crate::azure::api_trait::AzureDevOpsApi;
 use crate::mcp::tools::support::{
-    deserialize_non_empty_string, simplify_work_item_json, tool_text_success, work_items_to_csv,
+    MAX_QUERY_PAGE_SIZE, azure_error_to_mcp, deserialize_non_empty_string, invalid_params,
+    simplify_work_item_json, tool_text_success, work_items_to_csv,
 };
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
@@ -18,7 +19,7 @@ pub struct GetWorkItemsArgs {
     /// AzDO project name
     #[serde(deserialize_with = "deserialize_non_empty_string")]
     pub project: String,
-    /// Work item IDs (comma-separated or array)
+    /// Work item IDs (comma-separated or array, at most 1000)
     pub ids: Vec<i64>,
     /// Include the latest N comments (optional). Set to -1 for all comments.
     #[serde(default)]
@@ -27,7 +28,8 @@ pub struct GetWorkItemsArgs {
 
 #[mcp_tool(
     name = "azdo_get_work_items",
-    description = "Get multiple work items by IDs"
+    description = "Get multiple work items by IDs",
+    read_only = true
 )]
 pub async fn get_work_items(
     client: &(dyn AzureDevOpsApi + Send + Sync),
@@ -39,6 +41,14 @@ pub async fn get_work_items(
         return Ok(tool_text_success("No work items found"));
     }
 
+    if args.ids.len() > MAX_QUERY_PAGE_SIZE {
+        return Err(invalid_params(format!(
+            "Too many IDs ({}): at most {} work items can be fetched per call",
+            args.ids.len(),
+            MAX_QUERY_PAGE_SIZE
+        )));
+    }
+
     let ids: Vec<u32> = args.ids.iter().map(|&id| id as u32).collect();
     let work_items = client
         .get_work_items(
@@ -48,11 +58,7 @@ pub async fn get_work_items(
             args.include_latest_n_comments,
         )
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(azure_error_to_mcp)?;
 
     if work_items.is_empty() {
         return Ok(tool_text_success("No work items found"));
@@ -1,6 +1,11 @@
 use crate::azure::api_trait::AzureDevOpsApi;
+use crate::azure::artifact_links::{ARTIFACT_LINK_REL, HYPERLINK_REL};
+use crate::azure::models::WorkItemRelation;
 use crate::compact_llm;
-use crate::mcp::tools::support::{deserialize_non_empty_string, tool_text_success};
+use crate::mcp::tools::support::{
+    LinkTarget, LinkTargetArgs, azure_error_to_mcp, deserialize_non_empty_string, invalid_params,
+    resolve_link_target, resolve_work_item_link_type, tool_text_success,
+};
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
@@ -8,6 +13,7 @@ use rmcp::{
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
+use std::collections::HashMap;
 
 #[derive(Deserialize, JsonSchema)]
 pub struct LinkWorkItemsArgs {
@@ -19,49 +25,100 @@ pub struct LinkWorkItemsArgs {
     pub project: String,
     /// Source work item ID
     pub source_id: u32,
-    /// Target work item ID
-    pub target_id: u32,
-    /// Link type: "Parent", "Child", "Related", "Duplicate", "Dependency"
-    #[serde(deserialize_with = "deserialize_non_empty_string")]
-    pub link_type: String,
+    /// Target work item ID (requires link_type)
+    #[serde(default)]
+    pub target_id: Option<u32>,
+    /// Link type name or reference name, e.g. "Parent" (target is the parent of source), "Child", "Related", "Duplicate", "Successor", "Predecessor", "Tested By" or "System.LinkTypes.Hierarchy-Reverse"
+    #[serde(default)]
+    pub link_type: Option<String>,
+    /// Hyperlink URL to link instead of a work item
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Git repository name or ID (required with commit_id or pull_request_id)
+    #[serde(default)]
+    pub repository: Option<String>,
+    /// Commit SHA to link
+    #[serde(default)]
+    pub commit_id: Option<String>,
+    /// Pull request ID to link
+    #[serde(default)]
+    pub pull_request_id: Option<u32>,
+    /// Build ID to link
+    #[serde(default)]
+    pub build_id: Option<u32>,
+    /// Optional comment stored on the link
+    #[serde(default)]
+    pub comment: Option<String>,
 }
 
-#[mcp_tool(name = "azdo_link_work_items", description = "Link work items")]
+#[mcp_tool(
+    name = "azdo_link_work_items",
+    description = "Link a work item to another work item, a hyperlink, a commit, a pull request or a build",
+    read_only = false
+)]
 pub async fn link_work_items(
     client: &(dyn AzureDevOpsApi + Send + Sync),
     args: LinkWorkItemsArgs,
 ) -> Result<CallToolResult, McpError> {
     log::info!(
-        "Tool invoked: azdo_link_work_items(source_id={}, target_id={}, link_type={})",
+        "Tool invoked: azdo_link_work_items(source_id={}, target_id={:?}, link_type={:?}, url={:?}, commit_id={:?}, pull_request_id={:?}, build_id={:?})",
         args.source_id,
         args.target_id,
-        args.link_type
+        args.link_type,
+        args.url,
+        args.commit_id,
+        args.pull_request_id,
+        args.build_id
     );
 
-    // Map friendly names to Azure DevOps link type names
-    let link_type_ref = match args.link_type.to_lowercase().as_str() {
-        "parent" => "System.LinkTypes.Hierarchy-Forward",
-        "child" => "System.LinkTypes.Hierarchy-Reverse",
-        "related" => "System.LinkTypes.Related",
-        "duplicate" => "System.LinkTypes.Duplicate-Forward",
-        "dependency" => "System.LinkTypes.Dependency-Forward",
-        _ => &args.link_type, // Use as-is if not a known friendly name
+    let target = resolve_link_target(
+        client,
+        &args.organization,
+        &args.project,
+        &LinkTargetArgs {
+            target_id: args.target_id,
+            url: args.url.as_deref(),
+            repository: args.repository.as_deref(),
+            commit_id: args.commit_id.as_deref(),
+            pull_request_id: args.pull_request_id,
+            build_id: args.build_id,
+        },
+    )
+    .await?;
+
+    let mut attributes = HashMap::new();
+    let rel = match (&target, &args.link_type) {
+        (LinkTarget::WorkItem(_), Some(link_type)) => {
+            resolve_work_item_link_type(client, &args.organization, link_type).await?
+        }
+        (LinkTarget::WorkItem(_), None) => {
+            return Err(invalid_params(
+                "link_type is required when linking work items",
+            ));
+        }
+        (_, Some(_)) => {
+            return Err(invalid_params("link_type only applies to work item links"));
+        }
+        (LinkTarget::Hyperlink(_), None) => HYPERLINK_REL.to_string(),
+        (LinkTarget::Artifact { name, .. }, None) => {
+            attributes.insert("name".to_string(), serde_json::json!(name));
+            ARTIFACT_LINK_REL.to_string()
+        }
+    };
+    if let Some(comment) = &args.comment {
+        attributes.insert("comment".to_string(), serde_json::json!(comment));
+    }
+
+    let relation = WorkItemRelation {
+        rel,
+        url: target.url(),
+        attributes,
     };
 
     let result = client
-        .link_work_items(
-            &args.organization,
-            &args.project,
-            args.source_id,
-            args.target_id,
-            link_type_ref,
-        )
+        .link_work_items(&args.organization, &args.project, args.source_id, relation)
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(azure_error_to_mcp)?;
 
     let output = compact_llm::to_compact_string(&result).map_err(|e| McpError {
         code: ErrorCode(-32000),
@@ -0,0 +1,101 @@
+use crate::azure::api_trait::AzureDevOpsApi;
+use crate::azure::attachments::{ATTACHED_FILE_REL, attachment_id_from_url};
+use crate::azure::models::WorkItemRelation;
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, sanitize_csv_value, tool_text_success,
+};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use serde_json::Value;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ListAttachmentsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Work item ID
+    pub work_item_id: u32,
+}
+
+fn attribute_to_string(relation: &WorkItemRelation, key: &str) -> String {
+    match relation.attributes.get(key) {
+        Some(Value::String(s)) => sanitize_csv_value(s),
+        Some(Value::Number(n)) => n.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Converts attachment relations to CSV format.
+/// Columns: id, name, size, date, comment
+fn attachments_to_csv(relations: &[&WorkItemRelation]) -> Result<String, String> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+
+    wtr.write_record(["id", "name", "size", "date", "comment"])
+        .map_err(|e| format!("Failed to write CSV header: {}", e))?;
+
+    for relation in relations {
+        let date = attribute_to_string(relation, "authorizedDate");
+        wtr.write_record([
+            attachment_id_from_url(&relation.url),
+            &attribute_to_string(relation, "name"),
+            &attribute_to_string(relation, "resourceSize"),
+            date.split('T').next().unwrap_or_default(),
+            &attribute_to_string(relation, "comment"),
+        ])
+        .map_err(|e| format!("Failed to write CSV row: {}", e))?;
+    }
+
+    wtr.flush()
+        .map_err(|e| format!("Failed to flush CSV writer: {}", e))?;
+
+    let csv_bytes = wtr
+        .into_inner()
+        .map_err(|e| format!("Failed to get CSV bytes: {}", e))?;
+
+    String::from_utf8(csv_bytes).map_err(|e| format!("Failed to convert CSV to string: {}", e))
+}
+
+#[mcp_tool(
+    name = "azdo_list_attachments",
+    description = "List files attached to a work item",
+    read_only = true
+)]
+pub async fn list_attachments(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    args: ListAttachmentsArgs,
+) -> Result<CallToolResult, McpError> {
+    log::info!(
+        "Tool invoked: azdo_list_attachments(work_item_id={})",
+        args.work_item_id
+    );
+
+    let relations = client
+        .get_work_item_relations(&args.organization, &args.project, args.work_item_id)
+        .await
+        .map_err(azure_error_to_mcp)?;
+
+    let attachments: Vec<&WorkItemRelation> = relations
+        .iter()
+        .filter(|relation| relation.rel == ATTACHED_FILE_REL)
+        .collect();
+
+    if attachments.is_empty() {
+        return Ok(tool_text_success("No attachments found"));
+    }
+
+    let csv_output = attachments_to_csv(&attachments).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert to CSV: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(tool_text_success(csv_output))
+}
@@ -0,0 +1,132 @@
+use crate::azure::api_trait::AzureDevOpsApi;
+use crate::azure::artifact_links::{ARTIFACT_LINK_REL, describe_artifact_uri};
+use crate::azure::attachments::{ATTACHED_FILE_REL, attachment_id_from_url};
+use crate::azure::models::WorkItemRelation;
+use crate::azure::work_items::work_item_id_from_url;
+use crate::mcp::tools::support::{
+    azure_error_to_mcp, deserialize_non_empty_string, sanitize_csv_value, tool_text_success,
+};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+use std::collections::HashMap;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ListWorkItemRelationsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Work item ID
+    pub work_item_id: u32,
+}
+
+/// Short form of a relation target: the work item ID, attachment ID,
+/// artifact description or hyperlink URL.
+fn relation_target(relation: &WorkItemRelation) -> String {
+    if relation.rel == ATTACHED_FILE_REL {
+        return attachment_id_from_url(&relation.url).to_string();
+    }
+    if relation.rel == ARTIFACT_LINK_REL {
+        return describe_artifact_uri(&relation.url);
+    }
+    match work_item_id_from_url(&relation.url) {
+        Some(id) => id.to_string(),
+        None => relation.url.clone(),
+    }
+}
+
+fn attribute_to_string(relation: &WorkItemRelation, key: &str) -> String {
+    relation
+        .attributes
+        .get(key)
+        .and_then(|value| value.as_str())
+        .map(sanitize_csv_value)
+        .unwrap_or_default()
+}
+
+/// Converts relations to CSV format.
+/// Columns: type, target, name, comment
+fn relations_to_csv(
+    relations: &[WorkItemRelation],
+    type_names: &HashMap<String, String>,
+) -> Result<String, String> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+
+    wtr.write_record(["type", "target", "name", "comment"])
+        .map_err(|e| format!("Failed to write CSV header: {}", e))?;
+
+    for relation in relations {
+        let link_type = type_names.get(&relation.rel).unwrap_or(&relation.rel);
+        wtr.write_record([
+            sanitize_csv_value(link_type),
+            sanitize_csv_value(&relation_target(relation)),
+            attribute_to_string(relation, "name"),
+            attribute_to_string(relation, "comment"),
+        ])
+        .map_err(|e| format!("Failed to write CSV row: {}", e))?;
+    }
+
+    wtr.flush()
+        .map_err(|e| format!("Failed to flush CSV writer: {}", e))?;
+
+    let csv_bytes = wtr
+        .into_inner()
+        .map_err(|e| format!("Failed to get CSV bytes: {}", e))?;
+
+    String::from_utf8(csv_bytes).map_err(|e| format!("Failed to convert CSV to string: {}", e))
+}
+
+#[mcp_tool(
+    name = "azdo_list_work_item_relations",
+    description = "List the links of a work item (work items, hyperlinks, commits, pull requests, builds, attachments)",
+    read_only = true
+)]
+pub async fn list_work_item_relations(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    args: ListWorkItemRelationsArgs,
+) -> Result<CallToolResult, McpError> {
+    log::info!(
+        "Tool invoked: azdo_list_work_item_relations(work_item_id={})",
+        args.work_item_id
+    );
+
+    let relations = client
+        .get_work_item_relations(&args.organization, &args.project, args.work_item_id)
+        .await
+        .map_err(azure_error_to_mcp)?;
+
+    if relations.is_empty() {
+        return Ok(tool_text_success("No relations found"));
+    }
+
+    // Link type names are only used for display, so fall back to reference
+    // names rather than failing the whole listing.
+    let type_names: HashMap<String, String> = match client
+        .list_work_item_relation_types(&args.organization)
+        .await
+    {
+        Ok(types) => types
+            .into_iter()
+            .map(|relation_type| (relation_type.reference_name, relation_type.name))
+            .collect(),
+        Err(e) => {
+            log::warn!("Failed to list link types: {}", e);
+            HashMap::new()
+        }
+    };
+
+    let csv_output = relations_to_csv(&relations, &type_names).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to convert to CSV: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(tool_text_success(csv_output))
+}
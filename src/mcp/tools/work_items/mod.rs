@@ -1,21 +1,37 @@
 // Work Items module
+pub mod add_attachment;
 pub mod add_comment;
+pub mod batch_work_items;
 pub mod create_work_item;
+pub mod get_attachment;
 pub mod get_work_item;
+pub mod get_work_item_history;
+pub mod get_work_item_tree;
 pub mod get_work_items;
 pub mod link_work_items;
+pub mod list_attachments;
+pub mod list_work_item_relations;
 pub mod query_work_items;
 pub mod query_work_items_by_wiql;
+pub mod unlink_work_items;
 pub mod update_comment;
 pub mod update_work_item;
 
 // Re-export the public items
+pub use add_attachment::{AddAttachmentArgs, add_attachment};
 pub use add_comment::{AddCommentArgs, add_comment};
+pub use batch_work_items::{BatchWorkItemOperation, BatchWorkItemsArgs, batch_work_items};
 pub use create_work_item::{CreateWorkItemArgs, create_work_item};
+pub use get_attachment::{GetAttachmentArgs, get_attachment};
 pub use get_work_item::{GetWorkItemArgs, get_work_item};
+pub use get_work_item_history::{GetWorkItemHistoryArgs, get_work_item_history};
+pub use get_work_item_tree::{GetWorkItemTreeArgs, get_work_item_tree};
 pub use get_work_items::{GetWorkItemsArgs, get_work_items};
 pub use link_work_items::{LinkWorkItemsArgs, link_work_items};
+pub use list_attachments::{ListAttachmentsArgs, list_attachments};
+pub use list_work_item_relations::{ListWorkItemRelationsArgs, list_work_item_relations};
 pub use query_work_items::{QueryWorkItemsArgs, query_work_items};
 pub use query_work_items_by_wiql::{QueryWorkItemsArgsWiql, query_work_items_by_wiql};
+pub use unlink_work_items::{UnlinkWorkItemsArgs, unlink_work_items};
 pub use update_comment::{UpdateCommentArgs, update_comment};
 pub use update_work_item::{UpdateWorkItemArgs, update_work_item};
@@ -1,6 +1,7 @@
 use crate::azure::api_trait::AzureDevOpsApi;
 use crate::mcp::tools::support::{
-    deserialize_non_empty_string, simplify_work_item_json, tool_text_success, work_items_to_csv,
+    azure_error_to_mcp, deserialize_non_empty_string, query_page_summary, resolve_query_page,
+    simplify_work_item_json, tool_text_success, work_items_to_csv,
 };
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
@@ -110,11 +111,24 @@ pub struct QueryWorkItemsArgs {
     /// Include the latest N comments (optional). Set to -1 for all comments.
     #[serde(default)]
     pub include_latest_n_comments: Option<i32>,
+
+    /// Maximum number of work items to return (default: 200, max: 1000)
+    #[serde(default)]
+    pub top: Option<u32>,
+
+    /// Number of matching work items to skip (mutually exclusive with cursor)
+    #[serde(default)]
+    pub skip: Option<u32>,
+
+    /// Continuation cursor: the next_cursor value reported by the previous page
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
 #[mcp_tool(
     name = "azdo_query_work_items",
-    description = "Query work items by filters"
+    description = "Query work items by filters",
+    read_only = true
 )]
 pub async fn query_work_items(
     client: &(dyn AzureDevOpsApi + Send + Sync),
@@ -339,27 +353,31 @@ pub async fn query_work_items(
 
     log::debug!("Executing WIQL query: {}", query);
 
-    // Execute the query to get work items
-    let work_items = client
+    let (skip, top) = resolve_query_page(args.skip, args.cursor.as_deref(), args.top)?;
+
+    // Execute the query to get one page of work items
+    let page = client
         .query_work_items(
             &args.organization,
             &args.project,
             &query,
+            skip,
+            top,
             args.include_latest_n_comments,
         )
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
-
-    if work_items.is_empty() {
-        return Ok(tool_text_success("No work items found"));
+        .map_err(azure_error_to_mcp)?;
+
+    let summary = query_page_summary(&page);
+    if page.work_items.is_empty() {
+        return Ok(tool_text_success(format!(
+            "No work items found\n{}",
+            summary
+        )));
     }
 
     // Convert to JSON value, simplify, then convert to CSV
-    let mut json_value = serde_json::to_value(&work_items).map_err(|e| McpError {
+    let mut json_value = serde_json::to_value(&page.work_items).map_err(|e| McpError {
         code: ErrorCode(-32000),
         message: format!("Failed to serialize response: {}", e).into(),
         data: None,
@@ -371,5 +389,5 @@ pub async fn query_work_items(
         data: None,
     })?;
 
-    Ok(tool_text_success(csv_output))
+    Ok(tool_text_success(format!("{}\n{}", summary, csv_output)))
 }
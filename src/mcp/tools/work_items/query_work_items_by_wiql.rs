@@ -1,6 +1,7 @@
 use crate::azure::api_trait::AzureDevOpsApi;
 use crate::mcp::tools::support::{
-    deserialize_non_empty_string, simplify_work_item_json, tool_text_success, work_items_to_csv,
+    azure_error_to_mcp, deserialize_non_empty_string, query_results_to_text, resolve_query_page,
+    tool_text_success,
 };
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
@@ -18,16 +19,29 @@ pub struct QueryWorkItemsArgsWiql {
     /// AzDO project name
     #[serde(deserialize_with = "deserialize_non_empty_string")]
     pub project: String,
-    /// WIQL query string (e.g., "SELECT [System.Id] FROM WorkItems WHERE [System.State] = 'Active'")
+    /// WIQL query string (e.g., "SELECT [System.Id] FROM WorkItems WHERE [System.State] = 'Active'"); FROM WorkItemLinks queries return the links as an indented hierarchy
     pub query: String,
     /// Include the latest N comments (optional). Set to -1 for all comments.
     #[serde(default)]
     pub include_latest_n_comments: Option<i32>,
+
+    /// Maximum number of work items to return (default: 200, max: 1000)
+    #[serde(default)]
+    pub top: Option<u32>,
+
+    /// Number of matching work items to skip (mutually exclusive with cursor)
+    #[serde(default)]
+    pub skip: Option<u32>,
+
+    /// Continuation cursor: the next_cursor value reported by the previous page
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
 #[mcp_tool(
     name = "azdo_query_work_items_by_wiql",
-    description = "Query work items using WIQL"
+    description = "Query work items using WIQL, including link and tree (FROM WorkItemLinks) queries",
+    read_only = true
 )]
 pub async fn query_work_items_by_wiql(
     client: &(dyn AzureDevOpsApi + Send + Sync),
@@ -37,36 +51,24 @@ pub async fn query_work_items_by_wiql(
         "Tool invoked: azdo_query_work_items_by_wiql(query={})",
         args.query
     );
-    let items = client
+    let (skip, top) = resolve_query_page(args.skip, args.cursor.as_deref(), args.top)?;
+
+    let page = client
         .query_work_items(
             &args.organization,
             &args.project,
             &args.query,
+            skip,
+            top,
             args.include_latest_n_comments,
         )
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
-
-    if items.is_empty() {
-        return Ok(tool_text_success("No work items found"));
-    }
+        .map_err(azure_error_to_mcp)?;
 
-    // Convert to JSON value, simplify, then convert to CSV
-    let mut json_value = serde_json::to_value(&items).map_err(|e| McpError {
-        code: ErrorCode(-32000),
-        message: format!("Failed to serialize response: {}", e).into(),
-        data: None,
-    })?;
-    simplify_work_item_json(&mut json_value);
-    let csv_output = work_items_to_csv(&json_value).map_err(|e| McpError {
+    let results = query_results_to_text(&page).map_err(|e| McpError {
         code: ErrorCode(-32000),
-        message: format!("Failed to convert to CSV: {}", e).into(),
+        message: e.into(),
         data: None,
     })?;
-
-    Ok(tool_text_success(csv_output))
+    Ok(tool_text_success(results))
 }
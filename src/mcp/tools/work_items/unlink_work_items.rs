@@ -0,0 +1,115 @@
+use crate::azure::api_trait::AzureDevOpsApi;
+use crate::compact_llm;
+use crate::mcp::tools::support::{
+    LinkTarget, LinkTargetArgs, azure_error_to_mcp, deserialize_non_empty_string, invalid_params,
+    resolve_link_target, resolve_work_item_link_type, tool_text_success,
+};
+use mcp_tools_codegen::mcp_tool;
+use rmcp::{
+    ErrorData as McpError,
+    model::{CallToolResult, ErrorCode},
+    schemars::{self, JsonSchema},
+    serde::Deserialize,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct UnlinkWorkItemsArgs {
+    /// AzDO org name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub organization: String,
+    /// AzDO project name
+    #[serde(deserialize_with = "deserialize_non_empty_string")]
+    pub project: String,
+    /// Source work item ID
+    pub source_id: u32,
+    /// Linked work item ID to unlink
+    #[serde(default)]
+    pub target_id: Option<u32>,
+    /// Only remove links of this type, e.g. "Parent" or "System.LinkTypes.Related" (work item links only; default: all links to target_id)
+    #[serde(default)]
+    pub link_type: Option<String>,
+    /// Hyperlink URL to remove
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Git repository name or ID (required with commit_id or pull_request_id)
+    #[serde(default)]
+    pub repository: Option<String>,
+    /// Linked commit SHA to remove
+    #[serde(default)]
+    pub commit_id: Option<String>,
+    /// Linked pull request ID to remove
+    #[serde(default)]
+    pub pull_request_id: Option<u32>,
+    /// Linked build ID to remove
+    #[serde(default)]
+    pub build_id: Option<u32>,
+}
+
+#[mcp_tool(
+    name = "azdo_unlink_work_items",
+    description = "Remove links from a work item to another work item, a hyperlink, a commit, a pull request or a build",
+    read_only = false
+)]
+pub async fn unlink_work_items(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    args: UnlinkWorkItemsArgs,
+) -> Result<CallToolResult, McpError> {
+    log::info!(
+        "Tool invoked: azdo_unlink_work_items(source_id={}, target_id={:?}, link_type={:?}, url={:?}, commit_id={:?}, pull_request_id={:?}, build_id={:?})",
+        args.source_id,
+        args.target_id,
+        args.link_type,
+        args.url,
+        args.commit_id,
+        args.pull_request_id,
+        args.build_id
+    );
+
+    let target = resolve_link_target(
+        client,
+        &args.organization,
+        &args.project,
+        &LinkTargetArgs {
+            target_id: args.target_id,
+            url: args.url.as_deref(),
+            repository: args.repository.as_deref(),
+            commit_id: args.commit_id.as_deref(),
+            pull_request_id: args.pull_request_id,
+            build_id: args.build_id,
+        },
+    )
+    .await?;
+
+    let link_type = match (&target, &args.link_type) {
+        (LinkTarget::WorkItem(_), Some(link_type)) => {
+            Some(resolve_work_item_link_type(client, &args.organization, link_type).await?)
+        }
+        (_, Some(_)) => {
+            return Err(invalid_params("link_type only applies to work item links"));
+        }
+        (_, None) => None,
+    };
+
+    let result = client
+        .unlink_work_items(
+            &args.organization,
+            &args.project,
+            args.source_id,
+            &target.url(),
+            link_type,
+        )
+        .await
+        .map_err(azure_error_to_mcp)?;
+
+    let Some(result) = result else {
+        return Ok(tool_text_success("No matching link found"));
+    };
+
+    let output = compact_llm::to_compact_string(&result).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to serialize response: {}", e).into(),
+        data: None,
+    })?;
+
+    Ok(tool_text_success(output))
+}
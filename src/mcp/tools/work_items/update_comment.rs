@@ -1,7 +1,7 @@
 use crate::azure::api_trait::AzureDevOpsApi;
 use crate::compact_llm;
 use crate::mcp::tools::support::{
-    default_text_format, deserialize_non_empty_string, tool_text_success,
+    azure_error_to_mcp, default_text_format, deserialize_non_empty_string, tool_text_success,
 };
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
@@ -33,7 +33,8 @@ pub struct UpdateCommentArgs {
 
 #[mcp_tool(
     name = "azdo_update_comment",
-    description = "Update a comment on a work item"
+    description = "Update a comment on a work item",
+    read_only = false
 )]
 pub async fn update_comment(
     client: &(dyn AzureDevOpsApi + Send + Sync),
@@ -66,11 +67,7 @@ pub async fn update_comment(
             &format,
         )
         .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .map_err(azure_error_to_mcp)?;
 
     let output = compact_llm::to_compact_string(&result).map_err(|e| McpError {
         code: ErrorCode(-32000),
@@ -1,12 +1,16 @@
 use crate::azure::api_trait::AzureDevOpsApi;
+use crate::azure::client::AzureError;
+use crate::azure::models::WorkItem;
 use crate::compact_llm;
 use crate::mcp::tools::support::{
-    default_text_format, deserialize_non_empty_string, simplify_work_item_json, tool_text_success,
+    UNTRUSTED_CONTENT_WARNING, azure_error_to_mcp, azure_error_with_context, default_text_format,
+    deserialize_non_empty_string, invalid_params, simplify_field_name, simplify_work_item_json,
+    tool_text_success,
 };
 use mcp_tools_codegen::mcp_tool;
 use rmcp::{
     ErrorData as McpError,
-    model::{CallToolResult, ErrorCode},
+    model::{CallToolResult, ContentBlock, ErrorCode},
     schemars::{self, JsonSchema},
     serde::Deserialize,
 };
@@ -78,10 +82,18 @@ pub struct UpdateWorkItemArgs {
     #[serde(default)]
     pub remaining_work: Option<f64>,
 
-    /// Comma-separated tags (e.g., "bug, critical, ui")
+    /// Comma-separated tags (e.g., "bug, critical, ui"); replaces all existing tags
     #[serde(default)]
     pub tags: Option<String>,
 
+    /// Comma-separated tags to add, keeping the existing ones
+    #[serde(default)]
+    pub add_tags: Option<String>,
+
+    /// Comma-separated tags to remove, keeping the others
+    #[serde(default)]
+    pub remove_tags: Option<String>,
+
     /// Activity type (Development, Testing, Documentation, etc.)
     #[serde(default)]
     pub activity: Option<String>,
@@ -109,9 +121,100 @@ pub struct UpdateWorkItemArgs {
     /// Optional extra fields as JSON string (for custom fields)
     #[serde(default)]
     pub fields: Option<String>,
+
+    /// Fields to clear (unset), by argument name (e.g., "assigned_to", "iteration_path"), field name (e.g., "AssignedTo") or reference name (e.g., "Custom.Field")
+    #[serde(default)]
+    pub clear_fields: Vec<String>,
+
+    /// Only apply the update if the work item is still at this revision (the "Rev" of the work item as last read); on conflict nothing is changed and the current state is returned
+    #[serde(default)]
+    pub expected_rev: Option<u32>,
+}
+
+/// Update arguments and the fields they set, so `clear_fields` accepts the
+/// same names as the setters.
+const CLEARABLE_FIELDS: &[(&str, &str)] = &[
+    ("title", "System.Title"),
+    ("description", "System.Description"),
+    ("assigned_to", "System.AssignedTo"),
+    ("area_path", "System.AreaPath"),
+    ("iteration_path", "System.IterationPath"),
+    ("board_column", "System.BoardColumn"),
+    ("board_row", "System.BoardLane"),
+    ("priority", "Microsoft.VSTS.Common.Priority"),
+    ("severity", "Microsoft.VSTS.Common.Severity"),
+    ("story_points", "Microsoft.VSTS.Scheduling.StoryPoints"),
+    ("effort", "Microsoft.VSTS.Scheduling.Effort"),
+    ("remaining_work", "Microsoft.VSTS.Scheduling.RemainingWork"),
+    ("tags", "System.Tags"),
+    ("activity", "Microsoft.VSTS.Common.Activity"),
+    ("start_date", "Microsoft.VSTS.Scheduling.StartDate"),
+    ("target_date", "Microsoft.VSTS.Scheduling.TargetDate"),
+    (
+        "acceptance_criteria",
+        "Microsoft.VSTS.Common.AcceptanceCriteria",
+    ),
+    ("repro_steps", "Microsoft.VSTS.TCM.ReproSteps"),
+    ("justification", "Microsoft.VSTS.CMMI.Justification"),
+];
+
+/// Resolves a `clear_fields` entry to a field reference name. Accepts the
+/// argument name, the field name ("IterationPath") or its shortened output
+/// name ("Iteration"); names with a dot are taken as reference names of
+/// (possibly custom) fields.
+fn resolve_clear_field(name: &str) -> Result<String, McpError> {
+    let name = name.trim();
+    if name.contains('.') {
+        return Ok(name.to_string());
+    }
+    CLEARABLE_FIELDS
+        .iter()
+        .find(|(arg, reference_name)| {
+            let field_name = reference_name.rsplit('.').next().unwrap_or(reference_name);
+            arg.eq_ignore_ascii_case(name)
+                || field_name.eq_ignore_ascii_case(name)
+                || simplify_field_name(reference_name).eq_ignore_ascii_case(name)
+        })
+        .map(|(_, reference_name)| reference_name.to_string())
+        .ok_or_else(|| {
+            invalid_params(format!(
+                "Unknown field '{}' in clear_fields; use an argument name like \"assigned_to\" or a reference name like \"Custom.MyField\"",
+                name
+            ))
+        })
+}
+
+/// Splits a tag list on commas and semicolons (the separator Azure DevOps
+/// uses in System.Tags), dropping empty entries.
+fn split_tags(tags: &str) -> Vec<String> {
+    tags.split([',', ';'])
+        .map(|tag| tag.trim())
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| tag.to_string())
+        .collect()
 }
 
-#[mcp_tool(name = "azdo_update_work_item", description = "Update work item")]
+/// Applies tag additions and removals to the current System.Tags value.
+/// Tags are matched case-insensitively, like Azure DevOps does.
+fn merge_tags(current: &str, add: &[String], remove: &[String]) -> String {
+    let mut tags = split_tags(current);
+    for tag in add {
+        if !tags
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(tag))
+        {
+            tags.push(tag.clone());
+        }
+    }
+    tags.retain(|existing| !remove.iter().any(|tag| tag.eq_ignore_ascii_case(existing)));
+    tags.join("; ")
+}
+
+#[mcp_tool(
+    name = "azdo_update_work_item",
+    description = "Update work item",
+    read_only = false
+)]
 pub async fn update_work_item(
     client: &(dyn AzureDevOpsApi + Send + Sync),
     args: UpdateWorkItemArgs,
@@ -126,11 +229,12 @@ pub async fn update_work_item(
     }
 
     log::info!(
-        "Tool invoked: azdo_update_work_item(id={}, title={:?}, state={:?}, format={})",
+        "Tool invoked: azdo_update_work_item(id={}, title={:?}, state={:?}, format={}, expected_rev={:?})",
         args.id,
         args.title,
         args.state,
         format,
+        args.expected_rev,
     );
 
     // Build multiline fields format list for large text fields
@@ -274,45 +378,160 @@ pub async fn update_work_item(
                 field_map.insert(k, v);
             }
         } else {
-            return Err(McpError {
-                code: ErrorCode(-32602),
-                message: "Invalid JSON in extra fields".into(),
+            return Err(invalid_params("Invalid JSON in extra fields"));
+        }
+    }
+
+    let mut expected_rev = args.expected_rev;
+
+    // Adding or removing single tags is a read-modify-write of System.Tags;
+    // the revision that was read guards against losing a concurrent tag edit.
+    if args.add_tags.is_some() || args.remove_tags.is_some() {
+        if args.tags.is_some() {
+            return Err(invalid_params(
+                "tags replaces all tags and cannot be combined with add_tags or remove_tags",
+            ));
+        }
+        let current = client
+            .get_work_item(&args.organization, &args.project, args.id, None)
+            .await
+            .map_err(azure_error_to_mcp)?
+            .ok_or_else(|| McpError {
+                code: ErrorCode(-32000),
+                message: format!("Work item {} not found", args.id).into(),
                 data: None,
-            });
+            })?;
+        let current_tags = current
+            .fields
+            .get("System.Tags")
+            .and_then(|tags| tags.as_str())
+            .unwrap_or_default();
+        let tags = merge_tags(
+            current_tags,
+            &split_tags(args.add_tags.as_deref().unwrap_or_default()),
+            &split_tags(args.remove_tags.as_deref().unwrap_or_default()),
+        );
+        field_map.insert("System.Tags".to_string(), serde_json::json!(tags));
+        if expected_rev.is_none() {
+            expected_rev = current
+                .fields
+                .get("System.Rev")
+                .and_then(|rev| rev.as_u64())
+                .map(|rev| rev as u32);
+        }
+    }
+
+    let mut clear_fields: Vec<String> = Vec::new();
+    for name in &args.clear_fields {
+        let reference_name = resolve_clear_field(name)?;
+        if field_map.contains_key(&reference_name) {
+            return Err(invalid_params(format!(
+                "Field '{}' cannot be both set and cleared",
+                name
+            )));
+        }
+        if !clear_fields.contains(&reference_name) {
+            clear_fields.push(reference_name);
         }
     }
 
     let fields_vec: Vec<(String, serde_json::Value)> = field_map.into_iter().collect();
 
-    let work_item = client
+    let result = client
         .update_work_item(
             &args.organization,
             &args.project,
             args.id,
             &fields_vec,
+            &clear_fields,
             &multiline_formats,
+            expected_rev,
         )
-        .await
-        .map_err(|e| McpError {
-            code: ErrorCode(-32000),
-            message: e.to_string().into(),
-            data: None,
-        })?;
+        .await;
+
+    let work_item = match result {
+        Ok(work_item) => work_item,
+        Err(AzureError::Conflict(message)) => {
+            return conflict_result(client, &args, expected_rev, &message).await;
+        }
+        Err(e) => return Err(azure_error_to_mcp(e)),
+    };
 
-    let mut json_value = serde_json::to_value(&work_item).map_err(|e| McpError {
+    let output = work_item_to_compact_string(&work_item)?;
+
+    Ok(tool_text_success(output))
+}
+
+fn work_item_to_compact_string(work_item: &WorkItem) -> Result<String, McpError> {
+    let mut json_value = serde_json::to_value(work_item).map_err(|e| McpError {
         code: ErrorCode(-32000),
         message: format!("Failed to serialize response: {}", e).into(),
         data: None,
     })?;
     simplify_work_item_json(&mut json_value);
 
-    let output = compact_llm::to_compact_string(&json_value).map_err(|e| McpError {
+    compact_llm::to_compact_string(&json_value).map_err(|e| McpError {
+        code: ErrorCode(-32000),
+        message: format!("Failed to serialize response: {}", e).into(),
+        data: None,
+    })
+}
+
+/// Builds the answer for an update rejected because the work item changed
+/// since `expected_rev`: a tool error carrying the current state, so the
+/// caller can merge its changes and retry with the new revision.
+async fn conflict_result(
+    client: &(dyn AzureDevOpsApi + Send + Sync),
+    args: &UpdateWorkItemArgs,
+    expected_rev: Option<u32>,
+    message: &str,
+) -> Result<CallToolResult, McpError> {
+    log::info!(
+        "azdo_update_work_item(id={}) conflict at expected_rev={:?}: {}",
+        args.id,
+        expected_rev,
+        message
+    );
+
+    let current = client
+        .get_work_item(&args.organization, &args.project, args.id, None)
+        .await
+        .map_err(|e| {
+            azure_error_with_context("Update conflicted and reading the current state failed", e)
+        })?
+        .ok_or_else(|| McpError {
+            code: ErrorCode(-32000),
+            message: format!(
+                "Update conflicted and work item {} no longer exists",
+                args.id
+            )
+            .into(),
+            data: None,
+        })?;
+
+    let current_rev = current.fields.get("System.Rev").cloned();
+    let summary = compact_llm::to_compact_string(&serde_json::json!({
+        "conflict": true,
+        "expected_rev": expected_rev,
+        "current_rev": current_rev,
+    }))
+    .map_err(|e| McpError {
         code: ErrorCode(-32000),
         message: format!("Failed to serialize response: {}", e).into(),
         data: None,
     })?;
+    let current = work_item_to_compact_string(&current)?;
 
-    Ok(tool_text_success(output))
+    Ok(CallToolResult::error(vec![ContentBlock::text(format!(
+        "{}\nConflict: work item {} was changed since revision {}; nothing was updated. Re-apply the changes to the current state below and retry with expected_rev set to current_rev.\n{}\n{}",
+        UNTRUSTED_CONTENT_WARNING,
+        args.id,
+        expected_rev
+            .map(|rev| rev.to_string())
+            .unwrap_or_else(|| "read".to_string()),
+        summary,
+        current
+    ))]))
 }
 
 #[cfg(test)]
@@ -533,4 +752,36 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_resolve_clear_field_accepts_argument_field_and_reference_names() {
+        let cases = vec![
+            ("assigned_to", "System.AssignedTo"),
+            ("AssignedTo", "System.AssignedTo"),
+            ("iteration_path", "System.IterationPath"),
+            ("Iteration", "System.IterationPath"),
+            ("StoryPoints", "Microsoft.VSTS.Scheduling.StoryPoints"),
+            ("Custom.Risk", "Custom.Risk"),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(
+                resolve_clear_field(name).unwrap(),
+                expected,
+                "name '{name}'"
+            );
+        }
+        assert!(resolve_clear_field("no_such_field").is_err());
+    }
+
+    #[test]
+    fn test_merge_tags_adds_and_removes_case_insensitively() {
+        let tags = merge_tags(
+            "backend; Flaky; ui",
+            &["UI".to_string(), "triaged".to_string()],
+            &["flaky".to_string()],
+        );
+        assert_eq!(tags, "backend; ui; triaged");
+        assert_eq!(merge_tags("", &[], &[]), "");
+        assert_eq!(merge_tags("a", &[], &["A".to_string()]), "");
+    }
 }
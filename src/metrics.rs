@@ -0,0 +1,287 @@
+use once_cell::sync::Lazy;
+use rmcp::{ErrorData as McpError, model::CallToolResult};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Upper bounds (in seconds) of the tool call latency histogram buckets
+const LATENCY_BUCKETS: [f64; 10] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+/// Error class of calls answered with a result flagged `is_error` rather than
+/// with a JSON-RPC error
+const ERROR_RESULT_CLASS: &str = "error_result";
+
+/// Process-wide metrics registry, rendered by [`render`] for `/metrics`
+static METRICS: Lazy<Mutex<Metrics>> = Lazy::new(|| Mutex::new(Metrics::default()));
+
+#[derive(Debug, Default, Clone)]
+struct Histogram {
+    /// Cumulative count per bucket of [`LATENCY_BUCKETS`]
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if value <= bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+#[derive(Debug, Default)]
+struct Metrics {
+    tool_calls: BTreeMap<String, u64>,
+    tool_errors: BTreeMap<(String, String), u64>,
+    tool_durations: BTreeMap<String, Histogram>,
+    upstream_requests: BTreeMap<(String, String), u64>,
+    upstream_throttled: u64,
+    upstream_retries: u64,
+}
+
+fn with_metrics(update: impl FnOnce(&mut Metrics)) {
+    update(&mut METRICS.lock().unwrap_or_else(|e| e.into_inner()));
+}
+
+/// Runs a tool call, recording its count, latency and, when it fails with an
+/// error or a result flagged `is_error`, the class of the error
+pub async fn observe_tool_call(
+    tool: &str,
+    call: impl Future<Output = Result<CallToolResult, McpError>>,
+) -> Result<CallToolResult, McpError> {
+    let started = Instant::now();
+    let result = call.await;
+    let error = match &result {
+        Err(error) => Some(error_class(error)),
+        Ok(result) if result.is_error == Some(true) => Some(ERROR_RESULT_CLASS.to_string()),
+        Ok(_) => None,
+    };
+    record_tool_call(tool, started.elapsed(), error);
+    result
+}
+
+fn record_tool_call(tool: &str, duration: Duration, error_class: Option<String>) {
+    with_metrics(|metrics| {
+        *metrics.tool_calls.entry(tool.to_string()).or_default() += 1;
+        metrics
+            .tool_durations
+            .entry(tool.to_string())
+            .or_default()
+            .observe(duration.as_secs_f64());
+        if let Some(error_class) = error_class {
+            let key = (tool.to_string(), error_class);
+            *metrics.tool_errors.entry(key).or_default() += 1;
+        }
+    });
+}
+
+/// The `data.kind` set by `azure_error_to_mcp`, or a class derived from the
+/// JSON-RPC code for errors raised by the tools themselves
+fn error_class(error: &McpError) -> String {
+    if let Some(kind) = error
+        .data
+        .as_ref()
+        .and_then(|data| data.get("kind"))
+        .and_then(|kind| kind.as_str())
+    {
+        return kind.to_string();
+    }
+    match error.code.0 {
+        -32602 => "invalid_params",
+        -32601 => "method_not_found",
+        _ => "internal",
+    }
+    .to_string()
+}
+
+/// Records the outcome of one request to Azure DevOps (every attempt,
+/// including retries): its HTTP status, or `error` when none was received
+pub fn record_upstream_response(method: &str, status: Option<u16>) {
+    with_metrics(|metrics| {
+        let status = status.map_or_else(|| "error".to_string(), |status| status.to_string());
+        *metrics
+            .upstream_requests
+            .entry((method.to_string(), status.clone()))
+            .or_default() += 1;
+        if status == "429" {
+            metrics.upstream_throttled += 1;
+        }
+    });
+}
+
+/// Records that a request to Azure DevOps is retried
+pub fn record_upstream_retry() {
+    with_metrics(|metrics| metrics.upstream_retries += 1);
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Renders all metrics in the Prometheus text exposition format
+pub fn render() -> String {
+    let metrics = METRICS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut out = String::new();
+
+    out.push_str("# HELP mcp_tool_calls_total MCP tool calls, by tool.\n");
+    out.push_str("# TYPE mcp_tool_calls_total counter\n");
+    for (tool, count) in &metrics.tool_calls {
+        let _ = writeln!(
+            out,
+            "mcp_tool_calls_total{{tool=\"{}\"}} {count}",
+            escape_label(tool)
+        );
+    }
+
+    out.push_str("# HELP mcp_tool_errors_total Failed MCP tool calls, by tool and error class.\n");
+    out.push_str("# TYPE mcp_tool_errors_total counter\n");
+    for ((tool, kind), count) in &metrics.tool_errors {
+        let _ = writeln!(
+            out,
+            "mcp_tool_errors_total{{tool=\"{}\",kind=\"{}\"}} {count}",
+            escape_label(tool),
+            escape_label(kind)
+        );
+    }
+
+    out.push_str("# HELP mcp_tool_call_duration_seconds Latency of MCP tool calls, by tool.\n");
+    out.push_str("# TYPE mcp_tool_call_duration_seconds histogram\n");
+    for (tool, histogram) in &metrics.tool_durations {
+        let tool = escape_label(tool);
+        for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets) {
+            let _ = writeln!(
+                out,
+                "mcp_tool_call_duration_seconds_bucket{{tool=\"{tool}\",le=\"{bound}\"}} {count}"
+            );
+        }
+        let _ = writeln!(
+            out,
+            "mcp_tool_call_duration_seconds_bucket{{tool=\"{tool}\",le=\"+Inf\"}} {}",
+            histogram.count
+        );
+        let _ = writeln!(
+            out,
+            "mcp_tool_call_duration_seconds_sum{{tool=\"{tool}\"}} {}",
+            histogram.sum
+        );
+        let _ = writeln!(
+            out,
+            "mcp_tool_call_duration_seconds_count{{tool=\"{tool}\"}} {}",
+            histogram.count
+        );
+    }
+
+    out.push_str(
+        "# HELP azure_devops_requests_total Requests sent to Azure DevOps, by method and status.\n",
+    );
+    out.push_str("# TYPE azure_devops_requests_total counter\n");
+    for ((method, status), count) in &metrics.upstream_requests {
+        let _ = writeln!(
+            out,
+            "azure_devops_requests_total{{method=\"{method}\",status=\"{status}\"}} {count}"
+        );
+    }
+
+    out.push_str("# HELP azure_devops_throttled_total Requests Azure DevOps answered with 429.\n");
+    out.push_str("# TYPE azure_devops_throttled_total counter\n");
+    let _ = writeln!(
+        out,
+        "azure_devops_throttled_total {}",
+        metrics.upstream_throttled
+    );
+
+    out.push_str("# HELP azure_devops_retries_total Retried requests to Azure DevOps.\n");
+    out.push_str("# TYPE azure_devops_retries_total counter\n");
+    let _ = writeln!(
+        out,
+        "azure_devops_retries_total {}",
+        metrics.upstream_retries
+    );
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::{ContentBlock, ErrorCode};
+    use serde_json::json;
+
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let mut histogram = Histogram::default();
+        histogram.observe(0.07);
+        histogram.observe(3.0);
+        assert_eq!(histogram.buckets, [0, 1, 1, 1, 1, 1, 2, 2, 2, 2]);
+        assert_eq!(histogram.count, 2);
+        assert!((histogram.sum - 3.07).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_error_class() {
+        let azure = McpError::new(
+            ErrorCode(-32029),
+            "throttled",
+            Some(json!({"kind": "rate_limited"})),
+        );
+        assert_eq!(error_class(&azure), "rate_limited");
+        assert_eq!(
+            error_class(&McpError::invalid_params("bad", None)),
+            "invalid_params"
+        );
+        assert_eq!(
+            error_class(&McpError::internal_error("boom", None)),
+            "internal"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_render_includes_recorded_metrics() {
+        let tool = "metrics_test_tool";
+        observe_tool_call(tool, async { Ok(CallToolResult::success(vec![])) })
+            .await
+            .unwrap();
+        observe_tool_call(tool, async {
+            Err(McpError::new(
+                ErrorCode(-32002),
+                "missing",
+                Some(json!({"kind": "not_found"})),
+            ))
+        })
+        .await
+        .unwrap_err();
+        observe_tool_call(tool, async {
+            Ok(CallToolResult::error(vec![ContentBlock::text("failed")]))
+        })
+        .await
+        .unwrap();
+        record_upstream_response("GET", Some(429));
+        record_upstream_response("GET", None);
+
+        let rendered = render();
+        for expected in [
+            "mcp_tool_calls_total{tool=\"metrics_test_tool\"} 3",
+            "mcp_tool_errors_total{tool=\"metrics_test_tool\",kind=\"not_found\"} 1",
+            "mcp_tool_errors_total{tool=\"metrics_test_tool\",kind=\"error_result\"} 1",
+            "mcp_tool_call_duration_seconds_bucket{tool=\"metrics_test_tool\",le=\"+Inf\"} 3",
+            "mcp_tool_call_duration_seconds_count{tool=\"metrics_test_tool\"} 3",
+            "# TYPE azure_devops_requests_total counter",
+            "azure_devops_requests_total{method=\"GET\",status=\"error\"}",
+        ] {
+            assert!(
+                rendered.contains(expected),
+                "missing '{expected}' in:\n{rendered}"
+            );
+        }
+        assert!(!rendered.contains("azure_devops_throttled_total 0"));
+    }
+}
@@ -0,0 +1,526 @@
+use aws_lc_rs::digest::{SHA256, digest};
+use hyper::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Well-known path of the OAuth 2.0 protected resource metadata (RFC 9728)
+pub const PROTECTED_RESOURCE_METADATA_PATH: &str = "/.well-known/oauth-protected-resource";
+
+const RESOURCE_NAME: &str = "MCP for Azure DevOps Boards";
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthConfigError {
+    #[error("Failed to read {}: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid JWKS in {}: {message}", path.display())]
+    InvalidJwks { path: PathBuf, message: String },
+
+    #[error("Invalid OAuth resource URL '{url}': {message}")]
+    InvalidResource { url: String, message: String },
+}
+
+/// Why a request was not let through
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthRejection {
+    /// No bearer token was sent
+    MissingToken,
+    /// The token is unknown, malformed, expired or not issued for this server
+    InvalidToken(String),
+    /// The token is valid but lacks a required scope
+    InsufficientScope,
+}
+
+/// Access control of the HTTP server: a request is accepted when its bearer
+/// token is in the static allow-list or is a JWT that validates against the
+/// configured OAuth resource. With neither configured every request is
+/// accepted.
+#[derive(Clone, Default)]
+pub struct InboundAuth {
+    static_tokens: Vec<String>,
+    oauth: Option<OAuthResource>,
+}
+
+impl std::fmt::Debug for InboundAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InboundAuth")
+            .field("static_tokens", &self.static_tokens.len())
+            .field("oauth", &self.oauth)
+            .finish()
+    }
+}
+
+impl InboundAuth {
+    /// Adds tokens to the static allow-list; empty tokens are ignored
+    pub fn with_static_tokens(mut self, tokens: impl IntoIterator<Item = String>) -> Self {
+        self.static_tokens.extend(
+            tokens
+                .into_iter()
+                .map(|token| token.trim().to_string())
+                .filter(|token| !token.is_empty()),
+        );
+        self
+    }
+
+    pub fn with_oauth(mut self, oauth: OAuthResource) -> Self {
+        self.oauth = Some(oauth);
+        self
+    }
+
+    /// Whether requests have to be authenticated at all
+    pub fn is_enabled(&self) -> bool {
+        !self.static_tokens.is_empty() || self.oauth.is_some()
+    }
+
+    pub fn oauth(&self) -> Option<&OAuthResource> {
+        self.oauth.as_ref()
+    }
+
+    /// Checks the bearer token of a request
+    pub fn authenticate(&self, headers: &HeaderMap) -> Result<(), AuthRejection> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        let token = bearer_token(headers).ok_or(AuthRejection::MissingToken)?;
+        if self
+            .static_tokens
+            .iter()
+            .any(|allowed| constant_time_eq(allowed.as_bytes(), token.as_bytes()))
+        {
+            return Ok(());
+        }
+        match &self.oauth {
+            Some(oauth) => oauth.validate(token),
+            None => Err(AuthRejection::InvalidToken(
+                "the token is not allowed".to_string(),
+            )),
+        }
+    }
+
+    /// `WWW-Authenticate` challenge for a rejected request (RFC 6750), which
+    /// points OAuth clients at the protected resource metadata
+    pub fn challenge(&self, rejection: &AuthRejection) -> HeaderValue {
+        let mut params = vec!["realm=\"mcp\"".to_string()];
+        if let Some(oauth) = &self.oauth {
+            params.push(format!("resource_metadata=\"{}\"", oauth.metadata_url()));
+        }
+        match rejection {
+            AuthRejection::MissingToken => {}
+            AuthRejection::InvalidToken(_) => params.push("error=\"invalid_token\"".to_string()),
+            AuthRejection::InsufficientScope => {
+                params.push("error=\"insufficient_scope\"".to_string());
+                if let Some(oauth) = &self.oauth {
+                    params.push(format!("scope=\"{}\"", oauth.required_scopes.join(" ")));
+                }
+            }
+        }
+        HeaderValue::from_str(&format!("Bearer {}", params.join(", ")))
+            .unwrap_or_else(|_| HeaderValue::from_static("Bearer realm=\"mcp\""))
+    }
+}
+
+/// Reads a static token allow-list: one token per line, blank lines and lines
+/// starting with `#` are skipped
+pub fn load_static_tokens(path: &Path) -> Result<Vec<String>, AuthConfigError> {
+    let content = std::fs::read_to_string(path).map_err(|source| AuthConfigError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    let value = headers.get(AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+    let token = token.trim();
+    (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then_some(token)
+}
+
+/// Compares tokens without returning early on the first differing byte, so
+/// response timing does not reveal how much of a guessed token is right.
+/// Their SHA-256 digests are compared so that the work done does not depend
+/// on their lengths either.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    let (a, b) = (digest(&SHA256, a), digest(&SHA256, b));
+    a.as_ref()
+        .iter()
+        .zip(b.as_ref())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
+}
+
+#[derive(Clone)]
+struct VerificationKey {
+    kid: Option<String>,
+    key: DecodingKey,
+    algorithms: Vec<Algorithm>,
+}
+
+/// This server as an OAuth 2.1 protected resource (MCP authorization spec):
+/// access tokens are JWTs issued by `issuer` for `audience`, verified with the
+/// keys of a local JWKS file and optionally required to carry scopes.
+#[derive(Clone)]
+pub struct OAuthResource {
+    resource: reqwest::Url,
+    issuer: String,
+    audience: String,
+    required_scopes: Vec<String>,
+    keys: Vec<VerificationKey>,
+}
+
+impl std::fmt::Debug for OAuthResource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OAuthResource")
+            .field("resource", &self.resource.as_str())
+            .field("issuer", &self.issuer)
+            .field("audience", &self.audience)
+            .field("required_scopes", &self.required_scopes)
+            .field("keys", &self.keys.len())
+            .finish()
+    }
+}
+
+impl OAuthResource {
+    /// `resource` is the canonical URL of the MCP endpoint (e.g.
+    /// `https://mcp.example.com/mcp`), which is also the expected token
+    /// audience unless [`OAuthResource::with_audience`] overrides it.
+    pub fn from_jwks_file(
+        resource: &str,
+        issuer: &str,
+        jwks_path: &Path,
+    ) -> Result<Self, AuthConfigError> {
+        let jwks = std::fs::read_to_string(jwks_path).map_err(|source| AuthConfigError::Read {
+            path: jwks_path.to_path_buf(),
+            source,
+        })?;
+        let keys = parse_jwks(&jwks).map_err(|message| AuthConfigError::InvalidJwks {
+            path: jwks_path.to_path_buf(),
+            message,
+        })?;
+        Self::new(resource, issuer, keys)
+    }
+
+    fn new(
+        resource: &str,
+        issuer: &str,
+        keys: Vec<VerificationKey>,
+    ) -> Result<Self, AuthConfigError> {
+        let invalid = |message: &str| AuthConfigError::InvalidResource {
+            url: resource.to_string(),
+            message: message.to_string(),
+        };
+        let url = reqwest::Url::parse(resource).map_err(|e| invalid(&e.to_string()))?;
+        if !matches!(url.scheme(), "http" | "https") || !url.has_host() {
+            return Err(invalid("expected an http(s) URL"));
+        }
+        Ok(Self {
+            resource: url,
+            issuer: issuer.to_string(),
+            audience: resource.to_string(),
+            required_scopes: Vec::new(),
+            keys,
+        })
+    }
+
+    /// Expects tokens issued for `audience` instead of the resource URL
+    pub fn with_audience(mut self, audience: impl Into<String>) -> Self {
+        self.audience = audience.into();
+        self
+    }
+
+    /// Rejects tokens that do not carry all of `scopes`
+    pub fn with_required_scopes(mut self, scopes: Vec<String>) -> Self {
+        self.required_scopes = scopes;
+        self
+    }
+
+    /// Where the protected resource metadata of this server is published:
+    /// the well-known path inserted between the origin and the resource path
+    pub fn metadata_url(&self) -> String {
+        let origin = self.resource.origin().ascii_serialization();
+        let path = self.resource.path().trim_end_matches('/');
+        format!("{origin}{PROTECTED_RESOURCE_METADATA_PATH}{path}")
+    }
+
+    /// Protected resource metadata document (RFC 9728)
+    pub fn metadata(&self) -> Value {
+        let mut metadata = json!({
+            "resource": self.resource.as_str(),
+            "authorization_servers": [self.issuer],
+            "bearer_methods_supported": ["header"],
+            "resource_name": RESOURCE_NAME,
+        });
+        if !self.required_scopes.is_empty() {
+            metadata["scopes_supported"] = json!(self.required_scopes);
+        }
+        metadata
+    }
+
+    fn validate(&self, token: &str) -> Result<(), AuthRejection> {
+        let invalid = |message: String| AuthRejection::InvalidToken(message);
+        let header = jsonwebtoken::decode_header(token).map_err(|e| invalid(e.to_string()))?;
+        let key = self
+            .keys
+            .iter()
+            .find(|key| match (&header.kid, &key.kid) {
+                (Some(kid), Some(key_id)) => kid == key_id,
+                (None, _) => self.keys.len() == 1,
+                (Some(_), None) => false,
+            })
+            .ok_or_else(|| invalid("no matching key in the JWKS".to_string()))?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.algorithms = key.algorithms.clone();
+        validation.set_issuer(&[&self.issuer]);
+        validation.set_audience(&[&self.audience]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud"]);
+        let claims = jsonwebtoken::decode::<Value>(token, &key.key, &validation)
+            .map_err(|e| invalid(e.to_string()))?
+            .claims;
+
+        let granted = granted_scopes(&claims);
+        if self
+            .required_scopes
+            .iter()
+            .all(|scope| granted.contains(&scope.as_str()))
+        {
+            Ok(())
+        } else {
+            Err(AuthRejection::InsufficientScope)
+        }
+    }
+}
+
+/// Scopes of an access token: `scp` (Microsoft Entra ID) or `scope`
+/// (RFC 9068), either space-separated or as an array
+fn granted_scopes(claims: &Value) -> Vec<&str> {
+    match claims.get("scp").or_else(|| claims.get("scope")) {
+        Some(Value::String(scopes)) => scopes.split_whitespace().collect(),
+        Some(Value::Array(scopes)) => scopes.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Keys of a JWKS document. A key with an `alg` only verifies that
+/// algorithm; one without verifies any algorithm of its key type.
+fn parse_jwks(jwks: &str) -> Result<Vec<VerificationKey>, String> {
+    let set: JwkSet = serde_json::from_str(jwks).map_err(|e| e.to_string())?;
+    let mut keys = Vec::new();
+    for jwk in &set.keys {
+        let kid = jwk.common.key_id.clone();
+        let key = match DecodingKey::from_jwk(jwk) {
+            Ok(key) => key,
+            Err(e) => {
+                log::warn!("Skipping JWKS key {kid:?}: {e}");
+                continue;
+            }
+        };
+        let algorithms = match jwk.common.key_algorithm {
+            Some(alg) => match Algorithm::from_str(&alg.to_string()) {
+                Ok(alg) => vec![alg],
+                Err(_) => {
+                    log::warn!("Skipping JWKS key {kid:?}: unsupported algorithm {alg}");
+                    continue;
+                }
+            },
+            None => key.family().algorithms().to_vec(),
+        };
+        keys.push(VerificationKey {
+            kid,
+            key,
+            algorithms,
+        });
+    }
+    if keys.is_empty() {
+        return Err("no usable keys".to_string());
+    }
+    Ok(keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{EncodingKey, Header};
+
+    const SECRET: &[u8] = b"test-signing-secret-of-32-bytes!";
+    const RESOURCE: &str = "https://mcp.example.com/mcp";
+    const ISSUER: &str = "https://login.example.com/tenant/v2.0";
+
+    fn jwks() -> String {
+        use base64::Engine;
+        let k = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(SECRET);
+        json!({"keys": [{"kty": "oct", "kid": "key-1", "alg": "HS256", "k": k}]}).to_string()
+    }
+
+    fn oauth() -> OAuthResource {
+        OAuthResource::new(RESOURCE, ISSUER, parse_jwks(&jwks()).unwrap())
+            .unwrap()
+            .with_required_scopes(vec!["boards.read".to_string()])
+    }
+
+    fn token(claims: Value, kid: Option<&str>) -> String {
+        let mut header = Header::new(Algorithm::HS256);
+        header.kid = kid.map(str::to_string);
+        jsonwebtoken::encode(&header, &claims, &EncodingKey::from_secret(SECRET)).unwrap()
+    }
+
+    fn claims() -> Value {
+        json!({
+            "iss": ISSUER,
+            "aud": RESOURCE,
+            "exp": jsonwebtoken::get_current_timestamp() + 600,
+            "scp": "boards.read boards.write",
+        })
+    }
+
+    fn headers(authorization: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_str(authorization).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_static_tokens() {
+        let auth = InboundAuth::default()
+            .with_static_tokens(vec!["secret-1".to_string(), "  ".to_string()]);
+        assert!(auth.is_enabled());
+        assert_eq!(auth.authenticate(&headers("Bearer secret-1")), Ok(()));
+        assert_eq!(auth.authenticate(&headers("bearer secret-1")), Ok(()));
+        assert_eq!(
+            auth.authenticate(&HeaderMap::new()),
+            Err(AuthRejection::MissingToken)
+        );
+        assert_eq!(
+            auth.authenticate(&headers("Basic c2VjcmV0LTE=")),
+            Err(AuthRejection::MissingToken)
+        );
+        assert!(matches!(
+            auth.authenticate(&headers("Bearer secret-2")),
+            Err(AuthRejection::InvalidToken(_))
+        ));
+        assert!(
+            InboundAuth::default()
+                .authenticate(&HeaderMap::new())
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"secret-1", b"secret-1"));
+        assert!(!constant_time_eq(b"secret-1", b"secret-2"));
+        assert!(!constant_time_eq(b"secret-1", b"secret-10"));
+        assert!(!constant_time_eq(b"", b"secret-1"));
+    }
+
+    #[test]
+    fn test_load_static_tokens_skips_comments_and_blank_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tokens");
+        std::fs::write(&path, "# team A\nsecret-1\n\n  secret-2  \n").unwrap();
+        assert_eq!(
+            load_static_tokens(&path).unwrap(),
+            vec!["secret-1".to_string(), "secret-2".to_string()]
+        );
+        assert!(matches!(
+            load_static_tokens(&dir.path().join("missing")),
+            Err(AuthConfigError::Read { .. })
+        ));
+    }
+
+    #[test]
+    fn test_jwt_validation() {
+        let auth = InboundAuth::default().with_oauth(oauth());
+        let mut wrong_audience = claims();
+        wrong_audience["aud"] = json!("https://other.example.com");
+        let mut expired = claims();
+        expired["exp"] = json!(jsonwebtoken::get_current_timestamp() - 3600);
+        let mut missing_scope = claims();
+        missing_scope["scp"] = json!("boards.write");
+        let mut scope_array = claims();
+        scope_array.as_object_mut().unwrap().remove("scp");
+        scope_array["scope"] = json!(["boards.read"]);
+
+        type Check = fn(&Result<(), AuthRejection>) -> bool;
+        let cases: Vec<(&str, String, Check)> = vec![
+            ("valid", token(claims(), Some("key-1")), |r| r.is_ok()),
+            ("valid without kid", token(claims(), None), |r| r.is_ok()),
+            ("scope array", token(scope_array, None), |r| r.is_ok()),
+            ("unknown kid", token(claims(), Some("key-2")), |r| {
+                matches!(r, Err(AuthRejection::InvalidToken(_)))
+            }),
+            ("wrong audience", token(wrong_audience, None), |r| {
+                matches!(r, Err(AuthRejection::InvalidToken(_)))
+            }),
+            ("expired", token(expired, None), |r| {
+                matches!(r, Err(AuthRejection::InvalidToken(_)))
+            }),
+            ("missing scope", token(missing_scope, None), |r| {
+                *r == Err(AuthRejection::InsufficientScope)
+            }),
+            ("not a jwt", "opaque".to_string(), |r| {
+                matches!(r, Err(AuthRejection::InvalidToken(_)))
+            }),
+        ];
+        for (name, token, check) in cases {
+            let result = auth.authenticate(&headers(&format!("Bearer {token}")));
+            assert!(check(&result), "case '{name}': {result:?}");
+        }
+    }
+
+    #[test]
+    fn test_protected_resource_metadata_and_challenge() {
+        let auth = InboundAuth::default().with_oauth(oauth());
+        let oauth = auth.oauth().unwrap();
+        assert_eq!(
+            oauth.metadata_url(),
+            "https://mcp.example.com/.well-known/oauth-protected-resource/mcp"
+        );
+        let metadata = oauth.metadata();
+        assert_eq!(metadata["resource"], RESOURCE);
+        assert_eq!(metadata["authorization_servers"], json!([ISSUER]));
+        assert_eq!(metadata["scopes_supported"], json!(["boards.read"]));
+
+        assert_eq!(
+            auth.challenge(&AuthRejection::MissingToken),
+            "Bearer realm=\"mcp\", resource_metadata=\"https://mcp.example.com/.well-known/oauth-protected-resource/mcp\""
+        );
+        let challenge = auth.challenge(&AuthRejection::InsufficientScope);
+        assert!(
+            challenge
+                .to_str()
+                .unwrap()
+                .ends_with("error=\"insufficient_scope\", scope=\"boards.read\""),
+            "{challenge:?}"
+        );
+    }
+
+    #[test]
+    fn test_invalid_oauth_configuration() {
+        assert!(parse_jwks("{\"keys\": []}").is_err());
+        assert!(parse_jwks("not json").is_err());
+        assert!(matches!(
+            OAuthResource::new("mcp.example.com", ISSUER, parse_jwks(&jwks()).unwrap()),
+            Err(AuthConfigError::InvalidResource { .. })
+        ));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jwks.json");
+        std::fs::write(&path, "{\"keys\": [{\"kty\": \"unknown\"}]}").unwrap();
+        assert!(matches!(
+            OAuthResource::from_jwks_file(RESOURCE, ISSUER, &path),
+            Err(AuthConfigError::InvalidJwks { .. })
+        ));
+    }
+}
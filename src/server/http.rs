@@ -1,59 +1,348 @@
 use crate::mcp::server::AzureMcpServer;
+use crate::metrics;
+use crate::server::auth::{AuthRejection, InboundAuth, PROTECTED_RESOURCE_METADATA_PATH};
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full, combinators::BoxBody};
+use hyper::body::{Body, Frame, Incoming, SizeHint};
+use hyper::header::{CONTENT_TYPE, WWW_AUTHENTICATE};
+use hyper::{Method, Request, Response, StatusCode};
 use hyper_util::{
     rt::{TokioExecutor, TokioIo},
     server::conn::auto::Builder,
+    server::graceful::{GracefulShutdown, Watcher},
     service::TowerToHyperService,
 };
 use rmcp::transport::streamable_http_server::{
-    StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
+    SessionManager, StreamableHttpServerConfig, StreamableHttpService,
+    session::local::LocalSessionManager,
 };
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::sync::Semaphore;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::rustls::ServerConfig;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+use tokio_util::task::task_tracker::TaskTrackerToken;
+use tower::Service;
 
-const MAX_CONNECTIONS: usize = 256;
+pub const DEFAULT_MAX_CONNECTIONS: usize = 256;
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+const HEALTH_PATH: &str = "/healthz";
+const READY_PATH: &str = "/readyz";
+const METRICS_PATH: &str = "/metrics";
+const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+/// Longest time the readiness probe waits for a token before reporting the
+/// server as not ready
+const READY_TIMEOUT: Duration = Duration::from_secs(10);
+/// Longest time a client gets to complete the TLS handshake, during which
+/// its connection already holds one of the `max_connections` permits
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+type BoxResponse = Response<BoxBody<Bytes, Infallible>>;
+
+/// Settings of the HTTP transport
+#[derive(Debug)]
+pub struct HttpServerOptions {
+    /// Allowed `Host` header values; empty keeps rmcp's loopback-only default
+    pub allowed_hosts: Vec<String>,
+    /// Access control applied before requests reach the MCP service
+    pub auth: InboundAuth,
+    /// Connections served at once; further clients wait in the accept backlog
+    pub max_connections: usize,
+    /// Serve HTTPS with this configuration instead of plain HTTP
+    pub tls: Option<Arc<ServerConfig>>,
+    /// How long a shutdown waits for in-flight requests and connections
+    pub shutdown_timeout: Duration,
+}
+
+impl Default for HttpServerOptions {
+    fn default() -> Self {
+        Self {
+            allowed_hosts: Vec::new(),
+            auth: InboundAuth::default(),
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            tls: None,
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+        }
+    }
+}
 
 pub async fn run_server(
     server: AzureMcpServer,
     listener: tokio::net::TcpListener,
-    allowed_hosts: Vec<String>,
+    options: HttpServerOptions,
+) -> std::io::Result<()> {
+    run_server_with_shutdown(server, listener, options, std::future::pending()).await
+}
+
+/// Serves until `shutdown` completes, then stops accepting connections, lets
+/// in-flight requests finish, closes the MCP sessions (ending their SSE
+/// streams) and drains the open connections, all within
+/// `options.shutdown_timeout`.
+pub async fn run_server_with_shutdown(
+    server: AzureMcpServer,
+    listener: tokio::net::TcpListener,
+    options: HttpServerOptions,
+    shutdown: impl Future<Output = ()>,
 ) -> std::io::Result<()> {
     // Preserve rmcp's secure default (loopback-only `Host` validation, which
     // guards against DNS rebinding) unless the operator explicitly provides an
     // allow-list, in which case it fully replaces the default.
-    let mut config = StreamableHttpServerConfig::default();
-    if !allowed_hosts.is_empty() {
-        config = config.with_allowed_hosts(allowed_hosts);
+    let cancellation = CancellationToken::new();
+    let mut config =
+        StreamableHttpServerConfig::default().with_cancellation_token(cancellation.clone());
+    if !options.allowed_hosts.is_empty() {
+        config = config.with_allowed_hosts(options.allowed_hosts);
     }
 
-    let service = TowerToHyperService::new(StreamableHttpService::new(
-        move || Ok(server.clone()),
-        LocalSessionManager::default().into(),
-        config,
-    ));
+    let server = server.without_local_files();
+    let sessions = Arc::new(LocalSessionManager::default());
+    let probed_server = server.clone();
+    let mcp_service =
+        StreamableHttpService::new(move || Ok(server.new_session()), sessions.clone(), config);
+    let auth = Arc::new(options.auth);
+    // Tool calls are answered on the body of their POST, so a request counts
+    // as in flight until its response body has been sent or dropped.
+    let requests = TaskTracker::new();
+    let tracked_requests = requests.clone();
+    let service = TowerToHyperService::new(tower::service_fn(move |request: Request<_>| {
+        let auth = auth.clone();
+        let server = probed_server.clone();
+        let mut mcp_service = mcp_service.clone();
+        let in_flight = (request.method() != Method::GET).then(|| tracked_requests.token());
+        async move {
+            if let Some(response) = probe(&server, &request).await {
+                return Ok(response);
+            }
+            match guard(&auth, &request) {
+                Some(response) => Ok::<_, Infallible>(response),
+                None if request.method() == Method::GET && request.uri().path() == METRICS_PATH => {
+                    Ok(response(
+                        StatusCode::OK,
+                        METRICS_CONTENT_TYPE,
+                        metrics::render(),
+                    ))
+                }
+                None => {
+                    let response = mcp_service.call(request).await?;
+                    Ok(match in_flight {
+                        Some(token) => response.map(|body| {
+                            InFlightBody {
+                                body,
+                                _token: token,
+                            }
+                            .boxed()
+                        }),
+                        None => response,
+                    })
+                }
+            }
+        }
+    }));
 
-    let semaphore = Arc::new(Semaphore::new(MAX_CONNECTIONS));
+    let tls = options.tls.map(TlsAcceptor::from);
+    let semaphore = Arc::new(Semaphore::new(options.max_connections));
+    let connections = GracefulShutdown::new();
+    let builder = Builder::new(TokioExecutor::new());
+    tokio::pin!(shutdown);
 
     loop {
-        let (stream, _) = listener.accept().await?;
-        let permit = match semaphore.clone().acquire_owned().await {
-            Ok(permit) => permit,
-            Err(e) => {
-                log::error!("Failed to acquire connection permit: {:?}", e);
-                continue;
+        let permit = tokio::select! {
+            permit = semaphore.clone().acquire_owned() => {
+                permit.expect("the connection semaphore is never closed")
             }
+            () = &mut shutdown => break,
+        };
+        let stream = tokio::select! {
+            accepted = listener.accept() => accepted?.0,
+            () = &mut shutdown => break,
         };
-        let io = TokioIo::new(stream);
         let service = service.clone();
+        let builder = builder.clone();
+        let watcher = connections.watcher();
+        let tls = tls.clone();
 
         tokio::spawn(async move {
-            if let Err(err) = Builder::new(TokioExecutor::default())
-                .serve_connection(io, service)
-                .await
-            {
-                log::error!("Error serving connection: {:?}", err);
+            match tls {
+                Some(tls) => match tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, tls.accept(stream))
+                    .await
+                {
+                    Ok(Ok(stream)) => serve_connection(&builder, watcher, stream, service).await,
+                    Ok(Err(err)) => log::warn!("TLS handshake failed: {err}"),
+                    Err(_) => log::warn!(
+                        "TLS handshake not completed within {}s",
+                        TLS_HANDSHAKE_TIMEOUT.as_secs()
+                    ),
+                },
+                None => serve_connection(&builder, watcher, stream, service).await,
             }
 
             drop(permit);
         });
     }
+
+    drop(listener);
+    let deadline = tokio::time::Instant::now() + options.shutdown_timeout;
+    log::info!(
+        "Shutting down, waiting up to {:?} for in-flight requests",
+        options.shutdown_timeout
+    );
+    requests.close();
+    if tokio::time::timeout_at(deadline, requests.wait())
+        .await
+        .is_err()
+    {
+        log::warn!(
+            "Shutdown timeout reached with {} requests still in flight",
+            requests.len()
+        );
+    }
+
+    let session_ids: Vec<_> = sessions.sessions.read().await.keys().cloned().collect();
+    for session_id in session_ids {
+        if let Err(err) = sessions.close_session(&session_id).await {
+            log::warn!("Failed to close session {session_id}: {err}");
+        }
+    }
+    cancellation.cancel();
+    if tokio::time::timeout_at(deadline, connections.shutdown())
+        .await
+        .is_err()
+    {
+        log::warn!("Shutdown timeout reached before all connections were closed");
+    }
+    Ok(())
+}
+
+/// Response body of a request counted as in flight until the body has been
+/// sent or dropped
+struct InFlightBody<B> {
+    body: B,
+    /// Held only to be released with the body
+    _token: TaskTrackerToken,
+}
+
+impl<B: Body + Unpin> Body for InFlightBody<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        Pin::new(&mut self.body).poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.body.size_hint()
+    }
+}
+
+async fn serve_connection<I, S>(
+    builder: &Builder<TokioExecutor>,
+    watcher: Watcher,
+    io: I,
+    service: S,
+) where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    S: hyper::service::Service<Request<Incoming>, Response = BoxResponse> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    let connection = builder.serve_connection(TokioIo::new(io), service);
+    if let Err(err) = watcher.watch(connection.into_owned()).await {
+        log::error!("Error serving connection: {:?}", err);
+    }
+}
+
+/// Answers the orchestrator probes, which are served without authentication:
+/// `/healthz` reports that the process is up, `/readyz` that a credential of
+/// the chain can mint a token.
+async fn probe<B>(server: &AzureMcpServer, request: &Request<B>) -> Option<BoxResponse> {
+    if request.method() != Method::GET {
+        return None;
+    }
+    match request.uri().path() {
+        HEALTH_PATH => Some(response(StatusCode::OK, "text/plain", "ok".to_string())),
+        READY_PATH => Some(
+            match tokio::time::timeout(READY_TIMEOUT, server.check_ready()).await {
+                Ok(Ok(())) => response(StatusCode::OK, "text/plain", "ready".to_string()),
+                Ok(Err(e)) => {
+                    // The reason is only logged: probes are unauthenticated.
+                    log::warn!("Readiness check failed: {e}");
+                    response(
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        "text/plain",
+                        "not ready".to_string(),
+                    )
+                }
+                Err(_) => {
+                    log::warn!("Readiness check timed out acquiring a token");
+                    response(
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        "text/plain",
+                        "not ready".to_string(),
+                    )
+                }
+            },
+        ),
+        _ => None,
+    }
+}
+
+/// Answers the requests that must not reach the MCP service: the protected
+/// resource metadata, which is public, and requests that fail authentication.
+fn guard<B>(auth: &InboundAuth, request: &Request<B>) -> Option<BoxResponse> {
+    if let Some(oauth) = auth.oauth()
+        && request
+            .uri()
+            .path()
+            .starts_with(PROTECTED_RESOURCE_METADATA_PATH)
+    {
+        return Some(response(
+            StatusCode::OK,
+            "application/json",
+            oauth.metadata().to_string(),
+        ));
+    }
+
+    let rejection = auth.authenticate(request.headers()).err()?;
+    let status = match &rejection {
+        AuthRejection::MissingToken => StatusCode::UNAUTHORIZED,
+        AuthRejection::InvalidToken(reason) => {
+            log::warn!("Rejected HTTP request with an invalid token: {reason}");
+            StatusCode::UNAUTHORIZED
+        }
+        AuthRejection::InsufficientScope => StatusCode::FORBIDDEN,
+    };
+    let mut response = response(
+        status,
+        "text/plain",
+        status.canonical_reason().unwrap_or_default().to_string(),
+    );
+    response
+        .headers_mut()
+        .insert(WWW_AUTHENTICATE, auth.challenge(&rejection));
+    Some(response)
+}
+
+fn response(status: StatusCode, content_type: &'static str, body: String) -> BoxResponse {
+    let mut response = Response::new(Full::new(Bytes::from(body)).boxed());
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        CONTENT_TYPE,
+        content_type.parse().expect("valid content type"),
+    );
+    response
 }
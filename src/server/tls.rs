@@ -0,0 +1,91 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio_rustls::rustls::ServerConfig;
+use tokio_rustls::rustls::crypto::aws_lc_rs;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+
+#[derive(Debug, thiserror::Error)]
+pub enum TlsConfigError {
+    #[error("Failed to read certificates from {}: {message}", path.display())]
+    Certificates { path: PathBuf, message: String },
+
+    #[error("Failed to read private key from {}: {message}", path.display())]
+    PrivateKey { path: PathBuf, message: String },
+
+    #[error("Invalid TLS configuration: {0}")]
+    Config(String),
+}
+
+/// Builds the rustls configuration of the HTTP server from a PEM certificate
+/// chain (leaf first) and a PEM private key (PKCS#8, PKCS#1 or SEC1). Both
+/// HTTP/2 and HTTP/1.1 are offered through ALPN.
+pub fn load_server_config(
+    cert_path: &Path,
+    key_path: &Path,
+) -> Result<Arc<ServerConfig>, TlsConfigError> {
+    let certificates = CertificateDer::pem_file_iter(cert_path)
+        .and_then(|certificates| certificates.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| TlsConfigError::Certificates {
+            path: cert_path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+    if certificates.is_empty() {
+        return Err(TlsConfigError::Certificates {
+            path: cert_path.to_path_buf(),
+            message: "no certificate found".to_string(),
+        });
+    }
+    let key = PrivateKeyDer::from_pem_file(key_path).map_err(|e| TlsConfigError::PrivateKey {
+        path: key_path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+
+    // The provider is chosen explicitly: rustls cannot pick a default when
+    // several crypto backends are enabled in the dependency graph.
+    let mut config = ServerConfig::builder_with_provider(Arc::new(aws_lc_rs::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| TlsConfigError::Config(e.to_string()))?
+        .with_no_client_auth()
+        .with_single_cert(certificates, key)
+        .map_err(|e| TlsConfigError::Config(e.to_string()))?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(Arc::new(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/tls")
+            .join(name)
+    }
+
+    #[test]
+    fn test_load_server_config() {
+        let config = load_server_config(&fixture("localhost.crt"), &fixture("localhost.key"))
+            .expect("fixture certificate and key must load");
+        assert_eq!(
+            config.alpn_protocols,
+            vec![b"h2".to_vec(), b"http/1.1".to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_load_server_config_errors() {
+        assert!(matches!(
+            load_server_config(&fixture("missing.crt"), &fixture("localhost.key")),
+            Err(TlsConfigError::Certificates { .. })
+        ));
+        assert!(matches!(
+            load_server_config(&fixture("localhost.key"), &fixture("localhost.key")),
+            Err(TlsConfigError::Certificates { .. })
+        ));
+        assert!(matches!(
+            load_server_config(&fixture("localhost.crt"), &fixture("localhost.crt")),
+            Err(TlsConfigError::PrivateKey { .. })
+        ));
+    }
+}
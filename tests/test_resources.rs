@@ -0,0 +1,207 @@
+#[cfg(feature = "test-support")]
+mod tests {
+    use mcp_for_azure_devops_boards::azure::api_trait::{AzureDevOpsApi, MockAzureDevOpsApi};
+    use mcp_for_azure_devops_boards::azure::client::AzureError;
+    use mcp_for_azure_devops_boards::azure::iterations::{
+        IterationAttributes, TeamSettingsIteration,
+    };
+    use mcp_for_azure_devops_boards::azure::models::{WorkItem, WorkItemQueryPage};
+    use mcp_for_azure_devops_boards::mcp::resources::{
+        MAX_SUBSCRIPTIONS_PER_SESSION, Subscriptions, read_resource,
+    };
+    use mcp_for_azure_devops_boards::mcp::tools::support::UNTRUSTED_CONTENT_WARNING;
+    use rmcp::model::{ErrorCode, ResourceContents};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    fn work_item(id: u32, title: &str) -> WorkItem {
+        let mut fields = HashMap::new();
+        fields.insert("System.Title".to_string(), serde_json::json!(title));
+        fields.insert("System.WorkItemType".to_string(), serde_json::json!("Bug"));
+        WorkItem {
+            id,
+            fields,
+            url: None,
+            comments: None,
+        }
+    }
+
+    fn text(contents: &ResourceContents) -> &str {
+        match contents {
+            ResourceContents::TextResourceContents { text, .. } => text,
+            _ => panic!("expected text contents"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_work_item_resource() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_work_item()
+            .withf(|organization, project, id, _| {
+                organization == "contoso" && project == "My Project" && *id == 42
+            })
+            .returning(|_, _, id, _| Ok(Some(work_item(id, "Login fails"))));
+
+        let result = read_resource(&mock, "azdo://contoso/My%20Project/workitems/42")
+            .await
+            .unwrap();
+        let text = text(&result.contents[0]);
+        assert!(text.starts_with(UNTRUSTED_CONTENT_WARNING));
+        assert!(text.contains("Login fails"), "{text}");
+    }
+
+    #[tokio::test]
+    async fn test_read_iteration_resource_lists_its_work_items() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_team_current_iteration()
+            .returning(|_, _, _| {
+                Ok(Some(TeamSettingsIteration {
+                    id: "it-1".to_string(),
+                    name: "Sprint 7".to_string(),
+                    path: "Fabrikam\\Sprint 7".to_string(),
+                    attributes: IterationAttributes {
+                        start_date: Some("2026-10-05T00:00:00Z".to_string()),
+                        finish_date: Some("2026-10-16T00:00:00Z".to_string()),
+                        time_frame: None,
+                    },
+                    url: String::new(),
+                }))
+            });
+        mock.expect_query_work_items()
+            .withf(|_, _, query, _, _, _| {
+                query.contains("[System.IterationPath] = 'Fabrikam\\Sprint 7'")
+            })
+            .returning(|_, _, _, skip, _, _| {
+                Ok(WorkItemQueryPage {
+                    work_items: vec![work_item(7, "Ship it")],
+                    total_count: 1,
+                    skip,
+                    truncated: false,
+                    links: Vec::new(),
+                })
+            });
+
+        let result = read_resource(
+            &mock,
+            "azdo://contoso/Fabrikam/teams/Team/iterations/current",
+        )
+        .await
+        .unwrap();
+        let text = text(&result.contents[0]);
+        assert!(text.contains("iteration=Sprint 7"), "{text}");
+        assert!(text.contains("start_date=2026-10-05"), "{text}");
+        assert!(text.contains("Ship it"), "{text}");
+    }
+
+    #[tokio::test]
+    async fn test_read_unknown_resource() {
+        let mock = MockAzureDevOpsApi::new();
+        let error = read_resource(&mock, "azdo://contoso/Fabrikam/wiki/1")
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::RESOURCE_NOT_FOUND);
+
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_work_item().returning(|_, _, _, _| Ok(None));
+        let error = read_resource(&mock, "azdo://contoso/Fabrikam/workitems/404")
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::RESOURCE_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions_report_changed_resources() {
+        let revision = Arc::new(AtomicU32::new(1));
+        let current = revision.clone();
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_work_item().returning(move |_, _, id, _| {
+            let title = format!("Revision {}", current.load(Ordering::SeqCst));
+            Ok(Some(work_item(id, &title)))
+        });
+        let client: Arc<dyn AzureDevOpsApi + Send + Sync> = Arc::new(mock);
+
+        let subscriptions = Subscriptions::new(Duration::from_secs(60));
+        let uri = "azdo://contoso/Fabrikam/workitems/1";
+        subscriptions.watch(uri, client.clone()).await.unwrap();
+        assert!(subscriptions.poll_changes().await.is_empty());
+
+        revision.store(2, Ordering::SeqCst);
+        assert_eq!(subscriptions.poll_changes().await, vec![uri.to_string()]);
+        assert!(subscriptions.poll_changes().await.is_empty());
+
+        subscriptions.unwatch(uri);
+        revision.store(3, Ordering::SeqCst);
+        assert!(subscriptions.poll_changes().await.is_empty());
+        assert!(subscriptions.is_empty());
+
+        let error = subscriptions
+            .watch("azdo://contoso/Fabrikam", client)
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::RESOURCE_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions_are_capped_per_session() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_work_item()
+            .returning(|_, _, id, _| Ok(Some(work_item(id, "Title"))));
+        let client: Arc<dyn AzureDevOpsApi + Send + Sync> = Arc::new(mock);
+
+        let subscriptions = Subscriptions::new(Duration::from_secs(60));
+        for id in 0..MAX_SUBSCRIPTIONS_PER_SESSION {
+            let uri = format!("azdo://contoso/Fabrikam/workitems/{id}");
+            subscriptions.watch(&uri, client.clone()).await.unwrap();
+        }
+        let error = subscriptions
+            .watch("azdo://contoso/Fabrikam/workitems/1000", client.clone())
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+
+        // Subscribing again to a watched resource does not count twice
+        subscriptions
+            .watch("azdo://contoso/Fabrikam/workitems/0", client.clone())
+            .await
+            .unwrap();
+        subscriptions.unwatch("azdo://contoso/Fabrikam/workitems/0");
+        subscriptions
+            .watch("azdo://contoso/Fabrikam/workitems/1000", client)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions_are_dropped_after_repeated_auth_failures() {
+        let revoked = Arc::new(AtomicU32::new(0));
+        let current = revoked.clone();
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_work_item().returning(move |_, _, id, _| {
+            if current.load(Ordering::SeqCst) == 1 {
+                Err(AzureError::Unauthorized("token expired".to_string()))
+            } else {
+                Ok(Some(work_item(id, "Title")))
+            }
+        });
+        let client: Arc<dyn AzureDevOpsApi + Send + Sync> = Arc::new(mock);
+
+        let subscriptions = Subscriptions::new(Duration::from_secs(60));
+        let uri = "azdo://contoso/Fabrikam/workitems/1";
+        subscriptions.watch(uri, client).await.unwrap();
+
+        revoked.store(1, Ordering::SeqCst);
+        assert!(subscriptions.poll_changes().await.is_empty());
+        assert!(subscriptions.poll_changes().await.is_empty());
+        // A successful read resets the count
+        revoked.store(0, Ordering::SeqCst);
+        assert!(subscriptions.poll_changes().await.is_empty());
+        revoked.store(1, Ordering::SeqCst);
+        assert!(subscriptions.poll_changes().await.is_empty());
+        assert!(subscriptions.poll_changes().await.is_empty());
+        assert!(!subscriptions.is_empty());
+        assert!(subscriptions.poll_changes().await.is_empty());
+        assert!(subscriptions.is_empty());
+    }
+}
@@ -1,8 +1,22 @@
 #[cfg(feature = "test-support")]
 mod tests {
+    use mcp_for_azure_devops_boards::azure::api_trait::AzureDevOpsApi;
     use mcp_for_azure_devops_boards::azure::api_trait::MockAzureDevOpsApi;
-    use mcp_for_azure_devops_boards::mcp::server::AzureMcpServer;
-    use mcp_for_azure_devops_boards::server::http;
+    use mcp_for_azure_devops_boards::azure::client::AzureError;
+    use mcp_for_azure_devops_boards::azure::models::WorkItem;
+    use mcp_for_azure_devops_boards::azure::projects::Project;
+    use mcp_for_azure_devops_boards::mcp::defaults::ToolDefaults;
+    use mcp_for_azure_devops_boards::mcp::server::{AzureMcpServer, CallerClientFactory};
+    use mcp_for_azure_devops_boards::mcp::tool_filter::ToolFilter;
+    use mcp_for_azure_devops_boards::server::auth::{InboundAuth, OAuthResource};
+    use mcp_for_azure_devops_boards::server::http::{self, HttpServerOptions};
+    use mcp_for_azure_devops_boards::server::tls::load_server_config;
+    use serde_json::{Value, json};
+    use std::collections::HashMap;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
 
     #[tokio::test]
     async fn test_http_server_accepts_connection() {
@@ -13,7 +27,7 @@ mod tests {
         let addr = listener.local_addr().unwrap();
 
         let server_handle = tokio::spawn(async move {
-            let _ = http::run_server(server, listener, Vec::new()).await;
+            let _ = http::run_server(server, listener, HttpServerOptions::default()).await;
         });
 
         tokio::time::sleep(std::time::Duration::from_millis(100)).await;
@@ -46,7 +60,7 @@ mod tests {
         let addr = listener.local_addr().unwrap();
 
         let server_handle = tokio::spawn(async move {
-            let _ = http::run_server(server, listener, Vec::new()).await;
+            let _ = http::run_server(server, listener, HttpServerOptions::default()).await;
         });
 
         tokio::time::sleep(std::time::Duration::from_millis(100)).await;
@@ -76,7 +90,7 @@ mod tests {
         let addr = listener.local_addr().unwrap();
 
         let server_handle = tokio::spawn(async move {
-            let _ = http::run_server(server, listener, Vec::new()).await;
+            let _ = http::run_server(server, listener, HttpServerOptions::default()).await;
         });
 
         tokio::time::sleep(std::time::Duration::from_millis(100)).await;
@@ -112,7 +126,15 @@ mod tests {
         let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
         let addr = listener.local_addr().unwrap();
         tokio::spawn(async move {
-            let _ = http::run_server(server, listener, allowed_hosts).await;
+            let _ = http::run_server(
+                server,
+                listener,
+                HttpServerOptions {
+                    allowed_hosts,
+                    ..Default::default()
+                },
+            )
+            .await;
         });
         addr
     }
@@ -283,4 +305,924 @@ mod tests {
             );
         }
     }
+
+    fn project_mock(name: &'static str) -> MockAzureDevOpsApi {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_list_projects().returning(move |_| {
+            Ok(vec![Project {
+                id: "proj-1".to_string(),
+                name: name.to_string(),
+                description: None,
+                url: format!("https://dev.azure.com/org/{name}"),
+                state: "wellFormed".to_string(),
+                visibility: None,
+            }])
+        });
+        mock
+    }
+
+    /// Sends a JSON-RPC message within `session` and returns the response
+    /// message, which rmcp streams as a single SSE `data:` event.
+    async fn rpc(
+        client: &reqwest::Client,
+        addr: std::net::SocketAddr,
+        session: Option<&str>,
+        authorization: Option<&str>,
+        message: Value,
+    ) -> (Option<String>, Option<Value>) {
+        let mut request = client
+            .post(format!("http://{}/mcp", addr))
+            .header("Content-Type", "application/json")
+            .header("Accept", "application/json, text/event-stream")
+            .body(message.to_string());
+        if let Some(session) = session {
+            request = request.header("Mcp-Session-Id", session);
+        }
+        if let Some(authorization) = authorization {
+            request = request.header("Authorization", authorization);
+        }
+        let response = request.send().await.unwrap();
+        assert!(
+            response.status().is_success(),
+            "request {message} failed with {}",
+            response.status()
+        );
+        let session = response
+            .headers()
+            .get("Mcp-Session-Id")
+            .map(|value| value.to_str().unwrap().to_string());
+        let body = response.text().await.unwrap();
+        let data = body
+            .lines()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .map(str::trim)
+            .find(|data| !data.is_empty())
+            .map(|data| serde_json::from_str(data).unwrap());
+        (session, data)
+    }
+
+    async fn call_list_projects(
+        client: &reqwest::Client,
+        addr: std::net::SocketAddr,
+        session: &str,
+        authorization: Option<&str>,
+    ) -> Value {
+        let call = json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {"name": "azdo_list_projects", "arguments": {"organization": "org"}}
+        });
+        rpc(client, addr, Some(session), authorization, call)
+            .await
+            .1
+            .expect("tools/call must be answered")
+    }
+
+    #[tokio::test]
+    async fn test_auth_passthrough_uses_caller_credentials() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let recorded = received.clone();
+        let factory: CallerClientFactory = Arc::new(move |authorization| {
+            recorded
+                .lock()
+                .unwrap()
+                .push(authorization.as_header().to_str().unwrap().to_string());
+            Arc::new(project_mock("CallerProject")) as Arc<dyn AzureDevOpsApi + Send + Sync>
+        });
+        let server = AzureMcpServer::new_with_api(project_mock("ServerProject"))
+            .with_caller_clients(factory);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = http::run_server(server, listener, HttpServerOptions::default()).await;
+        });
+
+        let client = reqwest::Client::new();
+        let initialize = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": {"name": "test", "version": "0.1"}
+            }
+        });
+        let (session, response) = rpc(&client, addr, None, None, initialize).await;
+        let session = session.expect("initialize must assign a session");
+        let capabilities = &response.unwrap()["result"]["capabilities"];
+        assert!(
+            capabilities["resources"].get("subscribe").is_none(),
+            "{capabilities}"
+        );
+        let initialized = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        rpc(&client, addr, Some(&session), None, initialized).await;
+
+        // A personal access token sent with basic authentication is
+        // forwarded as it is.
+        let response =
+            call_list_projects(&client, addr, &session, Some("Basic OmNhbGxlci1wYXQ=")).await;
+        assert!(
+            response.to_string().contains("CallerProject"),
+            "the call must run with the caller's client: {response}"
+        );
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec!["Basic OmNhbGxlci1wYXQ=".to_string()]
+        );
+
+        // Missing or malformed credentials are rejected instead of silently
+        // falling back to the server's identity.
+        for authorization in [None, Some("Bearer ")] {
+            let response = call_list_projects(&client, addr, &session, authorization).await;
+            assert_eq!(
+                response["error"]["code"], -32001,
+                "unexpected response: {response}"
+            );
+        }
+        assert_eq!(received.lock().unwrap().len(), 1);
+
+        // Polling would keep the caller's credentials for the whole session
+        let (_, response) = rpc(
+            &client,
+            addr,
+            Some(&session),
+            Some("Basic OmNhbGxlci1wYXQ="),
+            subscribe_message("azdo://org/proj/workitems/1"),
+        )
+        .await;
+        let response = response.expect("resources/subscribe must be answered");
+        assert!(
+            response["error"]["message"]
+                .as_str()
+                .is_some_and(|message| message.contains("Subscriptions are not available")),
+            "{response}"
+        );
+        assert_eq!(received.lock().unwrap().len(), 1);
+    }
+
+    fn subscribe_message(uri: &str) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": 4,
+            "method": "resources/subscribe",
+            "params": {"uri": uri}
+        })
+    }
+
+    fn work_item(id: u32) -> WorkItem {
+        WorkItem {
+            id,
+            fields: HashMap::from([("System.Title".to_string(), json!("Title"))]),
+            url: None,
+            comments: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_auth_passthrough_fallback_uses_server_identity() {
+        let factory: CallerClientFactory = Arc::new(|_| {
+            // The caller can read work item 1 only
+            let mut caller = project_mock("CallerProject");
+            caller
+                .expect_get_work_item()
+                .returning(|_, _, id, _| Ok((id == 1).then(|| work_item(id))));
+            Arc::new(caller) as Arc<dyn AzureDevOpsApi + Send + Sync>
+        });
+        let server_reads = Arc::new(AtomicUsize::new(0));
+        let counted = server_reads.clone();
+        let mut server_mock = project_mock("ServerProject");
+        server_mock
+            .expect_get_work_item()
+            .returning(move |_, _, id, _| {
+                counted.fetch_add(1, Ordering::SeqCst);
+                Ok(Some(work_item(id)))
+            });
+        let server = AzureMcpServer::new_with_api(server_mock)
+            .with_caller_clients(factory)
+            .with_server_identity_fallback(true);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = http::run_server(server, listener, HttpServerOptions::default()).await;
+        });
+
+        let client = reqwest::Client::new();
+        let (session, _) = rpc(&client, addr, None, None, initialize_message()).await;
+        let session = session.expect("initialize must assign a session");
+        let initialized = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        rpc(&client, addr, Some(&session), None, initialized).await;
+
+        let response = call_list_projects(&client, addr, &session, None).await;
+        assert!(
+            response.to_string().contains("ServerProject"),
+            "the call must fall back to the server's client: {response}"
+        );
+        let response =
+            call_list_projects(&client, addr, &session, Some("Bearer access-token")).await;
+        assert!(
+            response.to_string().contains("CallerProject"),
+            "the call must run with the caller's client: {response}"
+        );
+
+        // Subscriptions are polled with the server's identity, and only for
+        // resources the caller can read
+        let (_, response) = rpc(
+            &client,
+            addr,
+            Some(&session),
+            Some("Bearer access-token"),
+            subscribe_message("azdo://org/proj/workitems/2"),
+        )
+        .await;
+        assert!(response.unwrap().get("error").is_some());
+        assert_eq!(server_reads.load(Ordering::SeqCst), 0);
+        let (_, response) = rpc(
+            &client,
+            addr,
+            Some(&session),
+            Some("Bearer access-token"),
+            subscribe_message("azdo://org/proj/workitems/1"),
+        )
+        .await;
+        let response = response.unwrap();
+        assert!(response.get("error").is_none(), "{response}");
+        assert_eq!(server_reads.load(Ordering::SeqCst), 1);
+    }
+
+    async fn spawn_server_with_auth(auth: InboundAuth) -> std::net::SocketAddr {
+        let server = AzureMcpServer::new_with_api(MockAzureDevOpsApi::new());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let options = HttpServerOptions {
+                auth,
+                ..Default::default()
+            };
+            let _ = http::run_server(server, listener, options).await;
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_static_token_auth_rejects_before_mcp_handling() {
+        let auth = InboundAuth::default().with_static_tokens(vec!["team-token".to_string()]);
+        let addr = spawn_server_with_auth(auth).await;
+        let client = reqwest::Client::new();
+
+        let missing = initialize_request(&client, addr, "localhost")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(missing.status().as_u16(), 401);
+        assert_eq!(
+            missing.headers()["www-authenticate"],
+            "Bearer realm=\"mcp\""
+        );
+
+        let wrong = initialize_request(&client, addr, "localhost")
+            .bearer_auth("other-token")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(wrong.status().as_u16(), 401);
+        assert_eq!(
+            wrong.headers()["www-authenticate"],
+            "Bearer realm=\"mcp\", error=\"invalid_token\""
+        );
+
+        // Authentication runs before the MCP service, so even a request the
+        // service would reject (GET without a session) gets the 401 first.
+        let unauthenticated_get = client
+            .get(format!("http://{}/mcp", addr))
+            .header("Accept", "text/event-stream")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(unauthenticated_get.status().as_u16(), 401);
+
+        let accepted = initialize_request(&client, addr, "localhost")
+            .bearer_auth("team-token")
+            .send()
+            .await
+            .unwrap();
+        assert!(
+            accepted.status().is_success(),
+            "an allowed token must reach MCP handling, got {}",
+            accepted.status()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oauth_protected_resource_metadata_is_public() {
+        let dir = tempfile::tempdir().unwrap();
+        let jwks_path = dir.path().join("jwks.json");
+        std::fs::write(
+            &jwks_path,
+            r#"{"keys":[{"kty":"oct","kid":"k1","alg":"HS256","k":"c2VjcmV0"}]}"#,
+        )
+        .unwrap();
+        let oauth = OAuthResource::from_jwks_file(
+            "http://mcp.example.com/mcp",
+            "https://login.example.com/tenant/v2.0",
+            &jwks_path,
+        )
+        .unwrap();
+        let addr = spawn_server_with_auth(InboundAuth::default().with_oauth(oauth)).await;
+        let client = reqwest::Client::new();
+
+        let rejected = initialize_request(&client, addr, "localhost")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(rejected.status().as_u16(), 401);
+        assert_eq!(
+            rejected.headers()["www-authenticate"],
+            "Bearer realm=\"mcp\", resource_metadata=\"http://mcp.example.com/.well-known/oauth-protected-resource/mcp\""
+        );
+
+        let metadata: Value = client
+            .get(format!(
+                "http://{}/.well-known/oauth-protected-resource/mcp",
+                addr
+            ))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(metadata["resource"], "http://mcp.example.com/mcp");
+        assert_eq!(
+            metadata["authorization_servers"],
+            json!(["https://login.example.com/tenant/v2.0"])
+        );
+    }
+
+    fn initialize_message() -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": {"name": "test", "version": "0.1"}
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_tls_serves_https() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tls");
+        let tls = load_server_config(
+            &fixtures.join("localhost.crt"),
+            &fixtures.join("localhost.key"),
+        )
+        .unwrap();
+        let server = AzureMcpServer::new_with_api(MockAzureDevOpsApi::new());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let options = HttpServerOptions {
+                tls: Some(tls),
+                ..Default::default()
+            };
+            let _ = http::run_server(server, listener, options).await;
+        });
+
+        let client = reqwest::Client::builder()
+            .tls_danger_accept_invalid_certs(true)
+            .build()
+            .unwrap();
+        let response = client
+            .post(format!("https://localhost:{}/mcp", addr.port()))
+            .header("Content-Type", "application/json")
+            .header("Accept", "application/json, text/event-stream")
+            .body(initialize_message().to_string())
+            .send()
+            .await
+            .unwrap();
+        assert!(
+            response.status().is_success(),
+            "initialize over HTTPS must succeed, got {}",
+            response.status()
+        );
+
+        let plain = reqwest::Client::new()
+            .post(format!("http://localhost:{}/mcp", addr.port()))
+            .send()
+            .await;
+        assert!(plain.is_err(), "plain HTTP must not be served");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_graceful_shutdown_finishes_in_flight_tool_calls() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_list_projects().returning(|_| {
+            std::thread::sleep(Duration::from_millis(500));
+            Ok(Vec::new())
+        });
+        let server = AzureMcpServer::new_with_api(mock);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let server_task = tokio::spawn(async move {
+            let shutdown = async {
+                let _ = shutdown_rx.await;
+            };
+            http::run_server_with_shutdown(server, listener, HttpServerOptions::default(), shutdown)
+                .await
+        });
+
+        let client = reqwest::Client::new();
+        let (session, _) = rpc(&client, addr, None, None, initialize_message()).await;
+        let session = session.expect("initialize must assign a session");
+        let initialized = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        rpc(&client, addr, Some(&session), None, initialized).await;
+
+        let call = {
+            let client = client.clone();
+            let session = session.clone();
+            tokio::spawn(async move { call_list_projects(&client, addr, &session, None).await })
+        };
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        shutdown_tx.send(()).unwrap();
+
+        let response = call.await.unwrap();
+        assert!(
+            response.get("result").is_some(),
+            "the in-flight tool call must complete during shutdown: {response}"
+        );
+        tokio::time::timeout(Duration::from_secs(5), server_task)
+            .await
+            .expect("the server must stop after draining")
+            .unwrap()
+            .unwrap();
+        assert!(
+            tokio::net::TcpStream::connect(addr).await.is_err(),
+            "no connections are accepted after shutdown"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_health_and_readiness_probes() {
+        let mut mock = MockAzureDevOpsApi::new();
+        let mut ready = false;
+        mock.expect_check_credentials().returning(move || {
+            // Not ready on the first probe, ready afterwards.
+            let result = if ready {
+                Ok(())
+            } else {
+                Err(AzureError::Unauthorized("no credential".to_string()))
+            };
+            ready = true;
+            result
+        });
+        let server = AzureMcpServer::new_with_api(mock);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let options = HttpServerOptions {
+                auth: InboundAuth::default().with_static_tokens(vec!["token".to_string()]),
+                ..Default::default()
+            };
+            let _ = http::run_server(server, listener, options).await;
+        });
+        let client = reqwest::Client::new();
+        let get = |path: &str| client.get(format!("http://{}{}", addr, path)).send();
+
+        // Probes are answered without authentication.
+        assert_eq!(get("/healthz").await.unwrap().status().as_u16(), 200);
+        let not_ready = get("/readyz").await.unwrap();
+        assert_eq!(not_ready.status().as_u16(), 503);
+        assert_eq!(not_ready.text().await.unwrap(), "not ready");
+        assert_eq!(get("/readyz").await.unwrap().status().as_u16(), 200);
+
+        // Metrics require the same credentials as the MCP endpoint.
+        assert_eq!(get("/metrics").await.unwrap().status().as_u16(), 401);
+        let metrics = client
+            .get(format!("http://{}/metrics", addr))
+            .bearer_auth("token")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(metrics.status().as_u16(), 200);
+        assert!(
+            metrics
+                .text()
+                .await
+                .unwrap()
+                .contains("# TYPE mcp_tool_calls_total counter")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_passthrough_only_server_is_ready_without_own_credentials() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_check_credentials()
+            .returning(|| Err(AzureError::Unauthorized("no credential".to_string())));
+        let factory: CallerClientFactory = Arc::new(|_| {
+            Arc::new(MockAzureDevOpsApi::new()) as Arc<dyn AzureDevOpsApi + Send + Sync>
+        });
+        let server = AzureMcpServer::new_with_api(mock).with_caller_clients(factory);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = http::run_server(server, listener, HttpServerOptions::default()).await;
+        });
+
+        let ready = reqwest::get(format!("http://{}/readyz", addr))
+            .await
+            .unwrap();
+        assert_eq!(ready.status().as_u16(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_count_tool_calls() {
+        let server = AzureMcpServer::new_with_api(project_mock("MetricsProject"));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = http::run_server(server, listener, HttpServerOptions::default()).await;
+        });
+
+        let client = reqwest::Client::new();
+        let (session, _) = rpc(&client, addr, None, None, initialize_message()).await;
+        let session = session.expect("initialize must assign a session");
+        let initialized = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        rpc(&client, addr, Some(&session), None, initialized).await;
+        call_list_projects(&client, addr, &session, None).await;
+
+        let metrics = client
+            .get(format!("http://{}/metrics", addr))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(
+            metrics.contains("mcp_tool_calls_total{tool=\"azdo_list_projects\"}")
+                && metrics
+                    .contains("mcp_tool_call_duration_seconds_count{tool=\"azdo_list_projects\"}"),
+            "tool calls must be counted:\n{metrics}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tool_filter_hides_tools_from_list_and_call() {
+        let filter = ToolFilter {
+            read_only: true,
+            enabled: vec!["azdo_list_*".to_string(), "azdo_*_work_item".to_string()],
+            disabled: vec!["azdo_list_tags".to_string()],
+        };
+        let server =
+            AzureMcpServer::new_with_api(project_mock("FilteredProject")).with_tool_filter(&filter);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = http::run_server(server, listener, HttpServerOptions::default()).await;
+        });
+
+        let client = reqwest::Client::new();
+        let (session, _) = rpc(&client, addr, None, None, initialize_message()).await;
+        let session = session.expect("initialize must assign a session");
+        let initialized = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        rpc(&client, addr, Some(&session), None, initialized).await;
+
+        let list = json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"});
+        let (_, response) = rpc(&client, addr, Some(&session), None, list).await;
+        let tools = response.expect("tools/list must be answered")["result"]["tools"].clone();
+        let names: Vec<&str> = tools
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tool| tool["name"].as_str().unwrap())
+            .collect();
+        assert!(names.contains(&"azdo_list_projects"), "{names:?}");
+        assert!(names.contains(&"azdo_get_work_item"), "{names:?}");
+        for hidden in [
+            "azdo_list_tags",
+            "azdo_create_work_item",
+            "azdo_update_work_item",
+            "azdo_query_work_items",
+        ] {
+            assert!(
+                !names.contains(&hidden),
+                "{hidden} must be hidden: {names:?}"
+            );
+        }
+        assert!(
+            tools
+                .as_array()
+                .unwrap()
+                .iter()
+                .all(|tool| tool["annotations"]["readOnlyHint"] == true)
+        );
+
+        let response = call_list_projects(&client, addr, &session, None).await;
+        assert!(response.to_string().contains("FilteredProject"));
+        let call = json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "tools/call",
+            "params": {
+                "name": "azdo_create_work_item",
+                "arguments": {
+                    "organization": "org",
+                    "project": "proj",
+                    "work_item_type": "Task",
+                    "title": "Must not be created"
+                }
+            }
+        });
+        let (_, response) = rpc(&client, addr, Some(&session), None, call).await;
+        let response = response.expect("tools/call must be answered");
+        assert!(
+            response["error"]["message"]
+                .as_str()
+                .is_some_and(|message| message.contains("tool not found")),
+            "hidden tools must not be callable: {response}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_local_file_arguments_are_rejected_over_http() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_upload_attachment().times(0);
+        mock.expect_add_attachment_to_work_item().times(0);
+        let server = AzureMcpServer::new_with_api(mock);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = http::run_server(server, listener, HttpServerOptions::default()).await;
+        });
+
+        let client = reqwest::Client::new();
+        let (session, _) = rpc(&client, addr, None, None, initialize_message()).await;
+        let session = session.expect("initialize must assign a session");
+        let initialized = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        rpc(&client, addr, Some(&session), None, initialized).await;
+
+        let call = json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {
+                "name": "azdo_add_attachment",
+                "arguments": {
+                    "organization": "org",
+                    "project": "proj",
+                    "work_item_id": 1,
+                    "file_path": "/etc/passwd"
+                }
+            }
+        });
+        let (_, response) = rpc(&client, addr, Some(&session), None, call).await;
+        let response = response.expect("tools/call must be answered");
+        assert_eq!(response["error"]["code"], -32602, "{response}");
+        assert!(
+            response["error"]["message"]
+                .as_str()
+                .is_some_and(|message| message.contains("content_base64")),
+            "{response}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_default_context_fills_omitted_arguments_per_session() {
+        let mut mock = MockAzureDevOpsApi::new();
+        let organizations = Arc::new(Mutex::new(Vec::new()));
+        let recorded = organizations.clone();
+        mock.expect_list_projects().returning(move |organization| {
+            recorded.lock().unwrap().push(organization.to_string());
+            Ok(vec![])
+        });
+        mock.expect_list_teams()
+            .returning(|_, project| Err(AzureError::ApiError(format!("project {project}"))));
+        let server = AzureMcpServer::new_with_api(mock).with_defaults(ToolDefaults {
+            organization: Some("server-org".to_string()),
+            ..Default::default()
+        });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = http::run_server(server, listener, HttpServerOptions::default()).await;
+        });
+
+        let client = reqwest::Client::new();
+        let mut sessions = Vec::new();
+        for _ in 0..2 {
+            let (session, _) = rpc(&client, addr, None, None, initialize_message()).await;
+            let session = session.expect("initialize must assign a session");
+            let initialized = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+            rpc(&client, addr, Some(&session), None, initialized).await;
+            sessions.push(session);
+        }
+        let call = |name: &str, arguments: Value| {
+            json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "tools/call",
+                "params": {"name": name, "arguments": arguments}
+            })
+        };
+
+        // The schema no longer requires the arguments that can be defaulted.
+        let list = json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"});
+        let (_, response) = rpc(&client, addr, Some(&sessions[0]), None, list).await;
+        let response = response.unwrap();
+        let list_teams = response["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|tool| tool["name"] == "azdo_list_teams")
+            .unwrap();
+        assert!(list_teams["inputSchema"]["properties"]["project"].is_object());
+        assert!(
+            !list_teams["inputSchema"]["required"]
+                .as_array()
+                .is_some_and(|required| required.contains(&json!("project"))),
+            "{list_teams}"
+        );
+
+        // The server default is used when the organization is omitted.
+        call_list_projects(&client, addr, &sessions[0], None).await;
+        let request = call("azdo_list_projects", json!({}));
+        rpc(&client, addr, Some(&sessions[0]), None, request).await;
+
+        // The first session overrides it; the second keeps the server default.
+        let request = call(
+            "azdo_set_context",
+            json!({"organization": "session-org", "project": "Fabrikam"}),
+        );
+        let (_, response) = rpc(&client, addr, Some(&sessions[0]), None, request).await;
+        assert!(response.unwrap().to_string().contains("session-org"));
+        for session in &sessions {
+            let request = call("azdo_list_projects", json!({}));
+            rpc(&client, addr, Some(session), None, request).await;
+        }
+        assert_eq!(
+            *organizations.lock().unwrap(),
+            vec!["org", "server-org", "session-org", "server-org"]
+        );
+
+        let request = call("azdo_list_teams", json!({}));
+        let (_, response) = rpc(&client, addr, Some(&sessions[0]), None, request).await;
+        assert!(
+            response.unwrap().to_string().contains("project Fabrikam"),
+            "the session's default project must be used"
+        );
+        let request = call("azdo_list_teams", json!({}));
+        let (_, response) = rpc(&client, addr, Some(&sessions[1]), None, request).await;
+        let response = response.unwrap();
+        assert_eq!(response["error"]["code"], -32602, "{response}");
+        assert!(
+            response["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("azdo_set_context")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resources_are_advertised_and_readable() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_board().returning(|_, _, team, board| {
+            Err(AzureError::ApiError(format!("board {board} of {team}")))
+        });
+        let server = AzureMcpServer::new_with_api(mock).with_defaults(ToolDefaults {
+            organization: Some("contoso".to_string()),
+            project: Some("Fabrikam".to_string()),
+            team: Some("Fabrikam Team".to_string()),
+            ..Default::default()
+        });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = http::run_server(server, listener, HttpServerOptions::default()).await;
+        });
+
+        let client = reqwest::Client::new();
+        let (session, response) = rpc(&client, addr, None, None, initialize_message()).await;
+        let session = session.expect("initialize must assign a session");
+        let capabilities = &response.unwrap()["result"]["capabilities"];
+        assert_eq!(
+            capabilities["resources"]["subscribe"],
+            json!(true),
+            "{capabilities}"
+        );
+        let initialized = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        rpc(&client, addr, Some(&session), None, initialized).await;
+
+        let request = json!({"jsonrpc": "2.0", "id": 2, "method": "resources/templates/list"});
+        let (_, response) = rpc(&client, addr, Some(&session), None, request).await;
+        let response = response.unwrap();
+        let templates: Vec<&str> = response["result"]["resourceTemplates"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|template| template["uriTemplate"].as_str().unwrap())
+            .collect();
+        assert!(templates.contains(&"azdo://{organization}/{project}/workitems/{id}"));
+        assert!(templates.contains(&"azdo://{organization}/{project}/queries/{queryId}"));
+
+        let request = json!({"jsonrpc": "2.0", "id": 3, "method": "resources/list"});
+        let (_, response) = rpc(&client, addr, Some(&session), None, request).await;
+        let response = response.unwrap();
+        assert_eq!(
+            response["result"]["resources"][0]["uri"],
+            "azdo://contoso/Fabrikam/teams/Fabrikam%20Team/iterations/current"
+        );
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 4,
+            "method": "resources/read",
+            "params": {"uri": "azdo://contoso/Fabrikam/teams/Fabrikam%20Team/boards/Stories"}
+        });
+        let (_, response) = rpc(&client, addr, Some(&session), None, request).await;
+        let response = response.unwrap();
+        assert!(
+            response["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("board Stories of Fabrikam Team"),
+            "{response}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prompts_are_listed_and_rendered() {
+        let server = AzureMcpServer::new_with_api(MockAzureDevOpsApi::new())
+            .with_defaults(ToolDefaults {
+                organization: Some("contoso".to_string()),
+                project: Some("Fabrikam".to_string()),
+                ..Default::default()
+            })
+            .with_tool_filter(&ToolFilter {
+                read_only: true,
+                ..Default::default()
+            });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = http::run_server(server, listener, HttpServerOptions::default()).await;
+        });
+
+        let client = reqwest::Client::new();
+        let (session, response) = rpc(&client, addr, None, None, initialize_message()).await;
+        let session = session.expect("initialize must assign a session");
+        assert!(response.unwrap()["result"]["capabilities"]["prompts"].is_object());
+        let initialized = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        rpc(&client, addr, Some(&session), None, initialized).await;
+
+        // triage_bugs updates and feature_breakdown creates work items, so
+        // --read-only hides them.
+        let request = json!({"jsonrpc": "2.0", "id": 2, "method": "prompts/list"});
+        let (_, response) = rpc(&client, addr, Some(&session), None, request).await;
+        let response = response.unwrap();
+        let names: Vec<&str> = response["result"]["prompts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|prompt| prompt["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["sprint_review", "standup_summary"]);
+
+        // The team set for the session fills the omitted argument.
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "tools/call",
+            "params": {"name": "azdo_set_context", "arguments": {"team": "Fabrikam Team"}}
+        });
+        rpc(&client, addr, Some(&session), None, request).await;
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 4,
+            "method": "prompts/get",
+            "params": {"name": "sprint_review"}
+        });
+        let (_, response) = rpc(&client, addr, Some(&session), None, request).await;
+        let response = response.unwrap();
+        let text = response["result"]["messages"][0]["content"]["text"]
+            .as_str()
+            .unwrap();
+        assert!(
+            text.contains("team \"Fabrikam Team\" in project \"Fabrikam\""),
+            "{text}"
+        );
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 5,
+            "method": "prompts/get",
+            "params": {"name": "feature_breakdown", "arguments": {"feature_id": "1"}}
+        });
+        let (_, response) = rpc(&client, addr, Some(&session), None, request).await;
+        assert_eq!(response.unwrap()["error"]["code"], -32602);
+    }
 }
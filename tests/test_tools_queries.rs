@@ -0,0 +1,215 @@
+#[cfg(feature = "test-support")]
+mod common;
+
+#[cfg(feature = "test-support")]
+mod tests {
+    use super::common::{assert_tool_output_has_warning, extract_text_from_result};
+    use mcp_for_azure_devops_boards::azure::api_trait::MockAzureDevOpsApi;
+    use mcp_for_azure_devops_boards::azure::models::{WorkItem, WorkItemLink, WorkItemQueryPage};
+    use mcp_for_azure_devops_boards::azure::queries::QueryHierarchyItem;
+    use mcp_for_azure_devops_boards::mcp::tools::queries::{
+        CreateQueryArgs, ListQueriesArgs, RunSavedQueryArgs, create_query::create_query,
+        list_queries::list_queries, run_saved_query::run_saved_query,
+    };
+    use rmcp::model::ErrorCode;
+    use std::collections::HashMap;
+
+    fn query_item(value: serde_json::Value) -> QueryHierarchyItem {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn work_item(id: u32, work_item_type: &str, title: &str) -> WorkItem {
+        let mut fields = HashMap::new();
+        fields.insert("System.Title".to_string(), serde_json::json!(title));
+        fields.insert(
+            "System.WorkItemType".to_string(),
+            serde_json::json!(work_item_type),
+        );
+        WorkItem {
+            id,
+            fields,
+            url: None,
+            comments: None,
+        }
+    }
+
+    fn run_args(query: &str) -> RunSavedQueryArgs {
+        RunSavedQueryArgs {
+            organization: "org".to_string(),
+            project: "proj".to_string(),
+            query: query.to_string(),
+            include_latest_n_comments: None,
+            top: None,
+            skip: None,
+            cursor: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_queries_lists_the_folder_tree() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_list_queries()
+            .withf(|_, _, depth| *depth == 2)
+            .returning(|_, _, _| {
+                Ok(vec![
+                    query_item(serde_json::json!({
+                        "id": "f1", "name": "Shared Queries", "path": "Shared Queries",
+                        "isFolder": true, "hasChildren": true,
+                        "children": [
+                            {"id": "q1", "name": "Epics, by area", "path": "Shared Queries/Epics, by area", "queryType": "tree"},
+                            {"id": "f2", "name": "Team", "path": "Shared Queries/Team", "isFolder": true, "hasChildren": true}
+                        ]
+                    })),
+                    query_item(serde_json::json!({
+                        "id": "f3", "name": "My Queries", "path": "My Queries", "isFolder": true
+                    })),
+                ])
+            });
+
+        let result = list_queries(
+            &mock,
+            ListQueriesArgs {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                folder: None,
+                depth: Some(2),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_tool_output_has_warning(&result);
+        let text = extract_text_from_result(&result);
+        assert!(text.contains("path,type,id\n"), "{text}");
+        assert!(text.contains("Shared Queries,folder,f1\n"), "{text}");
+        assert!(
+            text.contains("\"Shared Queries/Epics, by area\",tree,q1\n"),
+            "{text}"
+        );
+        assert!(text.contains("Shared Queries/Team,folder+,f2\n"), "{text}");
+        assert!(text.contains("My Queries,folder,f3\n"), "{text}");
+    }
+
+    #[tokio::test]
+    async fn test_list_queries_rejects_invalid_depth() {
+        let mock = MockAzureDevOpsApi::new();
+        let error = list_queries(
+            &mock,
+            ListQueriesArgs {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                folder: Some("Shared Queries".to_string()),
+                depth: Some(3),
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn test_run_saved_query_by_path_renders_links() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_query()
+            .withf(|_, _, id_or_path, _| id_or_path == "Shared Queries/Epics")
+            .returning(|_, _, _, _| {
+                Ok(query_item(serde_json::json!({
+                    "id": "q1", "name": "Epics", "path": "Shared Queries/Epics", "queryType": "tree"
+                })))
+            });
+        mock.expect_run_saved_query()
+            .withf(|_, _, query_id, skip, top, _| query_id == "q1" && *skip == 0 && *top == 200)
+            .returning(|_, _, _, skip, _, _| {
+                Ok(WorkItemQueryPage {
+                    work_items: vec![
+                        work_item(1, "Epic", "Checkout"),
+                        work_item(2, "Feature", "Payments"),
+                    ],
+                    total_count: 2,
+                    skip,
+                    truncated: false,
+                    links: vec![
+                        WorkItemLink {
+                            source: None,
+                            target: 1,
+                            link_type: None,
+                        },
+                        WorkItemLink {
+                            source: Some(1),
+                            target: 2,
+                            link_type: Some("System.LinkTypes.Hierarchy-Forward".to_string()),
+                        },
+                    ],
+                })
+            });
+
+        let result = run_saved_query(&mock, run_args("Shared Queries/Epics"))
+            .await
+            .unwrap();
+
+        assert_tool_output_has_warning(&result);
+        let text = extract_text_from_result(&result);
+        assert!(
+            text.contains("query=Shared Queries/Epics,query_type=tree\n"),
+            "{text}"
+        );
+        assert!(text.contains("total_count=2,returned=2"), "{text}");
+        assert!(text.contains("Payments"), "{text}");
+        assert!(
+            text.contains("hierarchy:\n1 Epic: Checkout\n  2 Feature: Payments\n"),
+            "{text}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_saved_query_rejects_folders() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_query().returning(|_, _, _, _| {
+            Ok(query_item(serde_json::json!({
+                "id": "f1", "name": "Shared Queries", "path": "Shared Queries", "isFolder": true
+            })))
+        });
+        mock.expect_run_saved_query().never();
+
+        let error = run_saved_query(&mock, run_args("Shared Queries"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+        assert!(error.message.contains("azdo_list_queries"));
+    }
+
+    #[tokio::test]
+    async fn test_create_query() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_create_query()
+            .withf(|_, _, parent, name, wiql| {
+                parent == "My Queries" && name == "Open bugs" && wiql.contains("'Bug'")
+            })
+            .returning(|_, _, _, _, _| {
+                Ok(query_item(serde_json::json!({
+                    "id": "q9", "name": "Open bugs", "path": "My Queries/Open bugs", "queryType": "flat"
+                })))
+            });
+
+        let result = create_query(
+            &mock,
+            CreateQueryArgs {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                folder: "My Queries".to_string(),
+                name: "Open bugs".to_string(),
+                wiql: "SELECT [System.Id] FROM WorkItems WHERE [System.WorkItemType] = 'Bug'"
+                    .to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_tool_output_has_warning(&result);
+        let text = extract_text_from_result(&result);
+        assert!(
+            text.contains("Created query My Queries/Open bugs (id=q9,query_type=flat)"),
+            "{text}"
+        );
+    }
+}
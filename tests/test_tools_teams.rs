@@ -6,17 +6,26 @@ mod tests {
     use super::common::{assert_tool_output_has_warning, extract_text_from_result};
     use mcp_for_azure_devops_boards::azure::api_trait::MockAzureDevOpsApi;
     use mcp_for_azure_devops_boards::azure::boards::Team;
+    use mcp_for_azure_devops_boards::azure::capacity::{
+        Activity, CapacityIdentity, DateRange, TeamCapacity, TeamMemberCapacity,
+    };
     use mcp_for_azure_devops_boards::azure::client::AzureError;
     use mcp_for_azure_devops_boards::azure::iterations::{
         IterationAttributes, TeamSettingsIteration,
     };
+    use mcp_for_azure_devops_boards::azure::models::{WorkItem, WorkItemQueryPage};
     use mcp_for_azure_devops_boards::azure::teams::{TeamMember, TeamMemberIdentity};
-    use mcp_for_azure_devops_boards::mcp::tools::support::UNTRUSTED_CONTENT_WARNING;
+    use mcp_for_azure_devops_boards::mcp::tools::support::{DayOffArgs, UNTRUSTED_CONTENT_WARNING};
+    use mcp_for_azure_devops_boards::mcp::tools::teams::capacity::{
+        ActivityArgs, GetSprintCapacityArgs, GetSprintRemainingCapacityArgs, SetMemberCapacityArgs,
+        get_sprint_capacity, get_sprint_remaining_capacity, set_member_capacity,
+    };
     use mcp_for_azure_devops_boards::mcp::tools::teams::{
         GetTeamArgs, GetTeamCurrentIterationArgs, ListTeamMembersArgs, ListTeamsArgs,
         get_team::get_team, get_team_current_iteration::get_team_current_iteration,
         list_team_members::list_team_members, list_teams::list_teams,
     };
+    use std::collections::HashMap;
 
     fn mock_team() -> Team {
         Team {
@@ -356,4 +365,286 @@ mod tests {
             "Output should indicate no current iteration"
         );
     }
+
+    fn mock_sprint() -> TeamSettingsIteration {
+        TeamSettingsIteration {
+            id: "sprint-1".to_string(),
+            name: "Sprint 1".to_string(),
+            path: "proj\\Sprint 1".to_string(),
+            attributes: IterationAttributes {
+                // Monday to Friday two weeks later, far enough ahead that the
+                // whole sprint is left
+                start_date: Some("2099-01-05T00:00:00Z".to_string()),
+                finish_date: Some("2099-01-16T00:00:00Z".to_string()),
+                time_frame: Some("future".to_string()),
+            },
+            url: "https://dev.azure.com/org/proj/_apis/work/teamsettings/iterations/sprint-1"
+                .to_string(),
+        }
+    }
+
+    fn day(date: &str) -> DateRange {
+        DateRange {
+            start: format!("{}T00:00:00Z", date),
+            end: format!("{}T00:00:00Z", date),
+        }
+    }
+
+    fn mock_capacity() -> TeamCapacity {
+        TeamCapacity {
+            team_members: vec![
+                TeamMemberCapacity {
+                    team_member: CapacityIdentity {
+                        id: "user-1".to_string(),
+                        display_name: "Alice Smith".to_string(),
+                        unique_name: Some("alice@example.com".to_string()),
+                    },
+                    activities: vec![
+                        Activity {
+                            capacity_per_day: 4.0,
+                            name: "Development".to_string(),
+                        },
+                        Activity {
+                            capacity_per_day: 2.0,
+                            name: "Testing".to_string(),
+                        },
+                    ],
+                    days_off: vec![day("2099-01-07")],
+                },
+                TeamMemberCapacity {
+                    team_member: CapacityIdentity {
+                        id: "user-2".to_string(),
+                        display_name: "Bob Jones".to_string(),
+                        unique_name: Some("bob@example.com".to_string()),
+                    },
+                    activities: vec![Activity {
+                        capacity_per_day: 5.0,
+                        name: "Development".to_string(),
+                    }],
+                    days_off: Vec::new(),
+                },
+            ],
+            total_capacity_per_day: 11.0,
+            total_days_off: 1,
+        }
+    }
+
+    fn task(id: u32, assignee: Option<(&str, &str)>, remaining_work: f64) -> WorkItem {
+        let mut fields = HashMap::new();
+        fields.insert(
+            "Microsoft.VSTS.Scheduling.RemainingWork".to_string(),
+            serde_json::json!(remaining_work),
+        );
+        if let Some((display_name, unique_name)) = assignee {
+            fields.insert(
+                "System.AssignedTo".to_string(),
+                serde_json::json!({"displayName": display_name, "uniqueName": unique_name}),
+            );
+        }
+        WorkItem {
+            id,
+            fields,
+            url: None,
+            comments: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_sprint_capacity_defaults_to_current_iteration() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_team_current_iteration()
+            .returning(|_, _, _| Ok(Some(mock_sprint())));
+        mock.expect_get_iteration_capacities()
+            .withf(|_, _, _, iteration_id| iteration_id == "sprint-1")
+            .returning(|_, _, _, _| Ok(mock_capacity()));
+        mock.expect_get_team_days_off()
+            .returning(|_, _, _, _| Ok(vec![day("2099-01-12")]));
+
+        let result = get_sprint_capacity(
+            &mock,
+            GetSprintCapacityArgs {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                team_id: "team-1".to_string(),
+                iteration_id: None,
+            },
+        )
+        .await
+        .unwrap();
+        let text = extract_text_from_result(&result);
+        assert!(
+            text.contains(
+                "iteration=Sprint 1,id=sprint-1,team_days_off=2099-01-12,total_capacity_per_day=11"
+            ),
+            "Output should start with the iteration summary: {}",
+            text
+        );
+        assert!(text.contains("member,activity,capacity_per_day,days_off"));
+        assert!(text.contains("Alice Smith,Development,4,2099-01-07"));
+        assert!(text.contains("Alice Smith,Testing,2,2099-01-07"));
+        assert!(text.contains("Bob Jones,Development,5,"));
+    }
+
+    #[tokio::test]
+    async fn test_set_member_capacity_resolves_member_from_team() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_team_iteration()
+            .returning(|_, _, _, _| Ok(mock_sprint()));
+        mock.expect_get_iteration_capacities()
+            .returning(|_, _, _, _| Ok(mock_capacity()));
+        // Carol has no capacity yet, so only the team members list knows her
+        mock.expect_list_team_members().returning(|_, _, _| {
+            Ok(vec![TeamMember {
+                identity: TeamMemberIdentity {
+                    display_name: "Carol White".to_string(),
+                    unique_name: "carol@example.com".to_string(),
+                    id: "user-3".to_string(),
+                },
+            }])
+        });
+        mock.expect_update_member_capacity()
+            .withf(|_, _, _, iteration_id, member_id, patch| {
+                iteration_id == "sprint-1"
+                    && member_id == "user-3"
+                    && patch.activities.as_ref().is_some_and(|activities| {
+                        activities.len() == 1
+                            && activities[0].name == "Design"
+                            && activities[0].capacity_per_day == 3.0
+                    })
+                    && patch.days_off.as_ref().is_some_and(|days_off| {
+                        days_off.len() == 1
+                            && days_off[0].start == "2099-01-08T00:00:00Z"
+                            && days_off[0].end == "2099-01-09T00:00:00Z"
+                    })
+            })
+            .returning(|_, _, _, _, _, patch| {
+                Ok(TeamMemberCapacity {
+                    team_member: CapacityIdentity {
+                        id: "user-3".to_string(),
+                        display_name: "Carol White".to_string(),
+                        unique_name: Some("carol@example.com".to_string()),
+                    },
+                    activities: patch.activities.unwrap_or_default(),
+                    days_off: patch.days_off.unwrap_or_default(),
+                })
+            });
+
+        let result = set_member_capacity(
+            &mock,
+            SetMemberCapacityArgs {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                team_id: "team-1".to_string(),
+                iteration_id: Some("sprint-1".to_string()),
+                member: "CAROL@example.com".to_string(),
+                activities: Some(vec![ActivityArgs {
+                    name: Some("Design".to_string()),
+                    capacity_per_day: 3.0,
+                }]),
+                days_off: Some(vec![DayOffArgs {
+                    start: "2099-01-08".to_string(),
+                    end: Some("2099-01-09".to_string()),
+                }]),
+            },
+        )
+        .await
+        .unwrap();
+        let text = extract_text_from_result(&result);
+        assert!(
+            text.contains(
+                "Updated capacity of Carol White for Sprint 1: activities=Design=3,days_off=2099-01-08..2099-01-09"
+            ),
+            "Unexpected output: {}",
+            text
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_member_capacity_requires_a_change() {
+        let mock = MockAzureDevOpsApi::new();
+
+        let result = set_member_capacity(
+            &mock,
+            SetMemberCapacityArgs {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                team_id: "team-1".to_string(),
+                iteration_id: None,
+                member: "Alice Smith".to_string(),
+                activities: None,
+                days_off: None,
+            },
+        )
+        .await;
+        let err = result.expect_err("Neither activities nor days_off should be an error");
+        assert!(err.message.contains("activities and/or days_off"));
+    }
+
+    #[tokio::test]
+    async fn test_get_sprint_remaining_capacity_balances_remaining_work() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_team_iteration()
+            .returning(|_, _, _, _| Ok(mock_sprint()));
+        mock.expect_get_team_working_days().returning(|_, _, _| {
+            Ok(["monday", "tuesday", "wednesday", "thursday", "friday"]
+                .iter()
+                .map(|day| day.to_string())
+                .collect())
+        });
+        mock.expect_get_iteration_capacities()
+            .returning(|_, _, _, _| Ok(mock_capacity()));
+        mock.expect_get_team_days_off()
+            .returning(|_, _, _, _| Ok(vec![day("2099-01-12")]));
+        mock.expect_query_work_items()
+            .withf(|_, _, query, _, _, _| {
+                query.contains("[System.IterationPath] = 'proj\\Sprint 1'")
+            })
+            .returning(|_, _, _, skip, _, _| {
+                let work_items = if skip == 0 {
+                    vec![
+                        task(1, Some(("Alice Smith", "alice@example.com")), 20.0),
+                        task(2, Some(("Bob Jones", "bob@example.com")), 30.0),
+                    ]
+                } else {
+                    vec![
+                        task(3, Some(("Bob Jones", "BOB@example.com")), 20.0),
+                        task(4, Some(("Carol White", "carol@example.com")), 3.0),
+                        task(5, None, 4.0),
+                    ]
+                };
+                Ok(WorkItemQueryPage {
+                    total_count: 5,
+                    truncated: skip == 0,
+                    skip,
+                    work_items,
+                    links: Vec::new(),
+                })
+            });
+
+        let result = get_sprint_remaining_capacity(
+            &mock,
+            GetSprintRemainingCapacityArgs {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                team_id: "team-1".to_string(),
+                iteration_id: Some("sprint-1".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        let text = extract_text_from_result(&result);
+        assert!(
+            text.contains(
+                "iteration=Sprint 1,id=sprint-1,from=2099-01-05,to=2099-01-16,team_working_days_left=9,team_days_off=2099-01-12,work_items=5"
+            ),
+            "Unexpected summary: {}",
+            text
+        );
+        // Alice: 10 working days minus the team day off and her own, at 6h/day
+        assert!(text.contains("Alice Smith,6,8,48,20,28"), "{}", text);
+        assert!(text.contains("Bob Jones,5,9,45,50,-5"), "{}", text);
+        assert!(text.contains("Carol White,0,0,0,3,-3"), "{}", text);
+        assert!(text.contains("(unassigned),0,0,0,4,-4"), "{}", text);
+        assert!(text.contains("(total),11,,93,77,16"), "{}", text);
+    }
 }
@@ -5,16 +5,28 @@ mod common;
 mod tests {
     use super::common::{assert_tool_output_has_warning, extract_text_from_result};
     use mcp_for_azure_devops_boards::azure::api_trait::MockAzureDevOpsApi;
+    use mcp_for_azure_devops_boards::azure::attachments::AttachmentReference;
     use mcp_for_azure_devops_boards::azure::client::AzureError;
-    use mcp_for_azure_devops_boards::azure::models::WorkItem;
+    use mcp_for_azure_devops_boards::azure::git::{GitProjectReference, GitRepository};
+    use mcp_for_azure_devops_boards::azure::models::{
+        WorkItem, WorkItemBatchResponse, WorkItemLink, WorkItemQueryPage, WorkItemRelation,
+        WorkItemRelationType, WorkItemUpdate, WorkItemWithRelations,
+    };
     use mcp_for_azure_devops_boards::mcp::tools::support::UNTRUSTED_CONTENT_WARNING;
     use mcp_for_azure_devops_boards::mcp::tools::work_items::{
-        AddCommentArgs, CreateWorkItemArgs, GetWorkItemArgs, GetWorkItemsArgs, LinkWorkItemsArgs,
-        QueryWorkItemsArgs, QueryWorkItemsArgsWiql, UpdateCommentArgs, UpdateWorkItemArgs,
-        add_comment::add_comment, create_work_item::create_work_item, get_work_item::get_work_item,
+        AddAttachmentArgs, AddCommentArgs, BatchWorkItemsArgs, CreateWorkItemArgs,
+        GetAttachmentArgs, GetWorkItemArgs, GetWorkItemHistoryArgs, GetWorkItemTreeArgs,
+        GetWorkItemsArgs, LinkWorkItemsArgs, ListAttachmentsArgs, ListWorkItemRelationsArgs,
+        QueryWorkItemsArgs, QueryWorkItemsArgsWiql, UnlinkWorkItemsArgs, UpdateCommentArgs,
+        UpdateWorkItemArgs, add_attachment::add_attachment, add_comment::add_comment,
+        batch_work_items::batch_work_items, create_work_item::create_work_item,
+        get_attachment::get_attachment, get_work_item::get_work_item,
+        get_work_item_history::get_work_item_history, get_work_item_tree::get_work_item_tree,
         get_work_items::get_work_items, link_work_items::link_work_items,
+        list_attachments::list_attachments, list_work_item_relations::list_work_item_relations,
         query_work_items::query_work_items, query_work_items_by_wiql::query_work_items_by_wiql,
-        update_comment::update_comment, update_work_item::update_work_item,
+        unlink_work_items::unlink_work_items, update_comment::update_comment,
+        update_work_item::update_work_item,
     };
     use std::collections::HashMap;
 
@@ -34,6 +46,58 @@ mod tests {
         }
     }
 
+    fn mock_relation_types() -> Vec<WorkItemRelationType> {
+        serde_json::from_value(serde_json::json!([
+            {"name": "Parent", "referenceName": "System.LinkTypes.Hierarchy-Reverse", "attributes": {"usage": "workItemLink", "enabled": true}},
+            {"name": "Child", "referenceName": "System.LinkTypes.Hierarchy-Forward", "attributes": {"usage": "workItemLink", "enabled": true}},
+            {"name": "Related", "referenceName": "System.LinkTypes.Related", "attributes": {"usage": "workItemLink", "enabled": true}},
+            {"name": "Hyperlink", "referenceName": "Hyperlink", "attributes": {"usage": "resourceLink", "enabled": true}},
+            {"name": "Artifact Link", "referenceName": "ArtifactLink", "attributes": {"usage": "resourceLink", "enabled": true}}
+        ]))
+        .unwrap()
+    }
+
+    fn link_args() -> LinkWorkItemsArgs {
+        LinkWorkItemsArgs {
+            organization: "org".to_string(),
+            project: "proj".to_string(),
+            source_id: 42,
+            target_id: Some(43),
+            link_type: Some("Related".to_string()),
+            url: None,
+            repository: None,
+            commit_id: None,
+            pull_request_id: None,
+            build_id: None,
+            comment: None,
+        }
+    }
+
+    fn unlink_args() -> UnlinkWorkItemsArgs {
+        UnlinkWorkItemsArgs {
+            organization: "org".to_string(),
+            project: "proj".to_string(),
+            source_id: 42,
+            target_id: None,
+            link_type: None,
+            url: None,
+            repository: None,
+            commit_id: None,
+            pull_request_id: None,
+            build_id: None,
+        }
+    }
+
+    fn mock_query_page(work_items: Vec<WorkItem>) -> WorkItemQueryPage {
+        WorkItemQueryPage {
+            total_count: work_items.len(),
+            work_items,
+            skip: 0,
+            truncated: false,
+            links: Vec::new(),
+        }
+    }
+
     #[tokio::test]
     async fn test_get_work_item_has_warning() {
         let mut mock = MockAzureDevOpsApi::new();
@@ -120,7 +184,7 @@ mod tests {
     async fn test_update_work_item_has_warning() {
         let mut mock = MockAzureDevOpsApi::new();
         mock.expect_update_work_item()
-            .returning(|_, _, _, _, _| Ok(mock_work_item()));
+            .returning(|_, _, _, _, _, _, _| Ok(mock_work_item()));
 
         let result = update_work_item(
             &mock,
@@ -143,6 +207,8 @@ mod tests {
                 effort: None,
                 remaining_work: None,
                 tags: None,
+                add_tags: None,
+                remove_tags: None,
                 activity: None,
                 start_date: None,
                 target_date: None,
@@ -150,6 +216,8 @@ mod tests {
                 repro_steps: None,
                 justification: None,
                 fields: None,
+                expected_rev: None,
+                clear_fields: vec![],
             },
         )
         .await
@@ -161,7 +229,7 @@ mod tests {
     async fn test_query_work_items_has_warning() {
         let mut mock = MockAzureDevOpsApi::new();
         mock.expect_query_work_items()
-            .returning(|_, _, _, _| Ok(vec![mock_work_item()]));
+            .returning(|_, _, _, _, _, _| Ok(mock_query_page(vec![mock_work_item()])));
 
         let result = query_work_items(
             &mock,
@@ -191,6 +259,9 @@ mod tests {
                 include_tags: vec![],
                 exclude_tags: vec![],
                 include_latest_n_comments: None,
+                top: None,
+                skip: None,
+                cursor: None,
             },
         )
         .await
@@ -202,7 +273,7 @@ mod tests {
     async fn test_query_work_items_by_wiql_has_warning() {
         let mut mock = MockAzureDevOpsApi::new();
         mock.expect_query_work_items()
-            .returning(|_, _, _, _| Ok(vec![mock_work_item()]));
+            .returning(|_, _, _, _, _, _| Ok(mock_query_page(vec![mock_work_item()])));
 
         let result = query_work_items_by_wiql(
             &mock,
@@ -211,6 +282,9 @@ mod tests {
                 project: "proj".to_string(),
                 query: "SELECT [System.Id] FROM WorkItems WHERE [System.State] = 'New'".to_string(),
                 include_latest_n_comments: None,
+                top: None,
+                skip: None,
+                cursor: None,
             },
         )
         .await
@@ -221,21 +295,12 @@ mod tests {
     #[tokio::test]
     async fn test_link_work_items_has_warning() {
         let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_list_work_item_relation_types()
+            .returning(|_| Ok(mock_relation_types()));
         mock.expect_link_work_items()
-            .returning(|_, _, _, _, _| Ok(serde_json::json!({"id": 42})));
+            .returning(|_, _, _, _| Ok(serde_json::json!({"id": 42})));
 
-        let result = link_work_items(
-            &mock,
-            LinkWorkItemsArgs {
-                organization: "org".to_string(),
-                project: "proj".to_string(),
-                source_id: 42,
-                target_id: 43,
-                link_type: "Related".to_string(),
-            },
-        )
-        .await
-        .unwrap();
+        let result = link_work_items(&mock, link_args()).await.unwrap();
         assert_tool_output_has_warning(&result);
     }
 
@@ -372,7 +437,7 @@ mod tests {
     async fn test_update_work_item_api_error_propagates() {
         let mut mock = MockAzureDevOpsApi::new();
         mock.expect_update_work_item()
-            .returning(|_, _, _, _, _| Err(AzureError::ApiError("test error".to_string())));
+            .returning(|_, _, _, _, _, _, _| Err(AzureError::ApiError("test error".to_string())));
 
         let result = update_work_item(
             &mock,
@@ -395,6 +460,8 @@ mod tests {
                 effort: None,
                 remaining_work: None,
                 tags: None,
+                add_tags: None,
+                remove_tags: None,
                 activity: None,
                 start_date: None,
                 target_date: None,
@@ -402,6 +469,8 @@ mod tests {
                 repro_steps: None,
                 justification: None,
                 fields: None,
+                expected_rev: None,
+                clear_fields: vec![],
             },
         )
         .await;
@@ -413,7 +482,7 @@ mod tests {
     async fn test_query_work_items_api_error_propagates() {
         let mut mock = MockAzureDevOpsApi::new();
         mock.expect_query_work_items()
-            .returning(|_, _, _, _| Err(AzureError::ApiError("test error".to_string())));
+            .returning(|_, _, _, _, _, _| Err(AzureError::ApiError("test error".to_string())));
 
         let result = query_work_items(
             &mock,
@@ -443,6 +512,9 @@ mod tests {
                 include_tags: vec![],
                 exclude_tags: vec![],
                 include_latest_n_comments: None,
+                top: None,
+                skip: None,
+                cursor: None,
             },
         )
         .await;
@@ -454,7 +526,7 @@ mod tests {
     async fn test_query_work_items_by_wiql_api_error_propagates() {
         let mut mock = MockAzureDevOpsApi::new();
         mock.expect_query_work_items()
-            .returning(|_, _, _, _| Err(AzureError::ApiError("test error".to_string())));
+            .returning(|_, _, _, _, _, _| Err(AzureError::ApiError("test error".to_string())));
 
         let result = query_work_items_by_wiql(
             &mock,
@@ -463,6 +535,9 @@ mod tests {
                 project: "proj".to_string(),
                 query: "SELECT [System.Id] FROM WorkItems WHERE [System.State] = 'New'".to_string(),
                 include_latest_n_comments: None,
+                top: None,
+                skip: None,
+                cursor: None,
             },
         )
         .await;
@@ -473,20 +548,12 @@ mod tests {
     #[tokio::test]
     async fn test_link_work_items_api_error_propagates() {
         let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_list_work_item_relation_types()
+            .returning(|_| Ok(mock_relation_types()));
         mock.expect_link_work_items()
-            .returning(|_, _, _, _, _| Err(AzureError::ApiError("test error".to_string())));
+            .returning(|_, _, _, _| Err(AzureError::ApiError("test error".to_string())));
 
-        let result = link_work_items(
-            &mock,
-            LinkWorkItemsArgs {
-                organization: "org".to_string(),
-                project: "proj".to_string(),
-                source_id: 42,
-                target_id: 43,
-                link_type: "Related".to_string(),
-            },
-        )
-        .await;
+        let result = link_work_items(&mock, link_args()).await;
 
         assert!(result.is_err());
     }
@@ -730,7 +797,7 @@ mod tests {
     async fn test_update_work_item_returns_compact_json() {
         let mut mock = MockAzureDevOpsApi::new();
         mock.expect_update_work_item()
-            .returning(|_, _, _, _, _| Ok(mock_work_item()));
+            .returning(|_, _, _, _, _, _, _| Ok(mock_work_item()));
 
         let result = update_work_item(
             &mock,
@@ -753,6 +820,8 @@ mod tests {
                 effort: None,
                 remaining_work: None,
                 tags: None,
+                add_tags: None,
+                remove_tags: None,
                 activity: None,
                 start_date: None,
                 target_date: None,
@@ -760,6 +829,8 @@ mod tests {
                 repro_steps: None,
                 justification: None,
                 fields: None,
+                expected_rev: None,
+                clear_fields: vec![],
             },
         )
         .await
@@ -776,7 +847,7 @@ mod tests {
     async fn test_query_work_items_returns_csv() {
         let mut mock = MockAzureDevOpsApi::new();
         mock.expect_query_work_items()
-            .returning(|_, _, _, _| Ok(vec![mock_work_item()]));
+            .returning(|_, _, _, _, _, _| Ok(mock_query_page(vec![mock_work_item()])));
 
         let result = query_work_items(
             &mock,
@@ -806,6 +877,9 @@ mod tests {
                 include_tags: vec![],
                 exclude_tags: vec![],
                 include_latest_n_comments: None,
+                top: None,
+                skip: None,
+                cursor: None,
             },
         )
         .await
@@ -826,7 +900,7 @@ mod tests {
     async fn test_query_work_items_empty_returns_message() {
         let mut mock = MockAzureDevOpsApi::new();
         mock.expect_query_work_items()
-            .returning(|_, _, _, _| Ok(vec![]));
+            .returning(|_, _, _, _, _, _| Ok(mock_query_page(vec![])));
 
         let result = query_work_items(
             &mock,
@@ -856,6 +930,9 @@ mod tests {
                 include_tags: vec![],
                 exclude_tags: vec![],
                 include_latest_n_comments: None,
+                top: None,
+                skip: None,
+                cursor: None,
             },
         )
         .await
@@ -875,7 +952,7 @@ mod tests {
     async fn test_query_work_items_by_wiql_returns_csv() {
         let mut mock = MockAzureDevOpsApi::new();
         mock.expect_query_work_items()
-            .returning(|_, _, _, _| Ok(vec![mock_work_item()]));
+            .returning(|_, _, _, _, _, _| Ok(mock_query_page(vec![mock_work_item()])));
 
         let result = query_work_items_by_wiql(
             &mock,
@@ -884,6 +961,9 @@ mod tests {
                 project: "proj".to_string(),
                 query: "SELECT [System.Id] FROM WorkItems WHERE [System.State] = 'New'".to_string(),
                 include_latest_n_comments: None,
+                top: None,
+                skip: None,
+                cursor: None,
             },
         )
         .await
@@ -903,21 +983,12 @@ mod tests {
     #[tokio::test]
     async fn test_link_work_items_returns_compact_json() {
         let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_list_work_item_relation_types()
+            .returning(|_| Ok(mock_relation_types()));
         mock.expect_link_work_items()
-            .returning(|_, _, _, _, _| Ok(serde_json::json!({"id": 42})));
+            .returning(|_, _, _, _| Ok(serde_json::json!({"id": 42})));
 
-        let result = link_work_items(
-            &mock,
-            LinkWorkItemsArgs {
-                organization: "org".to_string(),
-                project: "proj".to_string(),
-                source_id: 42,
-                target_id: 43,
-                link_type: "Related".to_string(),
-            },
-        )
-        .await
-        .unwrap();
+        let result = link_work_items(&mock, link_args()).await.unwrap();
 
         let text = extract_text_from_result(&result);
         let content = text
@@ -985,4 +1056,1099 @@ mod tests {
             "Output should contain updated comment text"
         );
     }
+
+    fn attachment_relation(id: &str, name: &str) -> WorkItemRelation {
+        let mut attributes = HashMap::new();
+        attributes.insert("name".to_string(), serde_json::json!(name));
+        attributes.insert("resourceSize".to_string(), serde_json::json!(1024));
+        attributes.insert(
+            "authorizedDate".to_string(),
+            serde_json::json!("2025-01-15T10:00:00.000Z"),
+        );
+        WorkItemRelation {
+            rel: "AttachedFile".to_string(),
+            url: format!("https://dev.azure.com/org/proj-id/_apis/wit/attachments/{id}"),
+            attributes,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_attachment_uploads_and_links() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_upload_attachment()
+            .withf(|_, _, file_name, content| file_name == "hello.txt" && content == b"hello")
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(AttachmentReference {
+                    id: "att-1".to_string(),
+                    url: "https://dev.azure.com/org/proj-id/_apis/wit/attachments/att-1"
+                        .to_string(),
+                })
+            });
+        mock.expect_add_attachment_to_work_item()
+            .withf(|_, _, id, url, comment| {
+                *id == 42
+                    && url.ends_with("/attachments/att-1")
+                    && comment.as_deref() == Some("build log")
+            })
+            .times(1)
+            .returning(|_, _, _, _, _| Ok(mock_work_item()));
+
+        let result = add_attachment(
+            &mock,
+            AddAttachmentArgs {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                work_item_id: 42,
+                file_path: None,
+                content_base64: Some("aGVsbG8=".to_string()),
+                file_name: Some("hello.txt".to_string()),
+                comment: Some("build log".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_tool_output_has_warning(&result);
+        let text = extract_text_from_result(&result);
+        assert!(
+            text.contains("att-1"),
+            "Output should contain attachment id"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_add_attachment_upload_error_propagates() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_upload_attachment()
+            .returning(|_, _, _, _| Err(AzureError::ApiError("test error".to_string())));
+        mock.expect_add_attachment_to_work_item().times(0);
+
+        let result = add_attachment(
+            &mock,
+            AddAttachmentArgs {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                work_item_id: 42,
+                file_path: None,
+                content_base64: Some("aGVsbG8=".to_string()),
+                file_name: Some("hello.txt".to_string()),
+                comment: None,
+            },
+        )
+        .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_attachments_returns_only_attached_files() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_work_item_relations().returning(|_, _, _| {
+            Ok(vec![
+                attachment_relation("att-1", "screenshot.png"),
+                WorkItemRelation {
+                    rel: "System.LinkTypes.Related".to_string(),
+                    url: "https://dev.azure.com/org/_apis/wit/workItems/7".to_string(),
+                    attributes: HashMap::new(),
+                },
+                attachment_relation("att-2", "build.log"),
+            ])
+        });
+
+        let result = list_attachments(
+            &mock,
+            ListAttachmentsArgs {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                work_item_id: 42,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_tool_output_has_warning(&result);
+        let text = extract_text_from_result(&result);
+        let content = text
+            .strip_prefix(UNTRUSTED_CONTENT_WARNING)
+            .unwrap_or(&text);
+        assert!(content.contains("att-1,screenshot.png,1024,2025-01-15"));
+        assert!(content.contains("att-2,build.log"));
+        assert!(
+            !content.contains("workItems/7"),
+            "Non-attachment relations must be filtered out"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_attachments_empty_returns_message() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_work_item_relations()
+            .returning(|_, _, _| Ok(vec![]));
+
+        let result = list_attachments(
+            &mock,
+            ListAttachmentsArgs {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                work_item_id: 42,
+            },
+        )
+        .await
+        .unwrap();
+
+        let text = extract_text_from_result(&result);
+        assert!(text.contains("No attachments found"));
+    }
+
+    #[tokio::test]
+    async fn test_get_attachment_returns_text_inline() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_attachment()
+            .returning(|_, _, _, _| Ok(b"ERROR: connection refused".to_vec()));
+
+        let result = get_attachment(
+            &mock,
+            GetAttachmentArgs {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                attachment_id: "att-1".to_string(),
+                file_name: Some("build.log".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_tool_output_has_warning(&result);
+        assert_eq!(result.content.len(), 1);
+        let text = extract_text_from_result(&result);
+        assert!(text.contains("ERROR: connection refused"));
+    }
+
+    #[tokio::test]
+    async fn test_get_attachment_returns_binary_as_blob_resource() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_attachment()
+            .returning(|_, _, _, _| Ok(vec![0x89, b'P', b'N', b'G', 0x00, 0x01]));
+
+        let result = get_attachment(
+            &mock,
+            GetAttachmentArgs {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                attachment_id: "att-1".to_string(),
+                file_name: Some("screenshot.png".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_tool_output_has_warning(&result);
+        assert_eq!(result.content.len(), 2);
+        let resource = result.content[1]
+            .as_resource()
+            .expect("Second content block should be an embedded resource");
+        let json = serde_json::to_value(&resource.resource).unwrap();
+        assert_eq!(json["mimeType"], "image/png");
+        assert_eq!(json["uri"], "azdo://org/proj/attachments/att-1");
+        assert_eq!(json["blob"], "iVBORwAB");
+    }
+
+    #[tokio::test]
+    async fn test_get_work_item_history_returns_changes() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_work_item_updates()
+            .withf(|_, _, id| *id == 42)
+            .returning(|_, _, _| {
+                let update: WorkItemUpdate = serde_json::from_value(serde_json::json!({
+                    "id": 3,
+                    "rev": 3,
+                    "revisedBy": {"displayName": "Bob", "uniqueName": "bob@example.com"},
+                    "revisedDate": "9999-01-01T00:00:00Z",
+                    "fields": {
+                        "System.State": {"oldValue": "Resolved", "newValue": "Active"},
+                        "System.ChangedDate": {"newValue": "2025-01-05T10:30:00Z"}
+                    }
+                }))
+                .unwrap();
+                Ok(vec![update])
+            });
+
+        let result = get_work_item_history(
+            &mock,
+            GetWorkItemHistoryArgs {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                id: 42,
+                fields: vec![],
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_tool_output_has_warning(&result);
+        let text = extract_text_from_result(&result);
+        assert!(
+            text.contains("3,2025-01-05T10:30:00Z,Bob <bob@example.com>,State,Resolved,Active"),
+            "Output should contain the state change, got: {text}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_work_item_history_api_error_propagates() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_work_item_updates()
+            .returning(|_, _, _| Err(AzureError::ApiError("test error".to_string())));
+
+        let result = get_work_item_history(
+            &mock,
+            GetWorkItemHistoryArgs {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                id: 42,
+                fields: vec![],
+            },
+        )
+        .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_query_work_items_by_wiql_reports_paging() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_query_work_items()
+            .withf(|_, _, _, skip, top, _| *skip == 40 && *top == 20)
+            .returning(|_, _, _, skip, _, _| {
+                Ok(WorkItemQueryPage {
+                    work_items: vec![mock_work_item()],
+                    total_count: 523,
+                    skip,
+                    truncated: true,
+                    links: Vec::new(),
+                })
+            });
+
+        let result = query_work_items_by_wiql(
+            &mock,
+            QueryWorkItemsArgsWiql {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                query: "SELECT [System.Id] FROM WorkItems".to_string(),
+                include_latest_n_comments: None,
+                top: Some(20),
+                skip: None,
+                cursor: Some("40".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+        let text = extract_text_from_result(&result);
+        let content = text
+            .strip_prefix(UNTRUSTED_CONTENT_WARNING)
+            .unwrap_or(&text);
+        assert!(
+            content.contains("total_count=523,returned=1,skip=40,truncated=true,next_cursor=41"),
+            "Output should report paging, got: {content}"
+        );
+        assert!(content.contains("Test Work Item"));
+    }
+
+    #[tokio::test]
+    async fn test_query_work_items_by_wiql_renders_link_queries_as_hierarchy() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_query_work_items()
+            .returning(|_, _, _, skip, _, _| {
+                let mut parent = mock_work_item();
+                parent.id = 7;
+                parent.fields.insert(
+                    "System.WorkItemType".to_string(),
+                    serde_json::json!("Feature"),
+                );
+                parent
+                    .fields
+                    .insert("System.Title".to_string(), serde_json::json!("Payments"));
+                Ok(WorkItemQueryPage {
+                    work_items: vec![parent, mock_work_item()],
+                    total_count: 2,
+                    skip,
+                    truncated: false,
+                    links: vec![
+                        WorkItemLink {
+                            source: None,
+                            target: 7,
+                            link_type: None,
+                        },
+                        WorkItemLink {
+                            source: Some(7),
+                            target: 42,
+                            link_type: Some("System.LinkTypes.Hierarchy-Forward".to_string()),
+                        },
+                    ],
+                })
+            });
+
+        let result = query_work_items_by_wiql(
+            &mock,
+            QueryWorkItemsArgsWiql {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                query: "SELECT [System.Id] FROM WorkItemLinks WHERE [System.Links.LinkType] = 'System.LinkTypes.Hierarchy-Forward' MODE (Recursive)".to_string(),
+                include_latest_n_comments: None,
+                top: None,
+                skip: None,
+                cursor: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_tool_output_has_warning(&result);
+        let text = extract_text_from_result(&result);
+        assert!(
+            text.contains("hierarchy:\n7 Feature: Payments\n  42 Bug: Test Work Item\n"),
+            "{text}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_work_items_rejects_invalid_paging() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_query_work_items().times(0);
+
+        let result = query_work_items_by_wiql(
+            &mock,
+            QueryWorkItemsArgsWiql {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                query: "SELECT [System.Id] FROM WorkItems".to_string(),
+                include_latest_n_comments: None,
+                top: Some(5000),
+                skip: None,
+                cursor: None,
+            },
+        )
+        .await;
+
+        let error = result.expect_err("top above the maximum must be rejected");
+        assert_eq!(error.code.0, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_get_work_items_rejects_too_many_ids() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_work_items().times(0);
+
+        let result = get_work_items(
+            &mock,
+            GetWorkItemsArgs {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                ids: (1..=1001).collect(),
+                include_latest_n_comments: None,
+            },
+        )
+        .await;
+
+        let error = result.expect_err("more than 1000 ids must be rejected");
+        assert_eq!(error.code.0, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_link_work_items_resolves_friendly_link_type() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_list_work_item_relation_types()
+            .returning(|_| Ok(mock_relation_types()));
+        mock.expect_link_work_items()
+            .withf(|_, _, source_id, relation| {
+                *source_id == 42
+                    && relation.rel == "System.LinkTypes.Hierarchy-Reverse"
+                    && relation.url.ends_with("/_apis/wit/workitems/43")
+                    && relation.attributes.get("comment")
+                        == Some(&serde_json::json!("split from epic"))
+            })
+            .returning(|_, _, _, _| Ok(serde_json::json!({"id": 42})));
+
+        let result = link_work_items(
+            &mock,
+            LinkWorkItemsArgs {
+                link_type: Some("parent".to_string()),
+                comment: Some("split from epic".to_string()),
+                ..link_args()
+            },
+        )
+        .await;
+
+        assert!(result.is_ok(), "Parent link should be created: {result:?}");
+    }
+
+    #[tokio::test]
+    async fn test_link_work_items_rejects_unknown_link_type() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_list_work_item_relation_types()
+            .returning(|_| Ok(mock_relation_types()));
+        mock.expect_link_work_items().times(0);
+
+        let error = link_work_items(
+            &mock,
+            LinkWorkItemsArgs {
+                link_type: Some("Blocks".to_string()),
+                ..link_args()
+            },
+        )
+        .await
+        .expect_err("unknown link type must be rejected");
+
+        assert_eq!(error.code.0, -32602);
+        assert!(error.message.contains("Parent"), "{}", error.message);
+    }
+
+    #[tokio::test]
+    async fn test_link_work_items_creates_hyperlink_and_commit_links() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_repository()
+            .withf(|_, _, repository| repository == "backend")
+            .returning(|_, _, _| {
+                Ok(GitRepository {
+                    id: "repo-guid".to_string(),
+                    name: "backend".to_string(),
+                    project: GitProjectReference {
+                        id: "project-guid".to_string(),
+                        name: "proj".to_string(),
+                    },
+                })
+            });
+        mock.expect_link_work_items()
+            .withf(|_, _, _, relation| {
+                relation.rel == "Hyperlink" && relation.url == "https://example.com/spec"
+            })
+            .times(1)
+            .returning(|_, _, _, _| Ok(serde_json::json!({"id": 42})));
+        mock.expect_link_work_items()
+            .withf(|_, _, _, relation| {
+                relation.rel == "ArtifactLink"
+                    && relation.url == "vstfs:///Git/Commit/project-guid%2Frepo-guid%2F1a2b3c"
+                    && relation.attributes.get("name")
+                        == Some(&serde_json::json!("Fixed in Commit"))
+            })
+            .times(1)
+            .returning(|_, _, _, _| Ok(serde_json::json!({"id": 42})));
+
+        link_work_items(
+            &mock,
+            LinkWorkItemsArgs {
+                target_id: None,
+                link_type: None,
+                url: Some("https://example.com/spec".to_string()),
+                ..link_args()
+            },
+        )
+        .await
+        .unwrap();
+
+        link_work_items(
+            &mock,
+            LinkWorkItemsArgs {
+                target_id: None,
+                link_type: None,
+                repository: Some("backend".to_string()),
+                commit_id: Some("1a2b3c".to_string()),
+                ..link_args()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_link_work_items_requires_exactly_one_target() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_link_work_items().times(0);
+
+        let error = link_work_items(
+            &mock,
+            LinkWorkItemsArgs {
+                build_id: Some(12),
+                ..link_args()
+            },
+        )
+        .await
+        .expect_err("two targets must be rejected");
+
+        assert_eq!(error.code.0, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_link_work_items_rejects_repository_without_git_target() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_repository().times(0);
+        mock.expect_link_work_items().times(0);
+
+        let error = link_work_items(
+            &mock,
+            LinkWorkItemsArgs {
+                repository: Some("web".to_string()),
+                ..link_args()
+            },
+        )
+        .await
+        .expect_err("repository must be rejected for a work item target");
+
+        assert_eq!(error.code.0, -32602);
+        assert!(error.message.contains("repository"), "{}", error.message);
+    }
+
+    #[tokio::test]
+    async fn test_unlink_work_items_rejects_link_type_for_artifact_links() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_unlink_work_items().times(0);
+
+        let error = unlink_work_items(
+            &mock,
+            UnlinkWorkItemsArgs {
+                build_id: Some(12),
+                link_type: Some("Child".to_string()),
+                ..unlink_args()
+            },
+        )
+        .await
+        .expect_err("link_type must be rejected for a build link");
+
+        assert_eq!(error.code.0, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_unlink_work_items_removes_build_link() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_unlink_work_items()
+            .withf(|_, _, source_id, target_url, link_type| {
+                *source_id == 42 && target_url == "vstfs:///Build/Build/12" && link_type.is_none()
+            })
+            .returning(|_, _, _, _, _| Ok(Some(serde_json::json!({"id": 42, "rev": 5}))));
+
+        let result = unlink_work_items(
+            &mock,
+            UnlinkWorkItemsArgs {
+                build_id: Some(12),
+                ..unlink_args()
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_tool_output_has_warning(&result);
+        let text = extract_text_from_result(&result);
+        assert!(text.contains("rev:5"), "{text}");
+    }
+
+    #[tokio::test]
+    async fn test_unlink_work_items_reports_missing_link() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_list_work_item_relation_types()
+            .returning(|_| Ok(mock_relation_types()));
+        mock.expect_unlink_work_items()
+            .withf(|_, _, _, _, link_type| {
+                link_type.as_deref() == Some("System.LinkTypes.Hierarchy-Forward")
+            })
+            .returning(|_, _, _, _, _| Ok(None));
+
+        let result = unlink_work_items(
+            &mock,
+            UnlinkWorkItemsArgs {
+                target_id: Some(43),
+                link_type: Some("Child".to_string()),
+                ..unlink_args()
+            },
+        )
+        .await
+        .unwrap();
+
+        let text = extract_text_from_result(&result);
+        assert!(text.contains("No matching link found"), "{text}");
+    }
+
+    #[tokio::test]
+    async fn test_list_work_item_relations_returns_csv() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_work_item_relations().returning(|_, _, _| {
+            Ok(vec![
+                WorkItemRelation {
+                    rel: "System.LinkTypes.Hierarchy-Reverse".to_string(),
+                    url: "https://dev.azure.com/org/p-guid/_apis/wit/workItems/7".to_string(),
+                    attributes: HashMap::from([("comment".to_string(), serde_json::json!("epic"))]),
+                },
+                WorkItemRelation {
+                    rel: "ArtifactLink".to_string(),
+                    url: "vstfs:///Git/PullRequestId/p-guid%2Fr-guid%2F17".to_string(),
+                    attributes: HashMap::from([(
+                        "name".to_string(),
+                        serde_json::json!("Pull Request"),
+                    )]),
+                },
+                WorkItemRelation {
+                    rel: "Hyperlink".to_string(),
+                    url: "https://example.com/spec".to_string(),
+                    attributes: HashMap::new(),
+                },
+            ])
+        });
+        mock.expect_list_work_item_relation_types()
+            .returning(|_| Ok(mock_relation_types()));
+
+        let result = list_work_item_relations(
+            &mock,
+            ListWorkItemRelationsArgs {
+                organization: "org".to_string(),
+                project: "proj".to_string(),
+                work_item_id: 42,
+            },
+        )
+        .await
+        .unwrap();
+
+        let text = extract_text_from_result(&result);
+        let content = text
+            .strip_prefix(UNTRUSTED_CONTENT_WARNING)
+            .unwrap_or(&text);
+        assert!(content.contains("type,target,name,comment"), "{content}");
+        assert!(content.contains("Parent,7,,epic"), "{content}");
+        assert!(
+            content.contains("Artifact Link,pull_request 17,Pull Request,"),
+            "{content}"
+        );
+        assert!(
+            content.contains("Hyperlink,https://example.com/spec,,"),
+            "{content}"
+        );
+    }
+
+    fn update_title_args(expected_rev: Option<u32>) -> UpdateWorkItemArgs {
+        serde_json::from_value(serde_json::json!({
+            "organization": "org",
+            "project": "proj",
+            "id": 42,
+            "title": "Updated Title",
+            "expected_rev": expected_rev,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_update_work_item_passes_expected_rev() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_update_work_item()
+            .withf(|_, _, id, _, _, _, expected_rev| *id == 42 && *expected_rev == Some(7))
+            .returning(|_, _, _, _, _, _, _| Ok(mock_work_item()));
+
+        let result = update_work_item(&mock, update_title_args(Some(7)))
+            .await
+            .unwrap();
+        assert_eq!(result.is_error, Some(false));
+    }
+
+    #[tokio::test]
+    async fn test_update_work_item_conflict_returns_current_state() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_update_work_item()
+            .returning(|_, _, _, _, _, _, _| {
+                Err(AzureError::Conflict(
+                    "TF26071: This work item has been changed by someone else".to_string(),
+                ))
+            });
+        mock.expect_get_work_item()
+            .withf(|_, _, id, _| *id == 42)
+            .returning(|_, _, _, _| {
+                let mut work_item = mock_work_item();
+                work_item
+                    .fields
+                    .insert("System.Rev".to_string(), serde_json::json!(9));
+                Ok(Some(work_item))
+            });
+
+        let result = update_work_item(&mock, update_title_args(Some(7)))
+            .await
+            .expect("a conflict is reported as a tool result, not a protocol error");
+
+        assert_eq!(result.is_error, Some(true));
+        assert_tool_output_has_warning(&result);
+        let text = extract_text_from_result(&result);
+        assert!(text.contains("conflict:true"), "{text}");
+        assert!(text.contains("expected_rev:7"), "{text}");
+        assert!(text.contains("current_rev:9"), "{text}");
+        assert!(text.contains("Test Work Item"), "{text}");
+    }
+
+    #[tokio::test]
+    async fn test_update_work_item_maps_azure_errors_to_distinct_codes() {
+        let cases: [(fn() -> AzureError, i32); 2] = [
+            (|| AzureError::Unauthorized("expired".to_string()), -32001),
+            (
+                || AzureError::NotFound {
+                    type_key: None,
+                    message: "missing".to_string(),
+                },
+                -32002,
+            ),
+        ];
+        for (error, code) in cases {
+            let mut mock = MockAzureDevOpsApi::new();
+            mock.expect_update_work_item()
+                .returning(move |_, _, _, _, _, _, _| Err(error()));
+            let error = update_work_item(&mock, update_title_args(None))
+                .await
+                .unwrap_err();
+            assert_eq!(error.code.0, code, "{}", error.message);
+        }
+
+        // Reading the current state after a conflict keeps the error class
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_update_work_item()
+            .returning(|_, _, _, _, _, _, _| Err(AzureError::Conflict("TF26071".to_string())));
+        mock.expect_get_work_item()
+            .returning(|_, _, _, _| Err(AzureError::Forbidden("denied".to_string())));
+        let error = update_work_item(&mock, update_title_args(Some(7)))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code.0, -32003);
+        assert!(
+            error
+                .message
+                .starts_with("Update conflicted and reading the current state failed"),
+            "{}",
+            error.message
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_work_item_clears_fields() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_update_work_item()
+            .withf(|_, _, _, fields, clear_fields, _, _| {
+                fields.is_empty()
+                    && clear_fields
+                        == [
+                            "System.AssignedTo".to_string(),
+                            "System.IterationPath".to_string(),
+                        ]
+            })
+            .returning(|_, _, _, _, _, _, _| Ok(mock_work_item()));
+
+        let args: UpdateWorkItemArgs = serde_json::from_value(serde_json::json!({
+            "organization": "org",
+            "project": "proj",
+            "id": 42,
+            "clear_fields": ["assigned_to", "IterationPath"],
+        }))
+        .unwrap();
+        update_work_item(&mock, args).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_update_work_item_rejects_setting_and_clearing_same_field() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_update_work_item().times(0);
+
+        let args: UpdateWorkItemArgs = serde_json::from_value(serde_json::json!({
+            "organization": "org",
+            "project": "proj",
+            "id": 42,
+            "title": "New title",
+            "clear_fields": ["title"],
+        }))
+        .unwrap();
+        let error = update_work_item(&mock, args).await.unwrap_err();
+        assert_eq!(error.code.0, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_update_work_item_adds_and_removes_single_tags() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_work_item().returning(|_, _, _, _| {
+            let mut work_item = mock_work_item();
+            work_item.fields.insert(
+                "System.Tags".to_string(),
+                serde_json::json!("backend; flaky"),
+            );
+            work_item
+                .fields
+                .insert("System.Rev".to_string(), serde_json::json!(3));
+            Ok(Some(work_item))
+        });
+        mock.expect_update_work_item()
+            .withf(|_, _, _, fields, _, _, expected_rev| {
+                fields
+                    == [(
+                        "System.Tags".to_string(),
+                        serde_json::json!("backend; triaged"),
+                    )]
+                    && *expected_rev == Some(3)
+            })
+            .returning(|_, _, _, _, _, _, _| Ok(mock_work_item()));
+
+        let args: UpdateWorkItemArgs = serde_json::from_value(serde_json::json!({
+            "organization": "org",
+            "project": "proj",
+            "id": 42,
+            "add_tags": "triaged",
+            "remove_tags": "Flaky",
+        }))
+        .unwrap();
+        update_work_item(&mock, args).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_batch_work_items_submits_one_batch_and_reports_per_item() {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_batch_work_items()
+            .withf(|organization, requests| organization == "org" && requests.len() == 2)
+            .times(1)
+            .returning(|_, _| {
+                Ok(vec![
+                    WorkItemBatchResponse {
+                        code: 200,
+                        body: r#"{"id":101,"fields":{"System.Title":"Checkout"}}"#.to_string(),
+                    },
+                    WorkItemBatchResponse {
+                        code: 200,
+                        body: r#"{"id":102,"fields":{"System.Title":"Pay by card"}}"#.to_string(),
+                    },
+                ])
+            });
+
+        let args: BatchWorkItemsArgs = serde_json::from_value(serde_json::json!({
+            "organization": "org",
+            "project": "proj",
+            "operations": [
+                {"op": "create", "id": -1, "work_item_type": "Feature", "title": "Checkout"},
+                {"op": "create", "id": -2, "work_item_type": "User Story", "title": "Pay by card", "parent_id": -1}
+            ]
+        }))
+        .unwrap();
+        let result = batch_work_items(&mock, args).await.unwrap();
+
+        assert_tool_output_has_warning(&result);
+        let text = extract_text_from_result(&result);
+        assert!(text.contains("succeeded=2,failed=0"), "{text}");
+        assert!(text.contains("1,create,-2,102,200,Pay by card"), "{text}");
+    }
+
+    fn tree_item(
+        id: u32,
+        work_item_type: &str,
+        title: &str,
+        fields: serde_json::Value,
+        children: &[u32],
+    ) -> WorkItemWithRelations {
+        let mut item: WorkItemWithRelations = serde_json::from_value(serde_json::json!({
+            "id": id,
+            "fields": fields,
+            "relations": children.iter().map(|child| serde_json::json!({
+                "rel": "System.LinkTypes.Hierarchy-Forward",
+                "url": format!("https://dev.azure.com/org/p1/_apis/wit/workItems/{child}"),
+            })).collect::<Vec<_>>(),
+        }))
+        .unwrap();
+        item.fields
+            .entry("System.TeamProject".to_string())
+            .or_insert(serde_json::json!("proj"));
+        item.fields.insert(
+            "System.WorkItemType".to_string(),
+            serde_json::json!(work_item_type),
+        );
+        item.fields
+            .insert("System.Title".to_string(), serde_json::json!(title));
+        item
+    }
+
+    fn mock_tree(items: Vec<WorkItemWithRelations>) -> MockAzureDevOpsApi {
+        let mut mock = MockAzureDevOpsApi::new();
+        mock.expect_get_work_items_with_relations()
+            .returning(move |_, _, ids| {
+                Ok(items
+                    .iter()
+                    .filter(|item| ids.contains(&item.id))
+                    .cloned()
+                    .collect())
+            });
+        mock
+    }
+
+    fn tree_args(depth: Option<u32>) -> GetWorkItemTreeArgs {
+        GetWorkItemTreeArgs {
+            organization: "org".to_string(),
+            project: "proj".to_string(),
+            id: 1,
+            depth,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_work_item_tree_rolls_up_estimates() {
+        let mock = mock_tree(vec![
+            tree_item(
+                1,
+                "Epic",
+                "Checkout",
+                serde_json::json!({"System.State": "Active"}),
+                &[2],
+            ),
+            tree_item(
+                2,
+                "Feature",
+                "Payments",
+                serde_json::json!({"System.State": "Active"}),
+                &[3, 4, 9],
+            ),
+            tree_item(
+                3,
+                "User Story",
+                "Card form",
+                serde_json::json!({
+                    "System.State": "New",
+                    "System.AssignedTo": {"displayName": "Jane Doe", "uniqueName": "jane@contoso.com"},
+                    "Microsoft.VSTS.Scheduling.StoryPoints": 5.0
+                }),
+                &[],
+            ),
+            tree_item(
+                4,
+                "User Story",
+                "Refunds",
+                serde_json::json!({"System.State": "Active", "Microsoft.VSTS.Scheduling.StoryPoints": 3.0}),
+                &[5, 6, 1],
+            ),
+            tree_item(
+                5,
+                "Task",
+                "API",
+                serde_json::json!({"Microsoft.VSTS.Scheduling.RemainingWork": 4.0}),
+                &[],
+            ),
+            tree_item(
+                6,
+                "Task",
+                "UI",
+                serde_json::json!({"Microsoft.VSTS.Scheduling.RemainingWork": 2.5}),
+                &[],
+            ),
+            tree_item(
+                9,
+                "Bug",
+                "Elsewhere",
+                serde_json::json!({"System.TeamProject": "Other"}),
+                &[10],
+            ),
+        ]);
+
+        let result = get_work_item_tree(&mock, tree_args(None)).await.unwrap();
+
+        assert_tool_output_has_warning(&result);
+        let text = extract_text_from_result(&result);
+        let content = text
+            .strip_prefix(UNTRUSTED_CONTENT_WARNING)
+            .unwrap_or(&text);
+        assert_eq!(
+            content.trim_start(),
+            [
+                "root=1,depth=3,items=7,truncated=false,cycles=1,outside_project=1",
+                "1 Epic: Checkout (state=Active,story_points=8,remaining_work=6.5)",
+                "  2 Feature: Payments (state=Active,story_points=8,remaining_work=6.5)",
+                "    3 User Story: Card form (state=New,assigned_to=Jane Doe,story_points=5)",
+                "    4 User Story: Refunds (state=Active,story_points=3,remaining_work=6.5)",
+                "      5 Task: API (remaining_work=4)",
+                "      6 Task: UI (remaining_work=2.5)",
+                "      1 [cycle]",
+                "    9 Bug: Elsewhere [other project: Other, not rolled up]",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_work_item_tree_does_not_add_own_estimates_to_children() {
+        let mock = mock_tree(vec![
+            tree_item(1, "Epic", "Checkout", serde_json::json!({}), &[2]),
+            tree_item(
+                2,
+                "Feature",
+                "Payments",
+                serde_json::json!({"Microsoft.VSTS.Scheduling.Effort": 13.0}),
+                &[3, 4],
+            ),
+            tree_item(
+                3,
+                "User Story",
+                "Card form",
+                serde_json::json!({
+                    "Microsoft.VSTS.Scheduling.StoryPoints": 5.0,
+                    "Microsoft.VSTS.Scheduling.RemainingWork": 10.0
+                }),
+                &[5, 6],
+            ),
+            tree_item(
+                4,
+                "User Story",
+                "Refunds",
+                serde_json::json!({"Microsoft.VSTS.Scheduling.StoryPoints": 3.0}),
+                &[],
+            ),
+            tree_item(
+                5,
+                "Task",
+                "API",
+                serde_json::json!({"Microsoft.VSTS.Scheduling.RemainingWork": 4.0}),
+                &[],
+            ),
+            tree_item(
+                6,
+                "Task",
+                "UI",
+                serde_json::json!({"Microsoft.VSTS.Scheduling.RemainingWork": 2.0}),
+                &[],
+            ),
+        ]);
+
+        let result = get_work_item_tree(&mock, tree_args(None)).await.unwrap();
+        let text = extract_text_from_result(&result);
+        let content = text
+            .strip_prefix(UNTRUSTED_CONTENT_WARNING)
+            .unwrap_or(&text);
+        assert_eq!(
+            content.trim_start(),
+            [
+                "root=1,depth=3,items=6,truncated=false",
+                "1 Epic: Checkout (story_points=8,remaining_work=6)",
+                "  2 Feature: Payments (story_points=8,own_story_points=13,remaining_work=6)",
+                "    3 User Story: Card form (story_points=5,remaining_work=6,own_remaining_work=10)",
+                "      5 Task: API (remaining_work=4)",
+                "      6 Task: UI (remaining_work=2)",
+                "    4 User Story: Refunds (story_points=3)",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_work_item_tree_reports_depth_limit_and_missing_items() {
+        let mock = mock_tree(vec![
+            tree_item(1, "Epic", "Checkout", serde_json::json!({}), &[2, 3]),
+            tree_item(2, "Feature", "Payments", serde_json::json!({}), &[4]),
+        ]);
+
+        let result = get_work_item_tree(&mock, tree_args(Some(1))).await.unwrap();
+        let text = extract_text_from_result(&result);
+        assert!(text.contains("not_accessible=1"), "{text}");
+        assert!(
+            text.contains("  2 Feature: Payments (children_not_expanded=1)\n"),
+            "{text}"
+        );
+        assert!(text.contains("  3 [not found or not accessible]"), "{text}");
+
+        let mock = MockAzureDevOpsApi::new();
+        let error = get_work_item_tree(&mock, tree_args(Some(11)))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+    }
 }